@@ -0,0 +1,623 @@
+//! Headless subcommands for managing rooms without launching the TUI.
+//!
+//! These commands reuse the same `room::*` operations as the interactive
+//! interface so that scripts and the TUI create and remove worktrees the
+//! same way.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use serde::Serialize;
+
+use crate::config::{Config, Hooks};
+use crate::room::{
+    CreateRoomOptions, DirtyStatus, RoomInfo, create_room, delete_snapshot, discover_rooms,
+    remove_room_with_snapshot, rename_room,
+};
+use crate::state::{EventLog, Room, RoomsState, TransientStateStore, TrashedRoom};
+
+/// Exit code for usage errors (invalid or missing arguments).
+pub const EXIT_USAGE: u8 = 2;
+
+/// A headless subcommand parsed from the command line.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// List rooms discovered from git worktrees.
+    List { json: bool },
+
+    /// Create a new room.
+    Create {
+        name: Option<String>,
+        branch: Option<String>,
+        base_branch: Option<String>,
+        /// Commit-ish to check out detached (`--detach`).
+        commit: Option<String>,
+    },
+
+    /// Delete a room's worktree (the branch is kept).
+    Delete { name: String, force: bool },
+
+    /// Rename a room's worktree directory.
+    Rename {
+        current_name: String,
+        new_name: String,
+    },
+}
+
+/// Paths and configuration shared by all subcommands.
+pub struct Context {
+    pub repo_root: PathBuf,
+    pub rooms_dir: PathBuf,
+    pub primary_worktree: PathBuf,
+    pub config: Config,
+    /// Skip lifecycle hooks (`--no-hooks`).
+    pub skip_hooks: bool,
+}
+
+impl Context {
+    /// Hooks to run around room operations.
+    fn hooks(&self) -> Hooks {
+        if self.skip_hooks {
+            Hooks::default()
+        } else {
+            self.config.hooks.clone()
+        }
+    }
+}
+
+impl Command {
+    /// Parse a subcommand and its arguments.
+    ///
+    /// `name` is the subcommand itself (e.g. `create`) and `args` are the
+    /// remaining arguments that were not consumed as global options.
+    pub fn parse(name: &str, args: &[String]) -> Result<Self, String> {
+        match name {
+            "list" | "ls" => parse_list(args),
+            "create" | "add" => parse_create(args),
+            "delete" | "rm" => parse_delete(args),
+            "rename" | "mv" => {
+                let positional = positional_args(args, "rename")?;
+                match positional.as_slice() {
+                    [current, new] => Ok(Self::Rename {
+                        current_name: current.clone(),
+                        new_name: new.clone(),
+                    }),
+                    _ => Err("'rename' requires <NAME> and <NEW_NAME>".to_string()),
+                }
+            }
+            other => Err(format!("unknown command '{other}'")),
+        }
+    }
+}
+
+fn parse_list(args: &[String]) -> Result<Command, String> {
+    let mut json = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--json" => json = true,
+            "--format" => match option_value(args, &mut i)?.as_str() {
+                "json" => json = true,
+                "text" => json = false,
+                other => return Err(format!("unknown format '{other}' (expected json or text)")),
+            },
+            arg => return Err(format!("unexpected argument '{arg}' for 'list'")),
+        }
+        i += 1;
+    }
+
+    Ok(Command::List { json })
+}
+
+fn parse_create(args: &[String]) -> Result<Command, String> {
+    let mut name = None;
+    let mut branch = None;
+    let mut base_branch = None;
+    let mut commit = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--branch" | "-b" => branch = Some(option_value(args, &mut i)?),
+            "--base" => base_branch = Some(option_value(args, &mut i)?),
+            "--detach" => commit = Some(option_value(args, &mut i)?),
+            arg if arg.starts_with('-') => {
+                return Err(format!("unknown option '{arg}' for 'create'"));
+            }
+            arg => {
+                if name.is_some() {
+                    return Err(format!("unexpected argument '{arg}' for 'create'"));
+                }
+                name = Some(arg.to_string());
+            }
+        }
+        i += 1;
+    }
+
+    if commit.is_some() && (branch.is_some() || base_branch.is_some()) {
+        return Err("'--detach' can't be combined with '--branch' or '--base'".to_string());
+    }
+
+    Ok(Command::Create {
+        name,
+        branch,
+        base_branch,
+        commit,
+    })
+}
+
+fn parse_delete(args: &[String]) -> Result<Command, String> {
+    let mut name = None;
+    let mut force = false;
+
+    for arg in args {
+        match arg.as_str() {
+            "--force" | "-f" => force = true,
+            arg if arg.starts_with('-') => {
+                return Err(format!("unknown option '{arg}' for 'delete'"));
+            }
+            arg => {
+                if name.is_some() {
+                    return Err(format!("unexpected argument '{arg}' for 'delete'"));
+                }
+                name = Some(arg.to_string());
+            }
+        }
+    }
+
+    let name = name.ok_or_else(|| "'delete' requires <NAME>".to_string())?;
+    Ok(Command::Delete { name, force })
+}
+
+fn option_value(args: &[String], i: &mut usize) -> Result<String, String> {
+    let option = &args[*i];
+    *i += 1;
+    args.get(*i)
+        .cloned()
+        .ok_or_else(|| format!("{option} requires a value"))
+}
+
+fn positional_args(args: &[String], command: &str) -> Result<Vec<String>, String> {
+    if let Some(option) = args.iter().find(|arg| arg.starts_with('-')) {
+        return Err(format!("unknown option '{option}' for '{command}'"));
+    }
+    Ok(args.to_vec())
+}
+
+/// Run a headless subcommand.
+pub fn run(command: Command, ctx: &Context) -> ExitCode {
+    let event_log = EventLog::new(&ctx.rooms_dir);
+
+    match command {
+        Command::List { json } => run_list(ctx, json),
+        Command::Create {
+            name,
+            branch,
+            base_branch,
+            commit,
+        } => {
+            let options = CreateRoomOptions {
+                name,
+                branch,
+                base_branch: base_branch.or_else(|| ctx.config.base_branch.clone()),
+                commit,
+                ports: ctx.config.ports.clone(),
+                files: ctx.config.files.clone(),
+                dependency_dirs: ctx.config.dependency_dirs.clone(),
+            };
+            match create_room(&ctx.repo_root, &ctx.rooms_dir, options, &ctx.hooks()) {
+                Ok(created) => {
+                    event_log.log_room_created(&created.name);
+                    update_state(ctx, &event_log, |state| {
+                        state.record_created(
+                            &created.name,
+                            created.branch.as_deref(),
+                            &created.path,
+                            created.base_branch.clone(),
+                        );
+                    });
+                    match (&created.branch, &created.upstream) {
+                        (Some(branch), Some(upstream)) => eprintln!(
+                            "Created room '{}' on branch '{branch}' tracking '{upstream}'",
+                            created.name
+                        ),
+                        (Some(branch), None) => {
+                            eprintln!("Created room '{}' on branch '{branch}'", created.name)
+                        }
+                        (None, _) => eprintln!("Created detached room '{}'", created.name),
+                    }
+                    if let Some(err) = &created.files_error {
+                        warn(&event_log, &created.name, &err.to_string());
+                    }
+                    println!("{}", created.path.display());
+                    ExitCode::SUCCESS
+                }
+                Err(e) => fail(&event_log, None, &format!("failed to create room: {e}")),
+            }
+        }
+        Command::Delete { name, force } => {
+            // Snapshotted like in the TUI, so even `--force` can be undone there
+            match remove_room_with_snapshot(
+                &ctx.repo_root,
+                &ctx.rooms_dir,
+                &name,
+                &ctx.rooms_dir.join(&name),
+                recorded_base_branch(ctx, &name).as_deref(),
+                force,
+                &ctx.hooks(),
+            ) {
+                Ok((removed, snapshot)) => {
+                    event_log.log_room_deleted(&name);
+                    let snapshotted = snapshot.is_some();
+                    let changes_ref = snapshot
+                        .as_ref()
+                        .and_then(|snapshot| snapshot.changes_ref.clone());
+                    let mut dropped = Vec::new();
+                    let trashed = update_state(ctx, &event_log, |state| {
+                        let metadata = state.remove_by_name(&name);
+                        if let Some(snapshot) = snapshot {
+                            dropped = state.record_trashed(TrashedRoom {
+                                name: name.clone(),
+                                branch: snapshot.branch,
+                                head: snapshot.head,
+                                changes_ref: snapshot.changes_ref,
+                                base_branch: metadata
+                                    .as_ref()
+                                    .and_then(|room| room.base_branch.clone()),
+                                notes: metadata.and_then(|room| room.notes),
+                                deleted_at: chrono::Utc::now(),
+                            });
+                        }
+                    });
+                    // Without a trash entry, nothing would ever restore or delete the snapshot
+                    let unreachable = changes_ref.filter(|_| !trashed);
+                    for changes_ref in dropped
+                        .into_iter()
+                        .filter_map(|room| room.changes_ref)
+                        .chain(unreachable)
+                    {
+                        let _ = delete_snapshot(&ctx.repo_root, &changes_ref);
+                    }
+                    if trashed && snapshotted {
+                        eprintln!(
+                            "Deleted room '{name}' (branch kept; press u in the TUI to undo)"
+                        );
+                    } else {
+                        eprintln!("Deleted room '{name}' (branch kept)");
+                    }
+                    if let Some(err) = removed.post_hook_error {
+                        warn(&event_log, &name, &format!("post_delete hook {err}"));
+                    }
+                    ExitCode::SUCCESS
+                }
+                Err(e) => fail(
+                    &event_log,
+                    Some(&name),
+                    &format!("failed to delete room: {e}"),
+                ),
+            }
+        }
+        Command::Rename {
+            current_name,
+            new_name,
+        } => match rename_room(
+            &ctx.repo_root,
+            &ctx.rooms_dir,
+            &current_name,
+            &new_name,
+            recorded_base_branch(ctx, &current_name).as_deref(),
+            &ctx.hooks(),
+        ) {
+            Ok(renamed) => {
+                event_log.log_room_renamed(&current_name, &new_name);
+                update_state(ctx, &event_log, |state| {
+                    state.record_renamed(&current_name, &new_name, &renamed.path);
+                });
+                eprintln!("Renamed room: {current_name} -> {new_name}");
+                if let Some(err) = renamed.post_hook_error {
+                    warn(&event_log, &new_name, &format!("post_rename hook {err}"));
+                }
+                ExitCode::SUCCESS
+            }
+            Err(e) => fail(
+                &event_log,
+                Some(&current_name),
+                &format!("failed to rename room: {e}"),
+            ),
+        },
+    }
+}
+
+/// The branch `room_name` was created from, if `state.json` recorded one.
+fn recorded_base_branch(ctx: &Context, room_name: &str) -> Option<String> {
+    RoomsState::load_from_rooms_dir(&ctx.rooms_dir)
+        .ok()?
+        .find_by_name(room_name)?
+        .base_branch
+        .clone()
+}
+
+/// Load `state.json`, apply `update`, and save it back under its lock.
+///
+/// Returns false if the state couldn't be loaded or saved. State failures
+/// are logged but never fail the command: the worktree operation has
+/// already succeeded at this point.
+fn update_state(ctx: &Context, event_log: &EventLog, update: impl FnOnce(&mut RoomsState)) -> bool {
+    let mut state = RoomsState::default();
+    let _lock = match state.reload_locked(&ctx.rooms_dir) {
+        Ok(lock) => lock,
+        Err(e) => {
+            event_log.log_error(None, &format!("Failed to load room state: {e}"));
+            eprintln!("warning: failed to load room state: {e}");
+            return false;
+        }
+    };
+    update(&mut state);
+    if let Err(e) = state.save_to_rooms_dir(&ctx.rooms_dir) {
+        event_log.log_error(None, &format!("Failed to save room state: {e}"));
+        eprintln!("warning: failed to save room state: {e}");
+        return false;
+    }
+    true
+}
+
+/// A room as emitted by `rooms list --json`.
+#[derive(Debug, Serialize)]
+struct RoomListEntry<'a> {
+    #[serde(flatten)]
+    room: &'a RoomInfo,
+
+    /// Uncommitted changes in the worktree, or null if they couldn't be checked.
+    dirty: Option<DirtyStatus>,
+
+    /// Persisted metadata from `state.json`, or null if the room isn't tracked.
+    metadata: Option<&'a Room>,
+}
+
+fn run_list(ctx: &Context, json: bool) -> ExitCode {
+    let transient = TransientStateStore::new();
+    let rooms = match discover_rooms(
+        &ctx.repo_root,
+        &ctx.rooms_dir,
+        Some(&ctx.primary_worktree),
+        &transient,
+    ) {
+        Ok(rooms) => rooms,
+        Err(e) => {
+            eprintln!("error: failed to discover rooms: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if json {
+        let state = RoomsState::load_from_rooms_dir(&ctx.rooms_dir).unwrap_or_default();
+        let entries: Vec<RoomListEntry> = rooms
+            .iter()
+            .map(|room| RoomListEntry {
+                room,
+                dirty: DirtyStatus::check(&room.path).ok(),
+                metadata: state.find_by_name(&room.name),
+            })
+            .collect();
+        return match serde_json::to_string_pretty(&entries) {
+            Ok(output) => {
+                println!("{output}");
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("error: failed to serialize rooms: {e}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    for line in format_room_table(&rooms) {
+        println!("{line}");
+    }
+    ExitCode::SUCCESS
+}
+
+/// Format rooms as aligned `name  branch  path` lines.
+fn format_room_table(rooms: &[RoomInfo]) -> Vec<String> {
+    let name_width = rooms.iter().map(|r| r.name.len()).max().unwrap_or(0);
+    let branch_width = rooms
+        .iter()
+        .map(|r| room_branch_label(r).len())
+        .max()
+        .unwrap_or(0);
+
+    rooms
+        .iter()
+        .map(|room| {
+            let mut line = format!(
+                "{:name_width$}  {:branch_width$}  {}",
+                room.name,
+                room_branch_label(room),
+                room.path.display()
+            );
+            if room.is_primary {
+                line.push_str("  [primary]");
+            }
+            if room.is_prunable {
+                line.push_str("  [prunable]");
+            }
+            line
+        })
+        .collect()
+}
+
+fn room_branch_label(room: &RoomInfo) -> String {
+    match &room.branch {
+        Some(branch) => branch.clone(),
+        None => format!("({})", room.branch_label()),
+    }
+}
+
+fn fail(event_log: &EventLog, room_name: Option<&str>, message: &str) -> ExitCode {
+    event_log.log_error(room_name, message);
+    eprintln!("error: {message}");
+    ExitCode::FAILURE
+}
+
+/// Log and print a problem that doesn't fail the command.
+fn warn(event_log: &EventLog, room_name: &str, message: &str) {
+    event_log.log_error(Some(room_name), message);
+    eprintln!("warning: {message}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::room::RoomStatus;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(
+            Command::parse("list", &[]),
+            Ok(Command::List { json: false })
+        );
+        assert!(Command::parse("list", &args(&["extra"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_list_json() {
+        assert_eq!(
+            Command::parse("list", &args(&["--json"])),
+            Ok(Command::List { json: true })
+        );
+        assert_eq!(
+            Command::parse("list", &args(&["--format", "json"])),
+            Ok(Command::List { json: true })
+        );
+        assert!(Command::parse("list", &args(&["--format", "yaml"])).is_err());
+    }
+
+    #[test]
+    fn test_room_list_entry_flattens_room() {
+        let room = RoomInfo {
+            name: "quick-fox".to_string(),
+            branch: Some("quick-fox".to_string()),
+            head: None,
+            path: PathBuf::from("/work/quick-fox"),
+            status: RoomStatus::Ready,
+            is_prunable: false,
+            last_error: None,
+            is_primary: false,
+        };
+        let entry = RoomListEntry {
+            room: &room,
+            dirty: None,
+            metadata: None,
+        };
+
+        let json = serde_json::to_value(&entry).unwrap();
+        assert_eq!(json["name"], "quick-fox");
+        assert_eq!(json["branch"], "quick-fox");
+        assert_eq!(json["status"], "ready");
+        assert!(json["dirty"].is_null());
+        assert!(json["metadata"].is_null());
+    }
+
+    #[test]
+    fn test_parse_create_with_options() {
+        let command = Command::parse(
+            "create",
+            &args(&["my-room", "-b", "feature", "--base", "main"]),
+        );
+        assert_eq!(
+            command,
+            Ok(Command::Create {
+                name: Some("my-room".to_string()),
+                branch: Some("feature".to_string()),
+                base_branch: Some("main".to_string()),
+                commit: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_create_detached() {
+        assert_eq!(
+            Command::parse("create", &args(&["--detach", "v1.2.0"])),
+            Ok(Command::Create {
+                name: None,
+                branch: None,
+                base_branch: None,
+                commit: Some("v1.2.0".to_string()),
+            })
+        );
+        assert!(Command::parse("create", &args(&["--detach", "v1.2.0", "-b", "x"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_create_missing_option_value() {
+        assert!(Command::parse("create", &args(&["--branch"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_delete() {
+        assert_eq!(
+            Command::parse("delete", &args(&["old", "--force"])),
+            Ok(Command::Delete {
+                name: "old".to_string(),
+                force: true,
+            })
+        );
+        assert!(Command::parse("delete", &[]).is_err());
+    }
+
+    #[test]
+    fn test_parse_rename_requires_two_names() {
+        assert!(Command::parse("rename", &args(&["only-one"])).is_err());
+        assert_eq!(
+            Command::parse("rename", &args(&["a", "b"])),
+            Ok(Command::Rename {
+                current_name: "a".to_string(),
+                new_name: "b".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_unknown_command() {
+        assert!(Command::parse("frobnicate", &[]).is_err());
+    }
+
+    #[test]
+    fn test_format_room_table_aligns_columns() {
+        let rooms = vec![
+            RoomInfo {
+                name: "repo".to_string(),
+                branch: Some("main".to_string()),
+                head: None,
+                path: PathBuf::from("/work/repo"),
+                status: RoomStatus::Ready,
+                is_prunable: false,
+                last_error: None,
+                is_primary: true,
+            },
+            RoomInfo {
+                name: "quick-fox".to_string(),
+                branch: None,
+                head: Some("abc123def456".to_string()),
+                path: PathBuf::from("/work/quick-fox"),
+                status: RoomStatus::Ready,
+                is_prunable: false,
+                last_error: None,
+                is_primary: false,
+            },
+        ];
+
+        let lines = format_room_table(&rooms);
+        assert_eq!(
+            lines[0],
+            "repo       main                  /work/repo  [primary]"
+        );
+        assert_eq!(lines[1], "quick-fox  (detached @ abc123d)  /work/quick-fox");
+    }
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
@@ -15,14 +16,200 @@ pub enum ConfigError {
 
     #[error("failed to parse config file: {0}")]
     Parse(#[from] serde_json::Error),
+
+    #[error("invalid config: {0}")]
+    Invalid(String),
+}
+
+/// How `post_create` hooks are run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookMode {
+    /// Type the commands into the room's shell.
+    #[default]
+    Terminal,
+    /// Run the commands as subprocesses and wait for them before opening the room.
+    Process,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Hooks {
+    /// How `post_create` is run.
+    #[serde(default)]
+    pub mode: HookMode,
+    /// Run before the worktree is created; a failure aborts the creation.
+    #[serde(default, deserialize_with = "deserialize_hook_commands")]
+    pub pre_create: Vec<String>,
+    /// Run after the worktree is created.
     #[serde(default, deserialize_with = "deserialize_hook_commands")]
     pub post_create: Vec<String>,
+    /// Typed into a room's shell when its first terminal is opened.
     #[serde(default, deserialize_with = "deserialize_hook_commands")]
     pub post_enter: Vec<String>,
+    /// Run when a room's last terminal tab is closed, and for open rooms on
+    /// quit, delete and rename.
+    #[serde(default, deserialize_with = "deserialize_hook_commands")]
+    pub on_leave: Vec<String>,
+    /// Run before the worktree is removed; a failure aborts the deletion.
+    #[serde(default, deserialize_with = "deserialize_hook_commands")]
+    pub pre_delete: Vec<String>,
+    /// Run in the repository root after the worktree is removed.
+    #[serde(default, deserialize_with = "deserialize_hook_commands")]
+    pub post_delete: Vec<String>,
+    /// Run in the moved worktree after a rename.
+    #[serde(default, deserialize_with = "deserialize_hook_commands")]
+    pub post_rename: Vec<String>,
+}
+
+/// A long-running process started in each room, such as a dev server.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Service {
+    /// Name shown in the sidebar badge and tab bar. Must be unique.
+    pub name: String,
+
+    /// Command run with `$SHELL -c` in the room's worktree.
+    pub command: String,
+
+    /// Extra environment variables for the command.
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+
+    /// Start the service when a room's first shell is opened.
+    #[serde(default = "default_true")]
+    pub autostart: bool,
+}
+
+/// Session restore behaviour on startup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Restore {
+    /// Re-spawn shells in rooms that were active when the app last quit.
+    #[serde(default = "default_true")]
+    pub sessions: bool,
+
+    /// Run `post_enter` hooks in restored shells.
+    #[serde(default)]
+    pub post_enter: bool,
+}
+
+impl Default for Restore {
+    fn default() -> Self {
+        Self {
+            sessions: true,
+            post_enter: false,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// A local file copied from the primary worktree into each new room.
+///
+/// Written as `"path"` to copy a file to the same place, or as
+/// `{ "from": ..., "to": ... }`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "RoomFileSpec")]
+pub struct RoomFile {
+    /// Path in the primary worktree.
+    pub from: String,
+    /// Path in the new room.
+    pub to: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RoomFileSpec {
+    Path(String),
+    Copy { from: String, to: String },
+}
+
+impl From<RoomFileSpec> for RoomFile {
+    fn from(spec: RoomFileSpec) -> Self {
+        match spec {
+            RoomFileSpec::Path(path) => Self {
+                from: path.clone(),
+                to: path,
+            },
+            RoomFileSpec::Copy { from, to } => Self { from, to },
+        }
+    }
+}
+
+/// How a dependency directory is brought into a new room.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShareMode {
+    /// Copy-on-write clone where supported, else a tree of hard links, else a copy.
+    #[default]
+    Clone,
+    /// Symlink to the primary worktree's directory.
+    Symlink,
+}
+
+/// A gitignored directory, such as `node_modules`, shared from the primary
+/// worktree with each new room.
+///
+/// Written as `"path"` to clone it, or as `{ "path": ..., "mode": ... }`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "DependencyDirSpec")]
+pub struct DependencyDir {
+    /// Path relative to the worktree root.
+    pub path: String,
+    /// How the directory is shared.
+    pub mode: ShareMode,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DependencyDirSpec {
+    Path(String),
+    Entry {
+        path: String,
+        #[serde(default)]
+        mode: ShareMode,
+    },
+}
+
+impl From<DependencyDirSpec> for DependencyDir {
+    fn from(spec: DependencyDirSpec) -> Self {
+        match spec {
+            DependencyDirSpec::Path(path) => Self {
+                path,
+                mode: ShareMode::default(),
+            },
+            DependencyDirSpec::Entry { path, mode } => Self { path, mode },
+        }
+    }
+}
+
+/// Ports reserved for each room.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ports {
+    /// First port handed out.
+    #[serde(default = "default_port_start")]
+    pub start: u16,
+
+    /// Number of consecutive ports each room gets.
+    #[serde(default = "default_ports_per_room")]
+    pub per_room: u16,
+}
+
+impl Default for Ports {
+    fn default() -> Self {
+        Self {
+            start: default_port_start(),
+            per_room: default_ports_per_room(),
+        }
+    }
+}
+
+fn default_port_start() -> u16 {
+    20000
+}
+
+fn default_ports_per_room() -> u16 {
+    10
 }
 
 /// Application configuration loaded from .roomsrc.json.
@@ -40,6 +227,26 @@ pub struct Config {
     /// Hooks to run for room lifecycle events.
     #[serde(default)]
     pub hooks: Hooks,
+
+    /// What to restore from the previous run on startup.
+    #[serde(default)]
+    pub restore: Restore,
+
+    /// Background processes supervised per room.
+    #[serde(default)]
+    pub services: Vec<Service>,
+
+    /// Port blocks reserved for each room.
+    #[serde(default)]
+    pub ports: Ports,
+
+    /// Local files copied into new rooms.
+    #[serde(default)]
+    pub files: Vec<RoomFile>,
+
+    /// Dependency directories cloned or symlinked into new rooms.
+    #[serde(default)]
+    pub dependency_dirs: Vec<DependencyDir>,
 }
 
 fn default_rooms_dir() -> String {
@@ -52,6 +259,11 @@ impl Default for Config {
             base_branch: None,
             rooms_dir: default_rooms_dir(),
             hooks: Hooks::default(),
+            restore: Restore::default(),
+            services: Vec::new(),
+            ports: Ports::default(),
+            files: Vec::new(),
+            dependency_dirs: Vec::new(),
         }
     }
 }
@@ -69,9 +281,43 @@ impl Config {
 
         let contents = std::fs::read_to_string(path)?;
         let config: Config = serde_json::from_str(&contents)?;
+        config.validate()?;
         Ok(config)
     }
 
+    /// Check constraints serde can't express.
+    fn validate(&self) -> Result<(), ConfigError> {
+        let mut names = std::collections::HashSet::new();
+        for service in &self.services {
+            if service.name.trim().is_empty() {
+                return Err(ConfigError::Invalid(
+                    "service name cannot be empty".to_string(),
+                ));
+            }
+            if !names.insert(service.name.as_str()) {
+                return Err(ConfigError::Invalid(format!(
+                    "duplicate service name '{}'",
+                    service.name
+                )));
+            }
+        }
+        if self.ports.per_room == 0 {
+            return Err(ConfigError::Invalid(
+                "ports.per_room must be at least 1".to_string(),
+            ));
+        }
+        let file_paths = self.files.iter().flat_map(|file| [&file.from, &file.to]);
+        let dir_paths = self.dependency_dirs.iter().map(|dir| &dir.path);
+        for path in file_paths.chain(dir_paths) {
+            if !is_relative_inside(path) {
+                return Err(ConfigError::Invalid(format!(
+                    "path '{path}' must be relative to the worktree"
+                )));
+            }
+        }
+        Ok(())
+    }
+
     /// Load configuration from the default location within a repository.
     pub fn load_from_primary<P: AsRef<Path>>(primary_worktree: P) -> Result<Self, ConfigError> {
         let config_path = primary_worktree.as_ref().join(CONFIG_FILE);
@@ -100,6 +346,15 @@ impl Config {
     }
 }
 
+/// Whether `path` is relative and stays inside the directory it's joined to.
+fn is_relative_inside(path: &str) -> bool {
+    let path = Path::new(path);
+    path.components().next().is_some()
+        && path
+            .components()
+            .all(|component| matches!(component, std::path::Component::Normal(_)))
+}
+
 fn deserialize_hook_commands<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
 where
     D: Deserializer<'de>,
@@ -137,6 +392,9 @@ mod tests {
         assert!(config.base_branch.is_none());
         assert!(config.hooks.post_create.is_empty());
         assert!(config.hooks.post_enter.is_empty());
+        assert!(config.restore.sessions);
+        assert!(!config.restore.post_enter);
+        assert!(config.services.is_empty());
     }
 
     #[test]
@@ -155,6 +413,10 @@ mod tests {
   "hooks": {
     "post_create": ["npm install", "make setup"],
     "post_enter": "ls -la"
+  },
+  "restore": {
+    "sessions": false,
+    "post_enter": true
   }
 }
 "#;
@@ -166,6 +428,8 @@ mod tests {
         assert_eq!(config.hooks.post_create[1], "make setup");
         assert_eq!(config.hooks.post_enter.len(), 1);
         assert_eq!(config.hooks.post_enter[0], "ls -la");
+        assert!(!config.restore.sessions);
+        assert!(config.restore.post_enter);
     }
 
     #[test]
@@ -181,6 +445,108 @@ mod tests {
         assert_eq!(path, PathBuf::from("/"));
     }
 
+    #[test]
+    fn test_parse_hook_mode() {
+        let config = Config::default();
+        assert_eq!(config.hooks.mode, HookMode::Terminal);
+
+        let json = r#"{"hooks": {"mode": "process", "post_create": "npm install"}}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(config.hooks.mode, HookMode::Process);
+
+        let json = r#"{"hooks": {"mode": "background"}}"#;
+        assert!(serde_json::from_str::<Config>(json).is_err());
+    }
+
+    #[test]
+    fn test_parse_lifecycle_hooks() {
+        let json = r#"{
+  "hooks": {
+    "pre_create": "docker info",
+    "on_leave": "docker compose stop",
+    "pre_delete": ["docker compose down", "dropdb --if-exists app_$ROOMS_ROOM"],
+    "post_delete": "echo removed",
+    "post_rename": "direnv allow"
+  }
+}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(config.hooks.pre_create, vec!["docker info"]);
+        assert_eq!(config.hooks.on_leave, vec!["docker compose stop"]);
+        assert_eq!(config.hooks.pre_delete.len(), 2);
+        assert_eq!(config.hooks.post_delete, vec!["echo removed"]);
+        assert_eq!(config.hooks.post_rename, vec!["direnv allow"]);
+        assert!(config.hooks.post_create.is_empty());
+    }
+
+    #[test]
+    fn test_parse_ports() {
+        let config = Config::default();
+        assert_eq!(config.ports, Ports::default());
+
+        let json = r#"{"ports": {"start": 4000}}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(config.ports.start, 4000);
+        assert_eq!(config.ports.per_room, 10);
+
+        let json = r#"{"ports": {"per_room": 0}}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_parse_files() {
+        let json = r#"{"files": [".env", {"from": ".env.example", "to": "api/.env"}]}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config.files,
+            vec![
+                RoomFile {
+                    from: ".env".to_string(),
+                    to: ".env".to_string(),
+                },
+                RoomFile {
+                    from: ".env.example".to_string(),
+                    to: "api/.env".to_string(),
+                },
+            ]
+        );
+        assert!(config.validate().is_ok());
+
+        for path in ["../secrets", "/etc/hosts", ""] {
+            let config = Config {
+                files: vec![RoomFile {
+                    from: ".env".to_string(),
+                    to: path.to_string(),
+                }],
+                ..Default::default()
+            };
+            assert!(config.validate().is_err(), "{path} should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_parse_dependency_dirs() {
+        let json = r#"{"dependency_dirs": ["node_modules", {"path": ".venv", "mode": "symlink"}]}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config.dependency_dirs,
+            vec![
+                DependencyDir {
+                    path: "node_modules".to_string(),
+                    mode: ShareMode::Clone,
+                },
+                DependencyDir {
+                    path: ".venv".to_string(),
+                    mode: ShareMode::Symlink,
+                },
+            ]
+        );
+
+        let json = r#"{"dependency_dirs": ["../cache"]}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_deserialize_hook_null() {
         let json = r#"{"hooks": {"post_create": null}}"#;
@@ -226,4 +592,38 @@ mod tests {
         let result: Result<Config, _> = serde_json::from_str(json);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_parse_services() {
+        let json = r#"
+{
+  "services": [
+    {"name": "web", "command": "npm run dev", "env": {"PORT": "3000"}},
+    {"name": "watch", "command": "cargo watch", "autostart": false}
+  ]
+}
+"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(config.services.len(), 2);
+        let web = &config.services[0];
+        assert_eq!(web.name, "web");
+        assert_eq!(web.command, "npm run dev");
+        assert_eq!(web.env.get("PORT").map(String::as_str), Some("3000"));
+        assert!(web.autostart);
+        assert!(!config.services[1].autostart);
+    }
+
+    #[test]
+    fn test_load_rejects_duplicate_service_names() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join(CONFIG_FILE);
+        std::fs::write(
+            &path,
+            r#"{"services": [{"name": "web", "command": "a"}, {"name": "web", "command": "b"}]}"#,
+        )
+        .unwrap();
+
+        let result = Config::load(&path);
+        assert!(matches!(result, Err(ConfigError::Invalid(_))));
+    }
 }
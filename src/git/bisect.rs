@@ -0,0 +1,263 @@
+use std::fs;
+use std::path::Path;
+
+use super::command::{CommandError, GitCommand};
+
+/// A verdict on the commit currently checked out by `git bisect`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BisectMark {
+    Good,
+    Bad,
+    Skip,
+}
+
+impl BisectMark {
+    /// The `git bisect` subcommand for this mark.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Good => "good",
+            Self::Bad => "bad",
+            Self::Skip => "skip",
+        }
+    }
+}
+
+/// Progress of a `git bisect` session in a worktree.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BisectStatus {
+    /// Current step, starting at 1.
+    pub step: usize,
+    /// Revisions left to test after the current one, once good and bad are known.
+    pub revisions_left: Option<usize>,
+    /// Rough number of steps left, once good and bad are known.
+    pub steps_left: Option<usize>,
+    /// SHA of the first bad commit, once found.
+    pub first_bad: Option<String>,
+}
+
+/// Start bisecting in `worktree_path` between a `bad` and a `good` commit-ish.
+///
+/// Git checks out the first commit to test. Bisect state is per worktree,
+/// so other worktrees are unaffected.
+///
+/// # Errors
+///
+/// Returns an error if git fails, e.g. when a ref doesn't exist.
+pub fn bisect_start_from<P: AsRef<Path>>(
+    worktree_path: P,
+    bad: &str,
+    good: &str,
+) -> Result<(), CommandError> {
+    GitCommand::new("bisect")
+        .args(&["start", bad, good])
+        .current_dir(worktree_path.as_ref())
+        .run_checked()?;
+    Ok(())
+}
+
+/// Mark the checked out commit in `worktree_path` and move to the next one.
+///
+/// # Errors
+///
+/// Returns an error if git fails, e.g. when no bisect is in progress.
+pub fn bisect_mark_from<P: AsRef<Path>>(
+    worktree_path: P,
+    mark: BisectMark,
+) -> Result<(), CommandError> {
+    GitCommand::new("bisect")
+        .arg(mark.as_str())
+        .current_dir(worktree_path.as_ref())
+        .run_checked()?;
+    Ok(())
+}
+
+/// End the bisect in `worktree_path` and check out the commit it started from.
+///
+/// # Errors
+///
+/// Returns an error if git fails, e.g. when local changes block the checkout.
+pub fn bisect_reset_from<P: AsRef<Path>>(worktree_path: P) -> Result<(), CommandError> {
+    GitCommand::new("bisect")
+        .arg("reset")
+        .current_dir(worktree_path.as_ref())
+        .run_checked()?;
+    Ok(())
+}
+
+/// Read the bisect progress of `worktree_path`, or `None` if it isn't bisecting.
+///
+/// # Errors
+///
+/// Returns an error if git fails to execute.
+pub fn bisect_status_from<P: AsRef<Path>>(
+    worktree_path: P,
+) -> Result<Option<BisectStatus>, CommandError> {
+    let worktree_path = worktree_path.as_ref();
+    let log_path = GitCommand::new("rev-parse")
+        .args(&["--git-path", "BISECT_LOG"])
+        .current_dir(worktree_path)
+        .run_checked()?;
+    let Ok(log) = fs::read_to_string(worktree_path.join(log_path.stdout)) else {
+        return Ok(None);
+    };
+
+    let mut status = parse_bisect_log(&log);
+    if status.first_bad.is_none() {
+        let refs = GitCommand::new("for-each-ref")
+            .args(&["--format=%(refname)", "refs/bisect"])
+            .current_dir(worktree_path)
+            .run_checked()?;
+        let good: Vec<&str> = refs
+            .stdout
+            .lines()
+            .filter(|refname| refname.starts_with("refs/bisect/good-"))
+            .collect();
+        let has_bad = refs
+            .stdout
+            .lines()
+            .any(|refname| refname == "refs/bisect/bad");
+        if has_bad && !good.is_empty() {
+            let mut args = vec!["--bisect-vars", "refs/bisect/bad", "--not"];
+            args.extend(good);
+            let vars = GitCommand::new("rev-list")
+                .args(&args)
+                .current_dir(worktree_path)
+                .run_checked()?;
+            status.revisions_left = bisect_var(&vars.stdout, "bisect_nr");
+            status.steps_left = bisect_var(&vars.stdout, "bisect_steps");
+        }
+    }
+    Ok(Some(status))
+}
+
+/// Count the marks made so far and find the result in a `BISECT_LOG`.
+fn parse_bisect_log(log: &str) -> BisectStatus {
+    let marks = log
+        .lines()
+        .filter(|line| {
+            ["good", "bad", "skip"]
+                .iter()
+                .any(|mark| line.starts_with(&format!("git bisect {mark}")))
+        })
+        .count();
+    let first_bad = log
+        .lines()
+        .rev()
+        .filter_map(|line| line.strip_prefix("# first bad commit: ["))
+        .filter_map(|rest| rest.split_once(']'))
+        .map(|(sha, _)| sha.to_string())
+        .next();
+    BisectStatus {
+        step: marks + 1,
+        first_bad,
+        ..Default::default()
+    }
+}
+
+/// Read a numeric `name=value` line from `git rev-list --bisect-vars` output.
+fn bisect_var(vars: &str, name: &str) -> Option<usize> {
+    vars.lines()
+        .filter_map(|line| line.split_once('='))
+        .find(|(key, _)| *key == name)
+        .and_then(|(_, value)| value.trim_matches('\'').parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+    }
+
+    #[test]
+    fn test_parse_bisect_log() {
+        let log = "\
+# bad: [6c36194] c20
+# good: [44e3d02] c1
+git bisect start 'HEAD' 'v1'
+# good: [7fa31d8] c10
+git bisect good 7fa31d8
+# bad: [1190a70] c15
+git bisect bad 1190a70
+";
+        let status = parse_bisect_log(log);
+        assert_eq!(status.step, 3);
+        assert_eq!(status.first_bad, None);
+
+        let done = format!("{log}# first bad commit: [1190a70] c15\n");
+        assert_eq!(
+            parse_bisect_log(&done).first_bad,
+            Some("1190a70".to_string())
+        );
+    }
+
+    #[test]
+    fn test_bisect_var() {
+        let vars = "bisect_rev='7fa31d8'\nbisect_nr=9\nbisect_all=19\nbisect_steps=3";
+        assert_eq!(bisect_var(vars, "bisect_nr"), Some(9));
+        assert_eq!(bisect_var(vars, "bisect_steps"), Some(3));
+        assert_eq!(bisect_var(vars, "missing"), None);
+    }
+
+    #[test]
+    fn test_bisect_session() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let repo = temp_dir.path();
+        git(repo, &["init"]);
+        git(repo, &["config", "user.email", "test@test.com"]);
+        git(repo, &["config", "user.name", "Test"]);
+        for i in 0..8 {
+            git(repo, &["commit", "--allow-empty", "-m", &format!("c{i}")]);
+        }
+
+        assert_eq!(bisect_status_from(repo).unwrap(), None);
+        let head = |rev: &str| {
+            let output = Command::new("git")
+                .args(["rev-parse", rev])
+                .current_dir(repo)
+                .output()
+                .unwrap();
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        };
+        let start = head("HEAD");
+        let first_bad = Command::new("git")
+            .args(["rev-parse", "HEAD~6"])
+            .current_dir(repo)
+            .output()
+            .unwrap();
+        let first_bad = String::from_utf8_lossy(&first_bad.stdout)
+            .trim()
+            .to_string();
+
+        bisect_start_from(repo, "HEAD", "HEAD~7").unwrap();
+        let status = bisect_status_from(repo).unwrap().unwrap();
+        assert_eq!(status.step, 1);
+        assert!(status.revisions_left.is_some());
+        assert_eq!(status.first_bad, None);
+
+        // Every commit after the good one is bad
+        let mut status = status;
+        for _ in 0..8 {
+            if status.first_bad.is_some() {
+                break;
+            }
+            bisect_mark_from(repo, BisectMark::Bad).unwrap();
+            status = bisect_status_from(repo).unwrap().unwrap();
+        }
+        assert_eq!(status.first_bad, Some(first_bad));
+        assert!(status.step > 1);
+
+        bisect_reset_from(repo).unwrap();
+        assert_eq!(bisect_status_from(repo).unwrap(), None);
+        assert_eq!(head("HEAD"), start);
+
+        assert!(bisect_mark_from(temp_dir.path().join("missing"), BisectMark::Good).is_err());
+    }
+}
@@ -0,0 +1,287 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+
+use super::command::{CommandError, GitCommand};
+
+/// Remote that wins when several remotes have the same branch.
+const PREFERRED_REMOTE: &str = "origin";
+
+/// A local or remote-tracking branch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Branch {
+    /// Short ref name, such as `main` or `origin/main`.
+    pub name: String,
+    /// Remote name for remote-tracking branches.
+    pub remote: Option<String>,
+    /// Time of the last commit.
+    pub committed_at: Option<DateTime<Utc>>,
+    /// Worktree that has the branch checked out, for local branches.
+    pub worktree: Option<PathBuf>,
+}
+
+impl Branch {
+    /// Name of the local branch, without the remote for remote-tracking branches.
+    pub fn local_name(&self) -> &str {
+        match &self.remote {
+            Some(remote) => &self.name[remote.len() + 1..],
+            None => &self.name,
+        }
+    }
+}
+
+/// List local and remote-tracking branches, most recently committed first.
+///
+/// Symbolic refs such as `origin/HEAD` are skipped.
+///
+/// # Errors
+///
+/// Returns an error if git fails to execute or the refs can't be listed.
+pub fn list_branches_from<P: AsRef<Path>>(repo_path: P) -> Result<Vec<Branch>, CommandError> {
+    let result = GitCommand::new("for-each-ref")
+        .args(&[
+            "--sort=-committerdate",
+            "--format=%(refname)%09%(symref)%09%(committerdate:unix)%09%(worktreepath)",
+            "refs/heads",
+            "refs/remotes",
+        ])
+        .current_dir(repo_path.as_ref())
+        .run_checked()?;
+    let remotes = list_remotes_from(repo_path.as_ref())?;
+
+    Ok(result
+        .stdout
+        .lines()
+        .filter_map(|line| parse_branch_line(line, &remotes))
+        .collect())
+}
+
+/// Parse a `for-each-ref` line, splitting remote-tracking refs at one of `remotes`.
+fn parse_branch_line(line: &str, remotes: &[String]) -> Option<Branch> {
+    let mut fields = line.split('\t');
+    let refname = fields.next()?;
+    let symref = fields.next().unwrap_or_default();
+    if !symref.is_empty() {
+        return None;
+    }
+    let committed_at = fields
+        .next()
+        .and_then(|timestamp| timestamp.parse().ok())
+        .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0));
+    let worktree = fields
+        .next()
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from);
+
+    if let Some(name) = refname.strip_prefix("refs/heads/") {
+        return Some(Branch {
+            name: name.to_string(),
+            remote: None,
+            committed_at,
+            worktree,
+        });
+    }
+    let name = refname.strip_prefix("refs/remotes/")?;
+    // Remote names can contain slashes, so the longest one that matches wins
+    let remote = remotes
+        .iter()
+        .filter(|remote| {
+            name.strip_prefix(remote.as_str())
+                .is_some_and(|rest| rest.len() > 1 && rest.starts_with('/'))
+        })
+        .max_by_key(|remote| remote.len())?;
+    Some(Branch {
+        name: name.to_string(),
+        remote: Some(remote.to_string()),
+        committed_at,
+        worktree: None,
+    })
+}
+
+/// Check `name` against git's branch name rules (`git check-ref-format --branch`).
+///
+/// Returns the name as git resolves it, or `None` if it isn't a valid
+/// branch name. Slashes and uppercase are kept as they are.
+///
+/// # Errors
+///
+/// Returns an error if git fails to execute.
+pub fn check_branch_name_from<P: AsRef<Path>>(
+    repo_path: P,
+    name: &str,
+) -> Result<Option<String>, CommandError> {
+    let result = GitCommand::new("check-ref-format")
+        .args(&["--branch", name])
+        .current_dir(repo_path.as_ref())
+        .run()?;
+
+    Ok(result.success().then_some(result.stdout))
+}
+
+/// Find the locally fetched remote-tracking branch for `branch`.
+///
+/// Returns the short ref name, such as `origin/feature-x`. When several
+/// remotes have the branch, `origin` is preferred, then the first remote
+/// in `git remote` order. Nothing is fetched.
+///
+/// # Errors
+///
+/// Returns an error if git fails to execute or the remotes can't be listed.
+pub fn find_remote_branch_from<P: AsRef<Path>>(
+    repo_path: P,
+    branch: &str,
+) -> Result<Option<String>, CommandError> {
+    let mut remotes = list_remotes_from(repo_path.as_ref())?;
+    remotes.sort_by_key(|remote| remote != PREFERRED_REMOTE);
+
+    for remote in remotes {
+        let result = GitCommand::new("rev-parse")
+            .args(&[
+                "--verify",
+                "--quiet",
+                &format!("refs/remotes/{remote}/{branch}"),
+            ])
+            .current_dir(repo_path.as_ref())
+            .run()?;
+        if result.success() {
+            return Ok(Some(format!("{remote}/{branch}")));
+        }
+    }
+    Ok(None)
+}
+
+/// Names of the repository's remotes, in `git remote` order.
+fn list_remotes_from(repo_path: &Path) -> Result<Vec<String>, CommandError> {
+    let result = GitCommand::new("remote")
+        .current_dir(repo_path)
+        .run_checked()?;
+
+    Ok(result.stdout.lines().map(str::to_string).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+    }
+
+    #[test]
+    fn test_parse_branch_line() {
+        let remotes = vec![
+            "origin".to_string(),
+            "team".to_string(),
+            "team/upstream".to_string(),
+        ];
+        let local = parse_branch_line(
+            "refs/heads/feature/login\t\t1700000000\t/src/app-login",
+            &remotes,
+        )
+        .unwrap();
+        assert_eq!(local.name, "feature/login");
+        assert_eq!(local.local_name(), "feature/login");
+        assert_eq!(local.remote, None);
+        assert_eq!(local.committed_at.unwrap().timestamp(), 1700000000);
+        assert_eq!(local.worktree, Some(PathBuf::from("/src/app-login")));
+
+        let remote = parse_branch_line(
+            "refs/remotes/origin/feature/login\t\t1700000000\t",
+            &remotes,
+        )
+        .unwrap();
+        assert_eq!(remote.name, "origin/feature/login");
+        assert_eq!(remote.local_name(), "feature/login");
+        assert_eq!(remote.remote, Some("origin".to_string()));
+        assert_eq!(remote.worktree, None);
+
+        let nested = parse_branch_line("refs/remotes/team/upstream/main\t\t1\t", &remotes).unwrap();
+        assert_eq!(nested.remote, Some("team/upstream".to_string()));
+        assert_eq!(nested.local_name(), "main");
+        let team = parse_branch_line("refs/remotes/team/main\t\t1\t", &remotes).unwrap();
+        assert_eq!(team.remote, Some("team".to_string()));
+        assert_eq!(team.local_name(), "main");
+
+        assert!(
+            parse_branch_line(
+                "refs/remotes/origin/HEAD\trefs/remotes/origin/main\t1\t",
+                &remotes
+            )
+            .is_none()
+        );
+        assert!(parse_branch_line("refs/tags/v1\t\t1\t", &remotes).is_none());
+        // Left behind by a remote that no longer exists
+        assert!(parse_branch_line("refs/remotes/gone/main\t\t1\t", &remotes).is_none());
+    }
+
+    #[test]
+    fn test_check_branch_name() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        git(temp_dir.path(), &["init"]);
+
+        assert_eq!(
+            check_branch_name_from(temp_dir.path(), "feature/JIRA-123_login").unwrap(),
+            Some("feature/JIRA-123_login".to_string())
+        );
+        for invalid in [
+            "bad..name",
+            "-leading",
+            "with space",
+            "trailing/",
+            "lock.lock",
+        ] {
+            assert_eq!(
+                check_branch_name_from(temp_dir.path(), invalid).unwrap(),
+                None,
+                "{invalid} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_find_remote_branch_prefers_origin() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let upstream = temp_dir.path().join("upstream");
+        let clone = temp_dir.path().join("clone");
+        std::fs::create_dir(&upstream).unwrap();
+        git(&upstream, &["init"]);
+        git(&upstream, &["config", "user.email", "test@test.com"]);
+        git(&upstream, &["config", "user.name", "Test"]);
+        git(&upstream, &["commit", "--allow-empty", "-m", "initial"]);
+        git(&upstream, &["branch", "feature-x"]);
+        git(temp_dir.path(), &["clone", "--quiet", "upstream", "clone"]);
+        git(&clone, &["remote", "add", "alpha", "../upstream"]);
+        git(&clone, &["fetch", "--quiet", "alpha"]);
+        git(&clone, &["remote", "add", "team/upstream", "../upstream"]);
+        git(&clone, &["fetch", "--quiet", "team/upstream"]);
+
+        assert_eq!(
+            find_remote_branch_from(&clone, "feature-x").unwrap(),
+            Some("origin/feature-x".to_string())
+        );
+        assert_eq!(find_remote_branch_from(&clone, "missing").unwrap(), None);
+
+        let branches = list_branches_from(&clone).unwrap();
+        let names: Vec<&str> = branches.iter().map(|branch| branch.name.as_str()).collect();
+        assert!(names.contains(&"origin/feature-x"));
+        assert!(names.contains(&"alpha/feature-x"));
+        let nested = branches
+            .iter()
+            .find(|branch| branch.name == "team/upstream/feature-x")
+            .unwrap();
+        assert_eq!(nested.remote.as_deref(), Some("team/upstream"));
+        assert_eq!(nested.local_name(), "feature-x");
+        assert!(!names.contains(&"origin/HEAD"));
+        let current = branches
+            .iter()
+            .find(|branch| branch.remote.is_none())
+            .unwrap();
+        assert!(current.worktree.is_some());
+    }
+}
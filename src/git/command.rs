@@ -1,6 +1,7 @@
 // Allow dead code for now - these utilities will be used in later implementation steps
 #![allow(dead_code)]
 
+use std::ffi::{OsStr, OsString};
 use std::path::Path;
 use std::process::Command;
 use thiserror::Error;
@@ -41,6 +42,7 @@ pub enum CommandError {
 pub struct GitCommand {
     args: Vec<String>,
     working_dir: Option<String>,
+    envs: Vec<(String, OsString)>,
 }
 
 impl GitCommand {
@@ -49,6 +51,7 @@ impl GitCommand {
         Self {
             args: vec![subcommand.to_string()],
             working_dir: None,
+            envs: Vec::new(),
         }
     }
 
@@ -70,10 +73,18 @@ impl GitCommand {
         self
     }
 
+    /// Set an environment variable for the command.
+    pub fn env<V: AsRef<OsStr>>(mut self, key: &str, value: V) -> Self {
+        self.envs
+            .push((key.to_string(), value.as_ref().to_os_string()));
+        self
+    }
+
     /// Execute the command and return a structured result.
     pub fn run(self) -> Result<CommandResult, CommandError> {
         let mut cmd = Command::new("git");
         cmd.args(&self.args);
+        cmd.envs(self.envs.iter().map(|(key, value)| (key, value)));
 
         if let Some(ref dir) = self.working_dir {
             cmd.current_dir(dir);
@@ -1,7 +1,16 @@
+mod bisect;
+mod branch;
 pub mod command;
 mod repo;
+mod status;
 mod worktree;
 
-pub use repo::{get_primary_worktree_path_from, get_repo_root};
+pub use bisect::{
+    BisectMark, BisectStatus, bisect_mark_from, bisect_reset_from, bisect_start_from,
+    bisect_status_from,
+};
+pub use branch::{Branch, check_branch_name_from, find_remote_branch_from, list_branches_from};
+pub use repo::{get_git_common_dir_from, get_primary_worktree_path_from, get_repo_root};
+pub use status::{AheadBehind, ahead_behind_from, list_stash_branches_from};
 #[allow(unused_imports)] // Worktree will be used in later steps
 pub use worktree::{Worktree, list_worktrees_from, prune_worktrees_from};
@@ -60,6 +60,27 @@ pub fn get_repo_root_from<P: AsRef<std::path::Path>>(path: P) -> Result<PathBuf,
 /// - Git command fails to execute
 pub fn get_primary_worktree_path_from<P: AsRef<std::path::Path>>(
     repo_root: P,
+) -> Result<PathBuf, CommandError> {
+    let mut common_dir = get_git_common_dir_from(repo_root)?;
+    if common_dir.file_name().and_then(|n| n.to_str()) == Some(".git")
+        && let Some(parent) = common_dir.parent()
+    {
+        common_dir = parent.to_path_buf();
+    }
+
+    Ok(common_dir)
+}
+
+/// Get the absolute path of the git directory shared by all worktrees
+/// (`git rev-parse --git-common-dir`), e.g. `/src/app/.git`.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The path is not inside a git repository
+/// - Git command fails to execute
+pub fn get_git_common_dir_from<P: AsRef<std::path::Path>>(
+    repo_root: P,
 ) -> Result<PathBuf, CommandError> {
     let result = GitCommand::new("rev-parse")
         .args(&["--path-format=absolute", "--git-common-dir"])
@@ -72,14 +93,7 @@ pub fn get_primary_worktree_path_from<P: AsRef<std::path::Path>>(
         });
     }
 
-    let mut common_dir = PathBuf::from(result.stdout);
-    if common_dir.file_name().and_then(|n| n.to_str()) == Some(".git")
-        && let Some(parent) = common_dir.parent()
-    {
-        common_dir = parent.to_path_buf();
-    }
-
-    Ok(common_dir)
+    Ok(PathBuf::from(result.stdout))
 }
 
 #[cfg(test)]
@@ -0,0 +1,136 @@
+use std::path::Path;
+
+use super::command::{CommandError, GitCommand};
+
+/// Commits on each side of two diverged refs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AheadBehind {
+    /// Commits on HEAD that the other ref doesn't have.
+    pub ahead: usize,
+    /// Commits on the other ref that HEAD doesn't have.
+    pub behind: usize,
+}
+
+impl AheadBehind {
+    /// Whether both sides point at the same history.
+    pub fn is_even(&self) -> bool {
+        self.ahead == 0 && self.behind == 0
+    }
+}
+
+/// Count commits between HEAD of `worktree_path` and `other` (e.g. `@{upstream}` or `main`).
+///
+/// Returns `None` when `other` doesn't resolve, such as a branch without an
+/// upstream or a detached HEAD.
+///
+/// # Errors
+///
+/// Returns an error if git fails to execute.
+pub fn ahead_behind_from<P: AsRef<Path>>(
+    worktree_path: P,
+    other: &str,
+) -> Result<Option<AheadBehind>, CommandError> {
+    let result = GitCommand::new("rev-list")
+        .args(&["--left-right", "--count", &format!("HEAD...{other}")])
+        .current_dir(worktree_path.as_ref())
+        .run()?;
+    if !result.success() {
+        return Ok(None);
+    }
+    Ok(parse_left_right_count(&result.stdout))
+}
+
+/// List the branch each stash entry was made on, newest first.
+///
+/// Stashes are shared by all worktrees of a repository; the branch in the
+/// stash message is the only link back to a worktree. Entries made on a
+/// detached HEAD have `None`.
+///
+/// # Errors
+///
+/// Returns an error if git fails to execute or the stash can't be listed.
+pub fn list_stash_branches_from<P: AsRef<Path>>(
+    repo_path: P,
+) -> Result<Vec<Option<String>>, CommandError> {
+    let result = GitCommand::new("stash")
+        .args(&["list", "--format=%gs"])
+        .current_dir(repo_path.as_ref())
+        .run_checked()?;
+    Ok(result.stdout.lines().map(parse_stash_branch).collect())
+}
+
+fn parse_left_right_count(output: &str) -> Option<AheadBehind> {
+    let (ahead, behind) = output.trim().split_once('\t')?;
+    Some(AheadBehind {
+        ahead: ahead.parse().ok()?,
+        behind: behind.parse().ok()?,
+    })
+}
+
+/// Extract the branch from a stash subject such as `WIP on main: abc123 msg`.
+fn parse_stash_branch(subject: &str) -> Option<String> {
+    let rest = subject
+        .strip_prefix("WIP on ")
+        .or_else(|| subject.strip_prefix("On "))?;
+    let (branch, _) = rest.split_once(':')?;
+    (branch != "(no branch)").then(|| branch.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+    }
+
+    #[test]
+    fn test_parse_stash_branch() {
+        assert_eq!(
+            parse_stash_branch("WIP on feature/login: abc123d fix"),
+            Some("feature/login".to_string())
+        );
+        assert_eq!(
+            parse_stash_branch("On main: before rebase"),
+            Some("main".to_string())
+        );
+        assert_eq!(parse_stash_branch("WIP on (no branch): abc123d fix"), None);
+        assert_eq!(parse_stash_branch("autostash"), None);
+    }
+
+    #[test]
+    fn test_ahead_behind_and_stashes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let repo = temp_dir.path();
+        git(repo, &["init", "-b", "main"]);
+        git(repo, &["config", "user.email", "test@test.com"]);
+        git(repo, &["config", "user.name", "Test"]);
+        git(repo, &["commit", "--allow-empty", "-m", "initial"]);
+        git(repo, &["branch", "base"]);
+        git(repo, &["commit", "--allow-empty", "-m", "one"]);
+        git(repo, &["commit", "--allow-empty", "-m", "two"]);
+
+        assert_eq!(
+            ahead_behind_from(repo, "base").unwrap(),
+            Some(AheadBehind {
+                ahead: 2,
+                behind: 0
+            })
+        );
+        assert_eq!(ahead_behind_from(repo, "@{upstream}").unwrap(), None);
+
+        assert!(list_stash_branches_from(repo).unwrap().is_empty());
+        std::fs::write(repo.join("file.txt"), "wip").unwrap();
+        git(repo, &["stash", "push", "--include-untracked"]);
+        assert_eq!(
+            list_stash_branches_from(repo).unwrap(),
+            vec![Some("main".to_string())]
+        );
+    }
+}
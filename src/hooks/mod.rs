@@ -0,0 +1,468 @@
+//! Lifecycle hooks run as supervised subprocesses.
+//!
+//! These hooks don't go through a room's terminal: each command runs with
+//! `$SHELL -c` with its output captured, and the first failing command stops
+//! the run. A command that runs longer than [`HOOK_TIMEOUT`] is killed and
+//! counts as failed. The room is described to the command through `ROOMS_*`
+//! environment variables and `{placeholder}` templates.
+
+use std::collections::BTreeMap;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex, mpsc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use thiserror::Error;
+
+use crate::state::PortBlock;
+use crate::terminal::user_shell;
+
+/// Number of output lines kept from a failed hook.
+pub const OUTPUT_TAIL_LINES: usize = 20;
+
+/// How long a hook command may run before it is killed.
+pub const HOOK_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// How long to keep reading output after a command exits.
+///
+/// A process the command left running in the background can hold the
+/// output pipe open indefinitely.
+const OUTPUT_GRACE: Duration = Duration::from_millis(500);
+
+/// How often a running command is checked for exit.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Errors from running hook commands.
+#[derive(Error, Debug)]
+pub enum HookError {
+    #[error("failed to run '{command}': {source}")]
+    Spawn {
+        command: String,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("'{command}' {}", describe_exit(*.exit_code))]
+    Failed {
+        command: String,
+        /// Exit code, or `None` if the command was killed by a signal.
+        exit_code: Option<i32>,
+        /// Last lines of combined stdout and stderr.
+        output: String,
+    },
+
+    #[error("'{command}' timed out after {}s and was killed", .timeout.as_secs())]
+    TimedOut {
+        command: String,
+        timeout: Duration,
+        /// Last lines of combined stdout and stderr.
+        output: String,
+    },
+}
+
+impl HookError {
+    /// Captured output of the failing command, if it ran.
+    pub fn output(&self) -> Option<&str> {
+        match self {
+            HookError::Spawn { .. } => None,
+            HookError::Failed { output, .. } | HookError::TimedOut { output, .. } => Some(output),
+        }
+    }
+}
+
+/// The room a hook runs for.
+#[derive(Debug, Clone)]
+pub struct HookContext {
+    /// Room name.
+    pub room: String,
+    /// Worktree path (it may not exist yet, or any more).
+    pub path: PathBuf,
+    /// Branch checked out in the room, if any.
+    pub branch: Option<String>,
+    /// Path to the primary worktree.
+    pub primary: PathBuf,
+    /// Branch the room's branch was created from, if known.
+    pub base_branch: Option<String>,
+    /// Ports reserved for the room, if any.
+    pub ports: Option<PortBlock>,
+}
+
+impl HookContext {
+    /// Values exposed to commands, as `(placeholder, environment variable, value)`.
+    fn values(&self) -> Vec<(String, String, String)> {
+        let mut values: Vec<(String, String, String)> = [
+            ("room", "ROOMS_ROOM", self.room.clone()),
+            (
+                "branch",
+                "ROOMS_BRANCH",
+                self.branch.clone().unwrap_or_default(),
+            ),
+            (
+                "path",
+                "ROOMS_PATH",
+                self.path.to_string_lossy().into_owned(),
+            ),
+            (
+                "primary",
+                "ROOMS_PRIMARY",
+                self.primary.to_string_lossy().into_owned(),
+            ),
+            (
+                "base_branch",
+                "ROOMS_BASE_BRANCH",
+                self.base_branch.clone().unwrap_or_default(),
+            ),
+        ]
+        .into_iter()
+        .map(|(placeholder, name, value)| (placeholder.to_string(), name.to_string(), value))
+        .collect();
+
+        if let Some(block) = self.ports {
+            values.push((
+                "port".to_string(),
+                "ROOMS_PORT".to_string(),
+                block.start.to_string(),
+            ));
+            for (index, port) in block.ports().enumerate() {
+                values.push((
+                    format!("port_{index}"),
+                    format!("ROOMS_PORT_{index}"),
+                    port.to_string(),
+                ));
+            }
+        }
+        values
+    }
+
+    /// Environment variables describing the room.
+    ///
+    /// `PORT` is set to the room's first port, for tools that read it.
+    pub fn env(&self) -> BTreeMap<String, String> {
+        let mut env: BTreeMap<String, String> = self
+            .values()
+            .into_iter()
+            .map(|(_, name, value)| (name, value))
+            .collect();
+        if let Some(block) = self.ports {
+            env.insert("PORT".to_string(), block.start.to_string());
+        }
+        env
+    }
+
+    /// Replace `{room}`, `{branch}`, `{path}`, `{primary}`, `{base_branch}`,
+    /// `{port}` and `{port_N}` in `command`. Other braces are left alone.
+    ///
+    /// Values are shell-quoted, since a branch name can contain `;`, `$(...)`
+    /// or backticks.
+    pub fn expand(&self, command: &str) -> String {
+        self.substitute(command, shell_quote)
+    }
+
+    /// Like [`HookContext::expand`], but inserts values verbatim, for text
+    /// that isn't run by a shell.
+    pub fn expand_text(&self, text: &str) -> String {
+        self.substitute(text, |value| value.to_string())
+    }
+
+    fn substitute(&self, command: &str, format_value: impl Fn(&str) -> String) -> String {
+        let values = self.values();
+        let mut expanded = String::with_capacity(command.len());
+        let mut rest = command;
+        while let Some(start) = rest.find('{') {
+            expanded.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let value = rest.find('}').and_then(|end| {
+                values
+                    .iter()
+                    .find(|(placeholder, _, _)| *placeholder == rest[1..end])
+                    .map(|(_, _, value)| (end, value))
+            });
+            match value {
+                Some((end, value)) => {
+                    expanded.push_str(&format_value(value));
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    expanded.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        expanded.push_str(rest);
+        expanded
+    }
+}
+
+/// Quote `value` for a POSIX shell. Values made only of safe characters are
+/// left as they are; anything else is single-quoted, with `'` written as `'\''`.
+fn shell_quote(value: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "_-./:=@%+,".contains(c);
+    if !value.is_empty() && value.chars().all(safe) {
+        return value.to_string();
+    }
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+fn describe_exit(exit_code: Option<i32>) -> String {
+    match exit_code {
+        Some(code) => format!("exited with code {}", code),
+        None => "was terminated by a signal".to_string(),
+    }
+}
+
+/// Run `commands` in order in `cwd`, stopping at the first failure.
+///
+/// Placeholders in each command are expanded from `context` first.
+pub fn run_commands<P: AsRef<Path>>(
+    commands: &[String],
+    cwd: P,
+    context: &HookContext,
+) -> Result<(), HookError> {
+    for command in commands {
+        run_command(
+            &context.expand(command),
+            cwd.as_ref(),
+            context,
+            HOOK_TIMEOUT,
+        )?;
+    }
+    Ok(())
+}
+
+fn run_command(
+    command: &str,
+    cwd: &Path,
+    context: &HookContext,
+    timeout: Duration,
+) -> Result<(), HookError> {
+    let spawn_error = |source| HookError::Spawn {
+        command: command.to_string(),
+        source,
+    };
+
+    // Share one pipe between stdout and stderr so the output stays interleaved.
+    let (mut reader, writer) = io::pipe().map_err(spawn_error)?;
+    let stderr = writer.try_clone().map_err(spawn_error)?;
+    let mut child = Command::new(user_shell())
+        .arg("-c")
+        .arg(command)
+        .current_dir(cwd)
+        .envs(context.env())
+        .stdin(Stdio::null())
+        .stdout(writer)
+        .stderr(stderr)
+        .spawn()
+        .map_err(spawn_error)?;
+
+    // Read on another thread, so a pipe held open by a background process
+    // can't keep us from noticing that the command exited.
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let (done_tx, done_rx) = mpsc::channel();
+    let collected = Arc::clone(&output);
+    thread::spawn(move || {
+        let mut buf = [0; 4096];
+        while let Ok(n @ 1..) = reader.read(&mut buf) {
+            collected
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .extend_from_slice(&buf[..n]);
+        }
+        let _ = done_tx.send(());
+    });
+    let captured = || {
+        let _ = done_rx.recv_timeout(OUTPUT_GRACE);
+        let output = output
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        output_tail(&String::from_utf8_lossy(&output), OUTPUT_TAIL_LINES)
+    };
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(spawn_error)? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(HookError::TimedOut {
+                command: command.to_string(),
+                timeout,
+                output: captured(),
+            });
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+    if status.success() {
+        return Ok(());
+    }
+
+    Err(HookError::Failed {
+        command: command.to_string(),
+        exit_code: status.code(),
+        output: captured(),
+    })
+}
+
+/// The last `lines` lines of `output`.
+fn output_tail(output: &str, lines: usize) -> String {
+    let all: Vec<&str> = output.lines().collect();
+    all[all.len().saturating_sub(lines)..].join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(path: &Path) -> HookContext {
+        HookContext {
+            room: "quick-fox".to_string(),
+            path: path.to_path_buf(),
+            branch: Some("feature/fox".to_string()),
+            primary: PathBuf::from("/src/app"),
+            base_branch: None,
+            ports: Some(PortBlock {
+                start: 20010,
+                count: 3,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_run_commands_in_worktree() {
+        let dir = tempfile::tempdir().unwrap();
+        let commands = vec![
+            "echo one > first".to_string(),
+            "cp first second".to_string(),
+        ];
+
+        run_commands(&commands, dir.path(), &context(dir.path())).unwrap();
+        assert!(dir.path().join("second").exists());
+    }
+
+    #[test]
+    fn test_run_commands_stops_at_first_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let commands = vec![
+            "echo installing; echo broken >&2; exit 4".to_string(),
+            "touch never".to_string(),
+        ];
+
+        let err = run_commands(&commands, dir.path(), &context(dir.path())).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "'echo installing; echo broken >&2; exit 4' exited with code 4"
+        );
+        assert_eq!(err.output(), Some("installing\nbroken"));
+        assert!(!dir.path().join("never").exists());
+    }
+
+    #[test]
+    fn test_run_command_times_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let started = Instant::now();
+
+        let err = run_command(
+            "echo waiting; sleep 30",
+            dir.path(),
+            &context(dir.path()),
+            Duration::from_millis(200),
+        )
+        .unwrap_err();
+        assert!(matches!(err, HookError::TimedOut { .. }));
+        assert_eq!(err.output(), Some("waiting"));
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_run_command_ignores_background_output_pipe() {
+        let dir = tempfile::tempdir().unwrap();
+        let started = Instant::now();
+
+        // The background sleep inherits the output pipe and keeps it open
+        run_command(
+            "sleep 5 & echo started",
+            dir.path(),
+            &context(dir.path()),
+            Duration::from_secs(20),
+        )
+        .unwrap();
+        assert!(started.elapsed() < Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_run_commands_exports_room_env() {
+        let dir = tempfile::tempdir().unwrap();
+        let commands = vec!["echo \"$ROOMS_ROOM $ROOMS_BRANCH\" > env".to_string()];
+
+        run_commands(&commands, dir.path(), &context(dir.path())).unwrap();
+        let env = std::fs::read_to_string(dir.path().join("env")).unwrap();
+        assert_eq!(env, "quick-fox feature/fox\n");
+    }
+
+    #[test]
+    fn test_expand_placeholders() {
+        let context = context(Path::new("/src/quick-fox"));
+
+        assert_eq!(
+            context.expand("cd {primary} && git diff {base_branch}..{branch}"),
+            "cd /src/app && git diff ''..feature/fox"
+        );
+        assert_eq!(
+            context.expand("createdb app_{room} -p {port} # {path}"),
+            "createdb app_quick-fox -p 20010 # /src/quick-fox"
+        );
+        assert_eq!(
+            context.expand("{port_0}:{port_2}:{port_3}"),
+            "20010:20012:{port_3}"
+        );
+        assert_eq!(
+            context.expand("echo {a,b} ${HOME} {unknown} {"),
+            "echo {a,b} ${HOME} {unknown} {"
+        );
+    }
+
+    #[test]
+    fn test_expand_quotes_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut context = context(dir.path());
+        context.branch = Some("x;touch pwned".to_string());
+        context.room = "it's $(touch pwned)".to_string();
+
+        assert_eq!(context.expand("echo {branch}"), "echo 'x;touch pwned'");
+        assert_eq!(
+            context.expand_text("branch: {branch}"),
+            "branch: x;touch pwned"
+        );
+
+        let commands = vec!["echo {branch} {room} > out".to_string()];
+        run_commands(&commands, dir.path(), &context).unwrap();
+        assert!(!dir.path().join("pwned").exists());
+        let out = std::fs::read_to_string(dir.path().join("out")).unwrap();
+        assert_eq!(out, "x;touch pwned it's $(touch pwned)\n");
+    }
+
+    #[test]
+    fn test_env_includes_ports() {
+        let mut context = context(Path::new("/src/quick-fox"));
+        let env = context.env();
+        assert_eq!(env["PORT"], "20010");
+        assert_eq!(env["ROOMS_PORT"], "20010");
+        assert_eq!(env["ROOMS_PORT_2"], "20012");
+        assert_eq!(env["ROOMS_ROOM"], "quick-fox");
+
+        context.ports = None;
+        let env = context.env();
+        assert!(!env.contains_key("PORT"));
+        assert!(!env.contains_key("ROOMS_PORT"));
+    }
+
+    #[test]
+    fn test_output_tail() {
+        assert_eq!(output_tail("a\nb\nc\n", 2), "b\nc");
+        assert_eq!(output_tail("a", 5), "a");
+        assert_eq!(output_tail("", 5), "");
+    }
+}
@@ -1,7 +1,9 @@
 use std::process::ExitCode;
 
+mod cli;
 mod config;
 mod git;
+mod hooks;
 mod room;
 mod state;
 mod terminal;
@@ -12,6 +14,8 @@ fn main() -> ExitCode {
     let mut skip_hooks = false;
     let mut debug_pty = false;
     let mut custom_rooms_dir: Option<String> = None;
+    let mut subcommand: Option<String> = None;
+    let mut subcommand_args: Vec<String> = Vec::new();
 
     // Parse arguments
     let mut i = 1;
@@ -40,6 +44,12 @@ fn main() -> ExitCode {
                 }
                 custom_rooms_dir = Some(args[i].clone());
             }
+            arg if subcommand.is_some() => {
+                subcommand_args.push(arg.to_string());
+            }
+            arg if !arg.starts_with('-') => {
+                subcommand = Some(arg.to_string());
+            }
             arg => {
                 eprintln!("error: unknown argument '{arg}'");
                 eprintln!("run 'rooms --help' for usage");
@@ -49,6 +59,18 @@ fn main() -> ExitCode {
         i += 1;
     }
 
+    let command = match subcommand {
+        Some(name) => match cli::Command::parse(&name, &subcommand_args) {
+            Ok(command) => Some(command),
+            Err(e) => {
+                eprintln!("error: {e}");
+                eprintln!("run 'rooms --help' for usage");
+                return ExitCode::from(cli::EXIT_USAGE);
+            }
+        },
+        None => None,
+    };
+
     // Initialize PTY debug logging if requested
     if debug_pty {
         if let Err(e) = terminal::debug_log::init() {
@@ -97,6 +119,17 @@ fn main() -> ExitCode {
         config.rooms_path(&primary_worktree)
     };
 
+    if let Some(command) = command {
+        let ctx = cli::Context {
+            repo_root,
+            rooms_dir,
+            primary_worktree,
+            config,
+            skip_hooks,
+        };
+        return cli::run(command, &ctx);
+    }
+
     // Launch TUI
     let mut app = ui::App::new(repo_root, rooms_dir, config, primary_worktree, skip_hooks);
 
@@ -114,6 +147,22 @@ fn print_help() {
 
 USAGE:
     rooms [OPTIONS]
+    rooms [OPTIONS] <COMMAND> [ARGS]
+
+COMMANDS:
+    list [--json]                List rooms (name, branch, path); --json adds
+                                 status and uncommitted change counts
+    create [NAME] [OPTIONS]      Create a room and print its path
+        -b, --branch <BRANCH>    Branch to use (defaults to the room name)
+        --base <BRANCH>          Base branch for a new branch
+        --detach <COMMIT>        Check out a commit, tag or ref detached
+    delete <NAME> [--force]      Remove a room's worktree (branch is kept);
+                                 --force also removes uncommitted changes,
+                                 which u in the TUI restores
+    rename <NAME> <NEW_NAME>     Rename a room's worktree directory
+
+    Without a command, rooms launches the TUI. Commands exit with 0 on
+    success, 1 when the operation fails and 2 on invalid arguments.
 
 OPTIONS:
     -h, --help           Print help information
@@ -5,11 +5,20 @@ use std::path::{Path, PathBuf};
 
 use thiserror::Error;
 
+use crate::config::{DependencyDir, Hooks, Ports, RoomFile};
 use crate::git::command::{CommandError, GitCommand};
-use crate::git::list_worktrees_from;
+use crate::git::{
+    check_branch_name_from, find_remote_branch_from, get_primary_worktree_path_from,
+    list_worktrees_from,
+};
+use crate::hooks::{self, HookContext, HookError};
 use crate::room::discovery::is_worktree_in_rooms_dir;
+use crate::room::files::{RoomFileError, materialize_files, share_dependency_dirs};
+use crate::state::{PortAllocations, PortBlock, PortsError};
 
-use super::naming::{generate_unique_room_name, sanitize_room_name, validate_room_name};
+use super::naming::{
+    generate_unique_room_name, room_name_from_branch, sanitize_room_name, validate_room_name,
+};
 
 #[derive(Error, Debug)]
 pub enum CreateRoomError {
@@ -19,22 +28,46 @@ pub enum CreateRoomError {
     #[error("room '{0}' already exists")]
     NameExists(String),
 
+    #[error("invalid branch name '{0}'")]
+    InvalidBranch(String),
+
+    #[error("'{0}' is not a commit")]
+    InvalidCommit(String),
+
     #[error("failed to create worktree: {0}")]
     WorktreeCreation(String),
 
     #[error("git command failed: {0}")]
     GitError(#[from] CommandError),
+
+    #[error("pre_create hook {0}")]
+    PreCreateHook(#[source] HookError),
+
+    #[error("failed to reserve ports: {0}")]
+    Ports(#[from] PortsError),
+
+    #[error("branch '{0}' has moved since the room was deleted; its changes weren't restored")]
+    BranchMoved(String),
 }
 
 /// Information about a newly created room.
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct CreatedRoom {
     /// Room name (directory name).
     pub name: String,
-    /// Git branch name.
-    pub branch: String,
+    /// Git branch name (None for a detached room).
+    pub branch: Option<String>,
     /// Path to the worktree directory.
     pub path: PathBuf,
+    /// Base branch the new branch was created from (None if the branch already existed).
+    pub base_branch: Option<String>,
+    /// Remote-tracking branch the new local branch tracks, if it was created from one.
+    pub upstream: Option<String>,
+    /// Ports reserved for the room.
+    pub ports: PortBlock,
+    /// First error from copying local files or dependency directories into
+    /// the room; the room is still created.
+    pub files_error: Option<RoomFileError>,
 }
 
 /// Options for creating a new room.
@@ -44,21 +77,40 @@ pub struct CreateRoomOptions {
     pub name: Option<String>,
 
     /// Branch name (optional, defaults to room name).
+    ///
+    /// Used as is; it only has to be a valid git branch name.
     pub branch: Option<String>,
 
     /// Base branch to create from (optional, defaults to HEAD).
     pub base_branch: Option<String>,
+
+    /// Commit-ish (SHA, tag, `HEAD~3`) to check out as a detached HEAD.
+    ///
+    /// When set, `branch` and `base_branch` are ignored and no branch is created.
+    pub commit: Option<String>,
+
+    /// Port block to reserve for the room.
+    pub ports: Ports,
+
+    /// Local files to copy from the primary worktree.
+    pub files: Vec<RoomFile>,
+
+    /// Dependency directories to share from the primary worktree.
+    pub dependency_dirs: Vec<DependencyDir>,
 }
 
 /// Create a new room with a git worktree.
 ///
-/// Returns the created room info on success.
+/// `pre_create` hooks run in the repository root first; a failing hook
+/// aborts the creation. Configured local files and dependency directories
+/// are brought into the new worktree afterwards. Returns the created room info on success.
 pub fn create_room(
     repo_root: &Path,
     rooms_dir: &Path,
     options: CreateRoomOptions,
+    hooks: &Hooks,
 ) -> Result<CreatedRoom, CreateRoomError> {
-    create_room_in_repo(repo_root, rooms_dir, options)
+    create_room_in_repo(repo_root, rooms_dir, options, hooks)
 }
 
 /// Create a new room with a git worktree, specifying the repository directory.
@@ -69,9 +121,25 @@ fn create_room_in_repo(
     repo_root: &Path,
     rooms_dir: &Path,
     options: CreateRoomOptions,
+    hooks: &Hooks,
 ) -> Result<CreatedRoom, CreateRoomError> {
     let existing_names = list_room_names(repo_root, rooms_dir)?;
 
+    // Branches follow git's own rules; only room names are sanitized
+    let requested_branch = match options.branch {
+        Some(b) => Some(
+            check_branch_name_from(repo_root, &b)?
+                .ok_or_else(|| CreateRoomError::InvalidBranch(b.clone()))?,
+        ),
+        None => None,
+    };
+
+    if let Some(commit) = &options.commit
+        && !check_commit_exists_in_repo(commit, repo_root)?
+    {
+        return Err(CreateRoomError::InvalidCommit(commit.clone()));
+    }
+
     // Determine room name
     let name = match options.name {
         Some(n) => {
@@ -82,14 +150,14 @@ fn create_room_in_repo(
             }
             sanitized
         }
-        None => generate_unique_room_name(|n| existing_names.contains(n)),
+        None => match options.commit.as_ref().or(requested_branch.as_ref()) {
+            Some(b) => room_name_from_branch(b, |n| existing_names.contains(n)),
+            None => generate_unique_room_name(|n| existing_names.contains(n)),
+        },
     };
 
     // Determine branch name (default to room name)
-    let branch = options
-        .branch
-        .map(|b| sanitize_room_name(&b))
-        .unwrap_or_else(|| name.clone());
+    let checkout_branch = requested_branch.unwrap_or_else(|| name.clone());
 
     // Determine worktree path
     let worktree_path = rooms_dir.join(&name);
@@ -103,43 +171,120 @@ fn create_room_in_repo(
     }
 
     // Create the worktree
-    // First, check if the branch exists
-    let branch_exists = check_branch_exists_in_repo(&branch, repo_root)?;
+    // First, check if the branch exists locally or on a remote
+    let source = if let Some(commit) = options.commit {
+        BranchSource::Detached(commit)
+    } else if check_branch_exists_in_repo(&checkout_branch, repo_root)? {
+        BranchSource::Local
+    } else if let Some(upstream) = find_remote_branch_from(repo_root, &checkout_branch)? {
+        BranchSource::Remote(upstream)
+    } else {
+        BranchSource::New(options.base_branch)
+    };
+    let base_branch = match &source {
+        BranchSource::New(base_branch) => base_branch.clone(),
+        BranchSource::Local | BranchSource::Remote(_) | BranchSource::Detached(_) => None,
+    };
+    let branch = match &source {
+        BranchSource::Detached(_) => None,
+        _ => Some(checkout_branch.clone()),
+    };
+
+    // Resolved before reserving ports, so failing here leaves nothing to release
+    let primary = get_primary_worktree_path_from(repo_root)?;
+    // Reserve ports first so pre_create hooks can use them
+    let ports = PortAllocations::update_in_rooms_dir(rooms_dir, |allocations| {
+        allocations.allocate(&name, &options.ports)
+    })?;
+
+    let context = HookContext {
+        room: name.clone(),
+        path: worktree_path.clone(),
+        branch: branch.clone(),
+        primary,
+        base_branch: base_branch.clone(),
+        ports: Some(ports),
+    };
+    let result = hooks::run_commands(&hooks.pre_create, repo_root, &context)
+        .map_err(CreateRoomError::PreCreateHook)
+        .and_then(|()| add_worktree(repo_root, &worktree_path, &checkout_branch, &source));
+    if let Err(e) = result {
+        // Nothing was created, so the ports are free again
+        let _ = PortAllocations::update_in_rooms_dir(rooms_dir, |allocations| {
+            allocations.release(&name);
+            Ok(())
+        });
+        return Err(e);
+    }
+
+    let files_error = materialize_files(&options.files, &context).err();
+    let dirs_error = share_dependency_dirs(&options.dependency_dirs, &context).err();
+
+    Ok(CreatedRoom {
+        name,
+        branch,
+        path: worktree_path,
+        base_branch,
+        upstream: match source {
+            BranchSource::Remote(upstream) => Some(upstream),
+            BranchSource::Local | BranchSource::New(_) | BranchSource::Detached(_) => None,
+        },
+        ports,
+        files_error: files_error.or(dirs_error),
+    })
+}
+
+/// Where a room's branch comes from.
+enum BranchSource {
+    /// The local branch already exists.
+    Local,
+    /// A new local branch tracking this remote-tracking branch.
+    Remote(String),
+    /// A new branch from this base branch, or from HEAD.
+    New(Option<String>),
+    /// No branch: a detached HEAD at this commit-ish.
+    Detached(String),
+}
 
+/// Add a worktree at `worktree_path` with `branch` checked out, creating
+/// the branch from `source` if needed. Detached worktrees don't use `branch`.
+fn add_worktree(
+    repo_root: &Path,
+    worktree_path: &Path,
+    branch: &str,
+    source: &BranchSource,
+) -> Result<(), CreateRoomError> {
     let worktree_path_str = worktree_path.to_string_lossy().to_string();
 
-    let result = if branch_exists {
+    let cmd = match source {
         // Use existing branch
-        GitCommand::new("worktree")
-            .args(&["add", &worktree_path_str, &branch])
-            .current_dir(repo_root)
-            .run()
-    } else {
+        BranchSource::Local => {
+            GitCommand::new("worktree").args(&["add", &worktree_path_str, branch])
+        }
+        // Create a local branch that tracks the remote one
+        BranchSource::Remote(upstream) => GitCommand::new("worktree").args(&[
+            "add",
+            "--track",
+            "-b",
+            branch,
+            &worktree_path_str,
+            upstream,
+        ]),
         // Create new branch from base (or HEAD)
-        match &options.base_branch {
-            Some(base) => {
-                let cmd = GitCommand::new("worktree").args(&[
-                    "add",
-                    "-b",
-                    &branch,
-                    &worktree_path_str,
-                    base,
-                ]);
-                cmd.current_dir(repo_root).run()
-            }
-            None => GitCommand::new("worktree")
-                .args(&["add", "-b", &branch, &worktree_path_str])
-                .current_dir(repo_root)
-                .run(),
+        BranchSource::New(Some(base)) => {
+            GitCommand::new("worktree").args(&["add", "-b", branch, &worktree_path_str, base])
+        }
+        BranchSource::New(None) => {
+            GitCommand::new("worktree").args(&["add", "-b", branch, &worktree_path_str])
+        }
+        BranchSource::Detached(commit) => {
+            GitCommand::new("worktree").args(&["add", "--detach", &worktree_path_str, commit])
         }
     };
+    let result = cmd.current_dir(repo_root).run();
 
     match result {
-        Ok(output) if output.success() => Ok(CreatedRoom {
-            name,
-            branch,
-            path: worktree_path,
-        }),
+        Ok(output) if output.success() => Ok(()),
         Ok(output) => Err(CreateRoomError::WorktreeCreation(output.stderr)),
         Err(e) => Err(CreateRoomError::GitError(e)),
     }
@@ -157,6 +302,17 @@ fn check_branch_exists_in_repo(branch: &str, repo_dir: &Path) -> Result<bool, Co
     Ok(result.success())
 }
 
+fn check_commit_exists_in_repo(commit: &str, repo_dir: &Path) -> Result<bool, CommandError> {
+    let cmd = GitCommand::new("rev-parse").args(&[
+        "--verify",
+        "--quiet",
+        &format!("{}^{{commit}}", commit),
+    ]);
+    let result = cmd.current_dir(repo_dir).run()?;
+
+    Ok(result.success())
+}
+
 fn list_room_names(repo_root: &Path, rooms_dir: &Path) -> Result<HashSet<String>, CreateRoomError> {
     let worktrees = list_worktrees_from(repo_root)?;
     let rooms_dir_canonical = rooms_dir
@@ -218,7 +374,7 @@ mod tests {
 
         let options = CreateRoomOptions::default();
 
-        let result = create_room_in_repo(&repo_path, &rooms_dir, options);
+        let result = create_room_in_repo(&repo_path, &rooms_dir, options, &Hooks::default());
         assert!(result.is_ok(), "Failed to create room: {:?}", result.err());
 
         let room = result.unwrap();
@@ -237,12 +393,56 @@ mod tests {
             ..Default::default()
         };
 
-        let result = create_room_in_repo(&repo_path, &rooms_dir, options);
+        let result = create_room_in_repo(&repo_path, &rooms_dir, options, &Hooks::default());
         assert!(result.is_ok());
 
         let room = result.unwrap();
         assert_eq!(room.name, "my-feature");
-        assert_eq!(room.branch, "my-feature");
+        assert_eq!(room.branch.as_deref(), Some("my-feature"));
+    }
+
+    #[test]
+    fn test_create_room_reserves_ports() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let rooms_dir = repo_path.join(".rooms");
+        std::fs::create_dir_all(&rooms_dir).unwrap();
+
+        let options = CreateRoomOptions {
+            name: Some("my-feature".to_string()),
+            ports: Ports {
+                start: 43000,
+                per_room: 2,
+            },
+            ..Default::default()
+        };
+        let room = create_room_in_repo(&repo_path, &rooms_dir, options, &Hooks::default()).unwrap();
+
+        assert!(room.ports.start >= 43000);
+        assert_eq!(room.ports.count, 2);
+        let ports = PortAllocations::load_from_rooms_dir(&rooms_dir).unwrap();
+        assert_eq!(ports.get("my-feature"), Some(room.ports));
+    }
+
+    #[test]
+    fn test_create_room_copies_files() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let rooms_dir = repo_path.join(".rooms");
+        std::fs::create_dir_all(&rooms_dir).unwrap();
+        std::fs::write(repo_path.join(".env.example"), "APP={room}\n").unwrap();
+
+        let options = CreateRoomOptions {
+            name: Some("my-feature".to_string()),
+            files: vec![RoomFile {
+                from: ".env.example".to_string(),
+                to: ".env".to_string(),
+            }],
+            ..Default::default()
+        };
+        let room = create_room_in_repo(&repo_path, &rooms_dir, options, &Hooks::default()).unwrap();
+
+        assert!(room.files_error.is_none());
+        let env = std::fs::read_to_string(room.path.join(".env")).unwrap();
+        assert_eq!(env, "APP=my-feature\n");
     }
 
     #[test]
@@ -256,15 +456,128 @@ mod tests {
             name: Some("duplicate".to_string()),
             ..Default::default()
         };
-        create_room_in_repo(&repo_path, &rooms_dir, options1).unwrap();
+        create_room_in_repo(&repo_path, &rooms_dir, options1, &Hooks::default()).unwrap();
 
         // Try to create room with same name
         let options2 = CreateRoomOptions {
             name: Some("duplicate".to_string()),
             ..Default::default()
         };
-        let result = create_room_in_repo(&repo_path, &rooms_dir, options2);
+        let result = create_room_in_repo(&repo_path, &rooms_dir, options2, &Hooks::default());
 
         assert!(matches!(result, Err(CreateRoomError::NameExists(_))));
     }
+
+    #[test]
+    fn test_create_room_keeps_branch_name() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let rooms_dir = repo_path.join(".rooms");
+        std::fs::create_dir_all(&rooms_dir).unwrap();
+
+        let options = CreateRoomOptions {
+            branch: Some("feature/JIRA-123_login".to_string()),
+            ..Default::default()
+        };
+        let room = create_room_in_repo(&repo_path, &rooms_dir, options, &Hooks::default()).unwrap();
+        assert_eq!(room.name, "feature-jira-123-login");
+        assert_eq!(room.branch.as_deref(), Some("feature/JIRA-123_login"));
+        assert!(rooms_dir.join("feature-jira-123-login").exists());
+
+        let options = CreateRoomOptions {
+            branch: Some("bad..branch".to_string()),
+            ..Default::default()
+        };
+        let result = create_room_in_repo(&repo_path, &rooms_dir, options, &Hooks::default());
+        assert!(matches!(result, Err(CreateRoomError::InvalidBranch(_))));
+    }
+
+    #[test]
+    fn test_create_room_tracks_remote_branch() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let rooms_dir = repo_path.join(".rooms");
+        std::fs::create_dir_all(&rooms_dir).unwrap();
+
+        for args in [
+            vec!["remote", "add", "origin", "."],
+            vec!["update-ref", "refs/remotes/origin/review-me", "HEAD"],
+        ] {
+            Command::new("git")
+                .args(args)
+                .current_dir(&repo_path)
+                .output()
+                .unwrap();
+        }
+
+        let options = CreateRoomOptions {
+            name: Some("review-me".to_string()),
+            ..Default::default()
+        };
+        let room = create_room_in_repo(&repo_path, &rooms_dir, options, &Hooks::default()).unwrap();
+        assert_eq!(room.upstream, Some("origin/review-me".to_string()));
+        assert_eq!(room.base_branch, None);
+
+        let output = Command::new("git")
+            .args(["config", "branch.review-me.remote"])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "origin");
+    }
+
+    #[test]
+    fn test_create_room_detached() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let rooms_dir = repo_path.join(".rooms");
+        std::fs::create_dir_all(&rooms_dir).unwrap();
+
+        Command::new("git")
+            .args(["tag", "v1.0"])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+
+        let options = CreateRoomOptions {
+            commit: Some("v1.0".to_string()),
+            ..Default::default()
+        };
+        let room = create_room_in_repo(&repo_path, &rooms_dir, options, &Hooks::default()).unwrap();
+        assert_eq!(room.name, "v1-0");
+        assert_eq!(room.branch, None);
+
+        let output = Command::new("git")
+            .args(["symbolic-ref", "--quiet", "HEAD"])
+            .current_dir(&room.path)
+            .output()
+            .unwrap();
+        assert!(!output.status.success(), "HEAD should be detached");
+
+        let options = CreateRoomOptions {
+            commit: Some("no-such-ref".to_string()),
+            ..Default::default()
+        };
+        let result = create_room_in_repo(&repo_path, &rooms_dir, options, &Hooks::default());
+        assert!(matches!(result, Err(CreateRoomError::InvalidCommit(_))));
+    }
+
+    #[test]
+    fn test_failing_pre_create_hook_aborts() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let rooms_dir = repo_path.join(".rooms");
+        std::fs::create_dir_all(&rooms_dir).unwrap();
+
+        let options = CreateRoomOptions {
+            name: Some("blocked".to_string()),
+            ..Default::default()
+        };
+        let hooks = Hooks {
+            pre_create: vec!["test \"$ROOMS_ROOM\" != blocked".to_string()],
+            ..Default::default()
+        };
+        let result = create_room_in_repo(&repo_path, &rooms_dir, options, &hooks);
+
+        assert!(matches!(result, Err(CreateRoomError::PreCreateHook(_))));
+        assert!(!rooms_dir.join("blocked").exists());
+        let ports = PortAllocations::load_from_rooms_dir(&rooms_dir).unwrap();
+        assert_eq!(ports.get("blocked"), None);
+    }
 }
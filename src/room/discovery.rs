@@ -64,6 +64,25 @@ pub fn discover_rooms(
     // List all worktrees from the repository root
     let worktrees = list_worktrees_from(repo_root)?;
 
+    Ok(rooms_from_worktrees(
+        &worktrees,
+        rooms_dir,
+        primary_worktree,
+        transient,
+    ))
+}
+
+/// Build rooms from an already listed set of worktrees.
+///
+/// This is the part of [`discover_rooms`] after `git worktree list`: it keeps
+/// worktrees inside `rooms_dir` plus the primary worktree and applies
+/// transient state.
+pub fn rooms_from_worktrees(
+    worktrees: &[Worktree],
+    rooms_dir: &Path,
+    primary_worktree: Option<&Path>,
+    transient: &TransientStateStore,
+) -> Vec<RoomInfo> {
     // Canonicalize rooms_dir for reliable path comparison
     let rooms_dir_canonical = rooms_dir
         .canonicalize()
@@ -74,7 +93,7 @@ pub fn discover_rooms(
         .or_else(|| primary_worktree.map(|path| path.to_path_buf()));
 
     // Filter to worktrees inside rooms_dir and include primary worktree
-    let rooms: Vec<RoomInfo> = worktrees
+    worktrees
         .iter()
         .filter(|wt| {
             let is_primary = primary_canonical
@@ -105,9 +124,7 @@ pub fn discover_rooms(
 
             room_info
         })
-        .collect();
-
-    Ok(rooms)
+        .collect()
 }
 
 /// Check if a worktree is located inside the rooms directory.
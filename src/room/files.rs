@@ -0,0 +1,338 @@
+//! Local files copied from the primary worktree into new rooms.
+//!
+//! Gitignored files such as `.env` don't come with `git worktree add`, so
+//! the ones listed in `files` are copied over with room placeholders
+//! expanded, like in hook commands but without shell quoting. Directories listed in
+//! `dependency_dirs` are cloned or symlinked so new rooms don't start with
+//! a cold install or build.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use thiserror::Error;
+
+use crate::config::{DependencyDir, RoomFile, ShareMode};
+use crate::hooks::HookContext;
+
+#[derive(Error, Debug)]
+pub enum RoomFileError {
+    #[error("failed to copy '{from}' to '{to}': {source}")]
+    Copy {
+        from: String,
+        to: String,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("failed to share '{path}': {source}")]
+    Share {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// Copy `files` from the primary worktree into the room at `context.path`.
+///
+/// Placeholders are expanded in text files; other files are copied as is.
+/// Files missing from the primary worktree and files the room already has
+/// (such as tracked ones) are skipped. Returns the paths that were written.
+pub fn materialize_files(
+    files: &[RoomFile],
+    context: &HookContext,
+) -> Result<Vec<PathBuf>, RoomFileError> {
+    let mut written = Vec::new();
+    for file in files {
+        let source = context.primary.join(&file.from);
+        let target = context.path.join(&file.to);
+        if !source.is_file() || target.exists() {
+            continue;
+        }
+        copy_file(&source, &target, context).map_err(|source| RoomFileError::Copy {
+            from: file.from.clone(),
+            to: file.to.clone(),
+            source,
+        })?;
+        written.push(target);
+    }
+    Ok(written)
+}
+
+/// Clone or symlink `dirs` from the primary worktree into the room at `context.path`.
+///
+/// Directories missing from the primary worktree or already present in the
+/// room are skipped. Returns the paths that were created.
+pub fn share_dependency_dirs(
+    dirs: &[DependencyDir],
+    context: &HookContext,
+) -> Result<Vec<PathBuf>, RoomFileError> {
+    let mut shared = Vec::new();
+    for dir in dirs {
+        let source = context.primary.join(&dir.path);
+        let target = context.path.join(&dir.path);
+        if !source.is_dir() || target.symlink_metadata().is_ok() {
+            continue;
+        }
+        let result = match dir.mode {
+            ShareMode::Clone => clone_dir(&source, &target),
+            ShareMode::Symlink => target
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|()| symlink_dir(&source, &target)),
+        };
+        result.map_err(|source| RoomFileError::Share {
+            path: dir.path.clone(),
+            source,
+        })?;
+        shared.push(target);
+    }
+    Ok(shared)
+}
+
+/// Copy-on-write clone `source` to `target`, falling back to hard links
+/// and then to plain copies for files that can't be linked.
+fn clone_dir(source: &Path, target: &Path) -> io::Result<()> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if reflink_dir(source, target) {
+        return Ok(());
+    }
+    // A failed clone can leave a partial tree behind.
+    if target.exists() {
+        fs::remove_dir_all(target)?;
+    }
+    link_tree(source, target)
+}
+
+/// Clone a directory with `cp`, which knows each platform's clone call.
+fn reflink_dir(source: &Path, target: &Path) -> bool {
+    let mut command = Command::new("cp");
+    if cfg!(target_os = "macos") {
+        command.arg("-cR");
+    } else {
+        command.args(["-a", "--reflink=always"]);
+    }
+    command
+        .arg(source)
+        .arg(target)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+fn link_tree(source: &Path, target: &Path) -> io::Result<()> {
+    fs::create_dir_all(target)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let from = entry.path();
+        let to = target.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            link_tree(&from, &to)?;
+        } else if file_type.is_symlink() {
+            symlink(&fs::read_link(&from)?, &to)?;
+        } else if fs::hard_link(&from, &to).is_err() {
+            fs::copy(&from, &to)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn symlink(original: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(original, link)
+}
+
+#[cfg(windows)]
+fn symlink(original: &Path, link: &Path) -> io::Result<()> {
+    // Relative links are resolved from the link's directory.
+    let resolved = link
+        .parent()
+        .map_or_else(|| original.to_path_buf(), |dir| dir.join(original));
+    if resolved.is_dir() {
+        std::os::windows::fs::symlink_dir(original, link)
+    } else {
+        std::os::windows::fs::symlink_file(original, link)
+    }
+}
+
+#[cfg(unix)]
+fn symlink_dir(original: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(original, link)
+}
+
+#[cfg(windows)]
+fn symlink_dir(original: &Path, link: &Path) -> io::Result<()> {
+    std::os::windows::fs::symlink_dir(original, link)
+}
+
+fn copy_file(source: &Path, target: &Path, context: &HookContext) -> io::Result<()> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let contents = fs::read(source)?;
+    match String::from_utf8(contents) {
+        Ok(text) => fs::write(target, context.expand_text(&text))?,
+        Err(binary) => fs::write(target, binary.into_bytes())?,
+    }
+    fs::set_permissions(target, fs::metadata(source)?.permissions())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::PortBlock;
+
+    fn context(primary: &Path, room: &Path) -> HookContext {
+        HookContext {
+            room: "quick-fox".to_string(),
+            path: room.to_path_buf(),
+            branch: Some("feature/fox".to_string()),
+            primary: primary.to_path_buf(),
+            base_branch: None,
+            ports: Some(PortBlock {
+                start: 20010,
+                count: 2,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_materialize_files() {
+        let primary = tempfile::tempdir().unwrap();
+        let room = tempfile::tempdir().unwrap();
+        fs::write(
+            primary.path().join(".env.example"),
+            "APP={room}\nPORT={port}\nHOME_DIR=${HOME}\n",
+        )
+        .unwrap();
+        fs::create_dir(primary.path().join("config")).unwrap();
+        fs::write(
+            primary.path().join("config/local.yml"),
+            "branch: {branch}\n",
+        )
+        .unwrap();
+        fs::write(room.path().join("tracked"), "keep").unwrap();
+        fs::write(primary.path().join("tracked"), "replace").unwrap();
+
+        let context = context(primary.path(), room.path());
+        let files = vec![
+            RoomFile {
+                from: ".env.example".to_string(),
+                to: ".env".to_string(),
+            },
+            RoomFile {
+                from: "config/local.yml".to_string(),
+                to: "config/local.yml".to_string(),
+            },
+            RoomFile {
+                from: "tracked".to_string(),
+                to: "tracked".to_string(),
+            },
+            RoomFile {
+                from: "missing".to_string(),
+                to: "missing".to_string(),
+            },
+        ];
+
+        let written = materialize_files(&files, &context).unwrap();
+        assert_eq!(
+            written,
+            vec![
+                room.path().join(".env"),
+                room.path().join("config/local.yml")
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(room.path().join(".env")).unwrap(),
+            "APP=quick-fox\nPORT=20010\nHOME_DIR=${HOME}\n"
+        );
+        assert_eq!(
+            fs::read_to_string(room.path().join("config/local.yml")).unwrap(),
+            "branch: feature/fox\n"
+        );
+        assert_eq!(
+            fs::read_to_string(room.path().join("tracked")).unwrap(),
+            "keep"
+        );
+    }
+
+    #[test]
+    fn test_share_dependency_dirs() {
+        let primary = tempfile::tempdir().unwrap();
+        let room = tempfile::tempdir().unwrap();
+        fs::create_dir_all(primary.path().join("node_modules/left-pad")).unwrap();
+        fs::write(
+            primary.path().join("node_modules/left-pad/index.js"),
+            "module.exports = {};\n",
+        )
+        .unwrap();
+        fs::create_dir_all(primary.path().join("tools/.venv")).unwrap();
+
+        let dirs = vec![
+            DependencyDir {
+                path: "node_modules".to_string(),
+                mode: ShareMode::Clone,
+            },
+            DependencyDir {
+                path: "tools/.venv".to_string(),
+                mode: ShareMode::Symlink,
+            },
+            DependencyDir {
+                path: "target".to_string(),
+                mode: ShareMode::Clone,
+            },
+        ];
+
+        let context = context(primary.path(), room.path());
+        let shared = share_dependency_dirs(&dirs, &context).unwrap();
+        assert_eq!(
+            shared,
+            vec![
+                room.path().join("node_modules"),
+                room.path().join("tools/.venv")
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(room.path().join("node_modules/left-pad/index.js")).unwrap(),
+            "module.exports = {};\n"
+        );
+        assert!(
+            !fs::symlink_metadata(room.path().join("node_modules"))
+                .unwrap()
+                .is_symlink()
+        );
+        assert_eq!(
+            fs::read_link(room.path().join("tools/.venv")).unwrap(),
+            primary.path().join("tools/.venv")
+        );
+
+        // Already shared directories are left alone
+        assert!(share_dependency_dirs(&dirs, &context).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_link_tree() {
+        let source = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        fs::create_dir(source.path().join("bin")).unwrap();
+        fs::write(source.path().join("bin/tool"), "#!/bin/sh\n").unwrap();
+        symlink(Path::new("bin/tool"), &source.path().join("tool")).unwrap();
+
+        let copy = target.path().join("copy");
+        link_tree(source.path(), &copy).unwrap();
+        assert_eq!(
+            fs::read_to_string(copy.join("bin/tool")).unwrap(),
+            "#!/bin/sh\n"
+        );
+        assert_eq!(
+            fs::read_link(copy.join("tool")).unwrap(),
+            Path::new("bin/tool")
+        );
+    }
+}
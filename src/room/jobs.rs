@@ -0,0 +1,919 @@
+//! Background queue for room operations.
+//!
+//! Creating, removing and renaming rooms, checking a room before deleting
+//! it, listing worktrees and branches and reading bisect progress all
+//! shell out to git, and can take seconds on a large repository or a
+//! worktree with a big `node_modules`. Jobs that change rooms run one at a
+//! time on a worker thread, in the order they were queued, so the UI thread
+//! never waits on git and operations on the same worktrees never race each
+//! other. Jobs that only read run in order on a second worker, so a slow
+//! hook in a room operation doesn't hold up refreshing the list. Jobs that
+//! haven't started yet can be cancelled.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+
+use crate::config::{Hooks, Ports};
+use crate::git::command::CommandError;
+use crate::git::{
+    BisectMark, BisectStatus, Branch, bisect_mark_from, bisect_reset_from, bisect_start_from,
+    bisect_status_from, check_branch_name_from, list_branches_from, list_worktrees_from,
+    prune_worktrees_from,
+};
+use crate::hooks::{HookContext, run_commands};
+use crate::state::{EventLog, PortAllocations, PortBlock, PortsError};
+
+use super::watcher::WorktreeListResult;
+use super::{
+    CreateRoomError, CreateRoomOptions, CreatedRoom, DeleteCheck, RemoveRoomError, RemovedRoom,
+    RenameRoomError, RenamedRoom, RoomSnapshot, branch_tip, create_room, delete_snapshot,
+    remove_room_with_snapshot, rename_room, restore_snapshot,
+};
+
+/// Identifies a queued job; unique within a [`JobQueue`].
+pub type JobId = u64;
+
+/// Git work to do in a room right after creating it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AfterCreate {
+    /// Start bisecting between the room's HEAD (bad) and this known-good commit-ish.
+    Bisect { good: String },
+    /// Put back the changes of a deleted room, saved at `changes_ref` if it
+    /// had any on top of `head`.
+    ///
+    /// The room isn't created if its branch no longer points at `head`.
+    Restore {
+        changes_ref: Option<String>,
+        head: String,
+    },
+}
+
+/// A room operation to run in the background.
+#[derive(Debug, Clone)]
+pub enum Job {
+    /// Create a room, then run `after_create` in it.
+    Create {
+        options: CreateRoomOptions,
+        after_create: Option<AfterCreate>,
+    },
+    /// Snapshot a room's uncommitted changes, then remove its worktree.
+    Remove {
+        room_name: String,
+        path: PathBuf,
+        /// The room's recorded base branch, for its delete hooks.
+        base_branch: Option<String>,
+        /// Set when the room has open terminals: `on_leave` hooks run in
+        /// this context first.
+        on_leave: Option<HookContext>,
+    },
+    /// Move a room's worktree to a new name.
+    Rename {
+        old_name: String,
+        new_name: String,
+        /// The room's recorded base branch, for its `post_rename` hooks.
+        base_branch: Option<String>,
+        /// Set when the room has open terminals: `on_leave` hooks run in
+        /// this context first.
+        on_leave: Option<HookContext>,
+    },
+    /// Look for uncommitted changes, unpushed commits and stashes in a room
+    /// before deleting it.
+    CheckDelete {
+        room_name: String,
+        path: PathBuf,
+        branch: Option<String>,
+        base_branch: Option<String>,
+        /// Delete the room without asking if the check finds nothing that
+        /// would be lost.
+        immediate: bool,
+    },
+    /// Delete snapshots of deleted rooms that can no longer be restored.
+    DiscardSnapshots { changes_refs: Vec<String> },
+    /// Reserve port blocks for rooms that don't have one yet.
+    ReservePorts {
+        room_names: Vec<String>,
+        config: Ports,
+    },
+    /// Check the branch name of a room to create against git's rules.
+    CheckBranchName { options: CreateRoomOptions },
+    /// List local and remote-tracking branches.
+    ListBranches,
+    /// Read the bisect progress of rooms, given as `(name, path)`.
+    CheckBisects { rooms: Vec<(String, PathBuf)> },
+    /// Mark the commit under test in a bisect room; git checks out the next one.
+    BisectMark {
+        room_name: String,
+        path: PathBuf,
+        mark: BisectMark,
+    },
+    /// End a room's bisect and go back to the commit it started from.
+    BisectReset { room_name: String, path: PathBuf },
+    /// Clean up worktree metadata for worktrees whose directory is gone.
+    Prune,
+    /// List worktrees again.
+    Refresh,
+}
+
+impl Job {
+    /// Name of the room the job works on, if any.
+    pub fn room_name(&self) -> Option<&str> {
+        match self {
+            Self::Create { options, .. } => options.name.as_deref(),
+            Self::Remove { room_name, .. }
+            | Self::CheckDelete { room_name, .. }
+            | Self::BisectMark { room_name, .. }
+            | Self::BisectReset { room_name, .. } => Some(room_name),
+            Self::Rename { old_name, .. } => Some(old_name),
+            Self::DiscardSnapshots { .. }
+            | Self::ReservePorts { .. }
+            | Self::CheckBranchName { .. }
+            | Self::ListBranches
+            | Self::CheckBisects { .. }
+            | Self::Prune
+            | Self::Refresh => None,
+        }
+    }
+
+    /// Whether the job only reads, and runs on the worker for reads.
+    fn is_read_only(&self) -> bool {
+        matches!(
+            self,
+            Self::CheckDelete { .. }
+                | Self::ListBranches
+                | Self::CheckBisects { .. }
+                | Self::Refresh
+        )
+    }
+
+    /// Whether worktrees are listed after the job: it changes them or asked for them.
+    fn lists_worktrees(&self) -> bool {
+        matches!(
+            self,
+            Self::Create { .. }
+                | Self::Remove { .. }
+                | Self::Rename { .. }
+                | Self::BisectMark { .. }
+                | Self::BisectReset { .. }
+                | Self::Prune
+                | Self::Refresh
+        )
+    }
+}
+
+/// What a finished job did.
+#[derive(Debug)]
+pub enum JobOutcome {
+    Created {
+        result: Result<CreatedRoom, CreateRoomError>,
+        /// Set when the room was created but its [`AfterCreate`] step failed.
+        after_create_error: Option<String>,
+    },
+    Removed {
+        result: Result<RemovedRoom, RemoveRoomError>,
+        /// State of the room before it was removed; `None` if the removal
+        /// failed or the worktree was already gone.
+        snapshot: Option<RoomSnapshot>,
+    },
+    Renamed(Result<RenamedRoom, RenameRoomError>),
+    DeleteChecked {
+        result: Result<DeleteCheck, RemoveRoomError>,
+        immediate: bool,
+    },
+    SnapshotsDiscarded(Result<(), CommandError>),
+    /// Blocks of every room with one, or why some couldn't be reserved.
+    PortsReserved(Result<BTreeMap<String, PortBlock>, PortsError>),
+    BranchNameChecked {
+        options: CreateRoomOptions,
+        /// The name as git resolves it, or `None` if it isn't valid.
+        result: Result<Option<String>, CommandError>,
+    },
+    BranchesListed(Result<Vec<Branch>, CommandError>),
+    /// Bisect progress of each checked room; `None` if it isn't bisecting.
+    BisectsChecked(Vec<(String, Result<Option<BisectStatus>, CommandError>)>),
+    BisectMarked {
+        mark: BisectMark,
+        /// Bisect progress after the mark.
+        result: Result<Option<BisectStatus>, CommandError>,
+    },
+    BisectReset(Result<(), CommandError>),
+    Pruned(Result<(), CommandError>),
+    Refreshed,
+}
+
+/// A finished job.
+#[derive(Debug)]
+pub struct JobResult {
+    pub id: JobId,
+    /// Room the job worked on, as returned by [`Job::room_name`].
+    pub room_name: Option<String>,
+    pub outcome: JobOutcome,
+    /// Worktrees listed right after the job, for jobs that change or refresh them.
+    ///
+    /// Applying these before the outcome means a created room is already
+    /// in the list when it is selected, and a removed one is already gone.
+    pub worktrees: Option<WorktreeListResult>,
+}
+
+/// Jobs waiting for the workers, oldest first.
+#[derive(Debug, Default)]
+struct Queue {
+    /// Jobs that change rooms.
+    jobs: VecDeque<(JobId, Job)>,
+    /// Jobs that only read; see [`Job::is_read_only`].
+    reads: VecDeque<(JobId, Job)>,
+    next_id: JobId,
+    stopped: bool,
+}
+
+impl Queue {
+    fn push(&mut self, job: Job) -> JobId {
+        // A queued refresh will see whatever changed before it runs
+        if matches!(job, Job::Refresh)
+            && let Some((id, _)) = self
+                .reads
+                .iter()
+                .find(|(_, job)| matches!(job, Job::Refresh))
+        {
+            return *id;
+        }
+        // Only the newest list of rooms to check matters
+        if let Job::CheckBisects { rooms } = &job
+            && let Some((id, Job::CheckBisects { rooms: queued })) = self
+                .reads
+                .iter_mut()
+                .find(|(_, job)| matches!(job, Job::CheckBisects { .. }))
+        {
+            queued.clone_from(rooms);
+            return *id;
+        }
+        self.next_id += 1;
+        if job.is_read_only() {
+            self.reads.push_back((self.next_id, job));
+        } else {
+            self.jobs.push_back((self.next_id, job));
+        }
+        self.next_id
+    }
+
+    /// Take the oldest job for the worker for reads, or the other one.
+    fn pop(&mut self, reads: bool) -> Option<(JobId, Job)> {
+        if reads {
+            self.reads.pop_front()
+        } else {
+            self.jobs.pop_front()
+        }
+    }
+
+    fn cancel(&mut self, id: JobId) -> Option<Job> {
+        for jobs in [&mut self.jobs, &mut self.reads] {
+            if let Some(index) = jobs.iter().position(|(queued, _)| *queued == id) {
+                return jobs.remove(index).map(|(_, job)| job);
+            }
+        }
+        None
+    }
+}
+
+struct Shared {
+    queue: Mutex<Queue>,
+    wakeup: Condvar,
+    /// Sends finished jobs to the UI thread.
+    ///
+    /// Held while a job's result is read, so a result is never sent after
+    /// one that saw a newer state of the worktrees.
+    results: Mutex<mpsc::Sender<JobResult>>,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, Queue> {
+        // The queue is plain data; a panic elsewhere can't leave it half updated.
+        self.queue
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn results(&self) -> MutexGuard<'_, mpsc::Sender<JobResult>> {
+        self.results
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Handle to the worker threads that run room jobs.
+///
+/// Dropping the handle discards jobs that haven't started and waits for
+/// the running ones, so quitting never leaves a worktree half removed.
+pub struct JobQueue {
+    shared: Arc<Shared>,
+    results: mpsc::Receiver<JobResult>,
+    workers: Vec<thread::JoinHandle<()>>,
+}
+
+impl JobQueue {
+    /// Start the workers for the rooms of `repo_root`, running `hooks` around
+    /// room operations.
+    pub fn spawn(repo_root: PathBuf, rooms_dir: PathBuf, hooks: Hooks) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let shared = Arc::new(Shared {
+            queue: Mutex::new(Queue::default()),
+            wakeup: Condvar::new(),
+            results: Mutex::new(tx),
+        });
+        let hooks = Arc::new(hooks);
+
+        let workers = [false, true]
+            .into_iter()
+            .map(|reads| {
+                let worker = Arc::clone(&shared);
+                let repo_root = repo_root.clone();
+                let rooms_dir = rooms_dir.clone();
+                let hooks = Arc::clone(&hooks);
+                thread::spawn(move || {
+                    loop {
+                        let (id, job) = {
+                            let mut queue = worker.lock();
+                            loop {
+                                if queue.stopped {
+                                    return;
+                                }
+                                if let Some(next) = queue.pop(reads) {
+                                    break next;
+                                }
+                                queue = worker
+                                    .wakeup
+                                    .wait(queue)
+                                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+                            }
+                        };
+                        if !run_job(&repo_root, &rooms_dir, &hooks, &worker, id, job) {
+                            return;
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        Self {
+            shared,
+            results: rx,
+            workers,
+        }
+    }
+
+    /// Queue a job behind the ones already waiting on its worker.
+    ///
+    /// A refresh or bisect check is only queued once: if one is already
+    /// waiting, its id is returned instead, and a waiting bisect check takes
+    /// the new list of rooms.
+    pub fn push(&self, job: Job) -> JobId {
+        let id = self.shared.lock().push(job);
+        self.shared.wakeup.notify_all();
+        id
+    }
+
+    /// Cancel the job `id` if it hasn't started yet.
+    ///
+    /// Returns the cancelled job. A job that is already running always
+    /// finishes: stopping `git worktree remove` halfway would leave the
+    /// room broken.
+    pub fn cancel(&self, id: JobId) -> Option<Job> {
+        self.shared.lock().cancel(id)
+    }
+
+    /// Jobs finished since the last call, in the order they ran.
+    pub fn poll(&self) -> Vec<JobResult> {
+        self.results.try_iter().collect()
+    }
+}
+
+impl Drop for JobQueue {
+    fn drop(&mut self) {
+        self.shared.lock().stopped = true;
+        self.shared.wakeup.notify_all();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Run `job` and send its result; `false` once nobody is listening.
+fn run_job(
+    repo_root: &Path,
+    rooms_dir: &Path,
+    hooks: &Hooks,
+    shared: &Shared,
+    id: JobId,
+    job: Job,
+) -> bool {
+    let room_name = job.room_name().map(str::to_string);
+    let lists_worktrees = job.lists_worktrees();
+    // A read runs entirely under the results lock, so whatever it read
+    // can't be reported after a change that finished while it ran
+    let reading = job.is_read_only().then(|| shared.results());
+    let outcome = match job {
+        Job::Create {
+            options,
+            after_create,
+        } => {
+            let result = check_restore_base(repo_root, &options, after_create.as_ref())
+                .and_then(|()| create_room(repo_root, rooms_dir, options, hooks));
+            // Runs before setup hooks, so they see the commit to test or the restored files
+            let after_create_error = match (&result, after_create) {
+                // The room was created at the bad commit
+                (Ok(created), Some(AfterCreate::Bisect { good })) => {
+                    bisect_start_from(&created.path, "HEAD", &good)
+                        .err()
+                        .map(|err| format!("failed to start bisect: {err}"))
+                }
+                (
+                    Ok(created),
+                    Some(AfterCreate::Restore {
+                        changes_ref: Some(changes_ref),
+                        ..
+                    }),
+                ) => match restore_snapshot(&created.path, &changes_ref) {
+                    Ok(()) => {
+                        // The files are back; a leftover ref only costs disk space
+                        let _ = delete_snapshot(repo_root, &changes_ref);
+                        None
+                    }
+                    Err(err) => Some(format!(
+                        "failed to restore changes from {changes_ref}: {err}"
+                    )),
+                },
+                _ => None,
+            };
+            JobOutcome::Created {
+                result,
+                after_create_error,
+            }
+        }
+        Job::Remove {
+            room_name,
+            path,
+            base_branch,
+            on_leave,
+        } => {
+            leave_room(rooms_dir, hooks, on_leave);
+            match remove_room_with_snapshot(
+                repo_root,
+                rooms_dir,
+                &room_name,
+                &path,
+                base_branch.as_deref(),
+                true,
+                hooks,
+            ) {
+                Ok((removed, snapshot)) => JobOutcome::Removed {
+                    result: Ok(removed),
+                    snapshot,
+                },
+                Err(e) => JobOutcome::Removed {
+                    result: Err(e),
+                    snapshot: None,
+                },
+            }
+        }
+        Job::Rename {
+            old_name,
+            new_name,
+            base_branch,
+            on_leave,
+        } => {
+            leave_room(rooms_dir, hooks, on_leave);
+            JobOutcome::Renamed(rename_room(
+                repo_root,
+                rooms_dir,
+                &old_name,
+                &new_name,
+                base_branch.as_deref(),
+                hooks,
+            ))
+        }
+        Job::CheckDelete {
+            path,
+            branch,
+            base_branch,
+            immediate,
+            ..
+        } => JobOutcome::DeleteChecked {
+            result: DeleteCheck::check(&path, branch.as_deref(), base_branch.as_deref()),
+            immediate,
+        },
+        Job::DiscardSnapshots { changes_refs } => JobOutcome::SnapshotsDiscarded(
+            changes_refs
+                .iter()
+                .try_for_each(|changes_ref| delete_snapshot(repo_root, changes_ref)),
+        ),
+        Job::ReservePorts { room_names, config } => JobOutcome::PortsReserved(
+            PortAllocations::reserve_in_rooms_dir(rooms_dir, &room_names, &config),
+        ),
+        Job::CheckBranchName { options } => {
+            let result = match &options.branch {
+                Some(branch) => check_branch_name_from(repo_root, branch),
+                None => Ok(None),
+            };
+            JobOutcome::BranchNameChecked { options, result }
+        }
+        Job::ListBranches => JobOutcome::BranchesListed(list_branches_from(repo_root)),
+        Job::CheckBisects { rooms } => JobOutcome::BisectsChecked(
+            rooms
+                .into_iter()
+                .map(|(name, path)| (name, bisect_status_from(&path)))
+                .collect(),
+        ),
+        Job::BisectMark { path, mark, .. } => JobOutcome::BisectMarked {
+            mark,
+            result: bisect_mark_from(&path, mark).and_then(|()| bisect_status_from(&path)),
+        },
+        Job::BisectReset { path, .. } => JobOutcome::BisectReset(bisect_reset_from(&path)),
+        Job::Prune => JobOutcome::Pruned(prune_worktrees_from(repo_root)),
+        Job::Refresh => JobOutcome::Refreshed,
+    };
+    let results = reading.unwrap_or_else(|| shared.results());
+    results
+        .send(JobResult {
+            id,
+            room_name,
+            outcome,
+            worktrees: lists_worktrees.then(|| list_worktrees_from(repo_root)),
+        })
+        .is_ok()
+}
+
+/// Run `on_leave` hooks in a room about to be removed or moved, logging a failure.
+///
+/// The room goes away either way: its terminals are closed once the job finishes.
+fn leave_room(rooms_dir: &Path, hooks: &Hooks, context: Option<HookContext>) {
+    let Some(context) = context else {
+        return;
+    };
+    if let Err(err) = run_commands(&hooks.on_leave, &context.path, &context) {
+        EventLog::new(rooms_dir).log_error(Some(&context.room), &format!("on_leave hook {err}"));
+    }
+}
+
+/// Refuse to restore a deleted room's changes onto a branch that moved.
+///
+/// The changes were saved on top of the room's old HEAD; restoring them
+/// over newer commits would show those commits as reverted.
+fn check_restore_base(
+    repo_root: &Path,
+    options: &CreateRoomOptions,
+    after_create: Option<&AfterCreate>,
+) -> Result<(), CreateRoomError> {
+    let (
+        Some(branch),
+        Some(AfterCreate::Restore {
+            changes_ref: Some(_),
+            head,
+        }),
+    ) = (&options.branch, after_create)
+    else {
+        return Ok(());
+    };
+    match branch_tip(repo_root, branch)? {
+        Some(tip) if tip != *head => Err(CreateRoomError::BranchMoved(branch.clone())),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use std::time::{Duration, Instant};
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+    }
+
+    fn remove(room_name: &str) -> Job {
+        Job::Remove {
+            room_name: room_name.to_string(),
+            path: PathBuf::from(room_name),
+            base_branch: None,
+            on_leave: None,
+        }
+    }
+
+    fn wait_for(jobs: &JobQueue, count: usize) -> Vec<JobResult> {
+        let deadline = Instant::now() + Duration::from_secs(30);
+        let mut results = Vec::new();
+        while results.len() < count && Instant::now() < deadline {
+            results.extend(jobs.poll());
+            thread::sleep(Duration::from_millis(10));
+        }
+        results
+    }
+
+    #[test]
+    fn test_queue_dedupes_refresh_and_cancels() {
+        let mut queue = Queue::default();
+        let refresh = queue.push(Job::Refresh);
+        let check = queue.push(Job::CheckDelete {
+            room_name: "quick-fox".to_string(),
+            path: PathBuf::from("quick-fox"),
+            branch: None,
+            base_branch: None,
+            immediate: false,
+        });
+        assert_eq!(queue.push(Job::Refresh), refresh);
+        let remove = queue.push(remove("quick-fox"));
+        assert!(remove > check);
+        assert_eq!(queue.reads.len(), 2);
+        assert_eq!(queue.jobs.len(), 1);
+
+        // The job asked for is cancelled, not the first one for the room
+        assert!(matches!(
+            queue.cancel(remove),
+            Some(Job::Remove { room_name, .. }) if room_name == "quick-fox"
+        ));
+        assert!(queue.cancel(remove).is_none());
+        assert_eq!(queue.reads[1].0, check);
+        assert!(queue.cancel(check).is_some());
+        assert_eq!(queue.reads.len(), 1);
+        assert!(queue.jobs.is_empty());
+
+        let bisects = queue.push(Job::CheckBisects { rooms: Vec::new() });
+        let rooms = vec![("bisect-a".to_string(), PathBuf::from("bisect-a"))];
+        assert_eq!(
+            queue.push(Job::CheckBisects {
+                rooms: rooms.clone()
+            }),
+            bisects
+        );
+        assert!(matches!(
+            &queue.reads[1].1,
+            Job::CheckBisects { rooms: queued } if *queued == rooms
+        ));
+        assert!(queue.pop(false).is_none());
+        assert_eq!(queue.pop(true).map(|(id, _)| id), Some(refresh));
+    }
+
+    #[test]
+    fn test_jobs_run_in_order() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let repo = temp_dir.path().to_path_buf();
+        let rooms_dir = repo.join(".rooms");
+        git(&repo, &["init"]);
+        git(&repo, &["config", "user.email", "test@test.com"]);
+        git(&repo, &["config", "user.name", "Test"]);
+        git(&repo, &["commit", "--allow-empty", "-m", "initial"]);
+        std::fs::create_dir_all(&rooms_dir).unwrap();
+
+        let jobs = JobQueue::spawn(repo.clone(), rooms_dir.clone(), Hooks::default());
+        let create = jobs.push(Job::Create {
+            options: CreateRoomOptions {
+                name: Some("quick-fox".to_string()),
+                ..Default::default()
+            },
+            after_create: None,
+        });
+        let rename = jobs.push(Job::Rename {
+            old_name: "quick-fox".to_string(),
+            new_name: "slow-fox".to_string(),
+            base_branch: None,
+            on_leave: None,
+        });
+        let mut results = wait_for(&jobs, 2);
+        let ids: Vec<JobId> = results.iter().map(|result| result.id).collect();
+        assert_eq!(ids, vec![create, rename]);
+        assert!(matches!(&results[1].outcome, JobOutcome::Renamed(Ok(_))));
+
+        // Reads run on their own worker, so the check waits for the rename
+        // here rather than in the queue
+        let check = jobs.push(Job::CheckDelete {
+            room_name: "slow-fox".to_string(),
+            path: rooms_dir.join("slow-fox"),
+            branch: Some("quick-fox".to_string()),
+            base_branch: None,
+            immediate: false,
+        });
+        results.extend(wait_for(&jobs, 1));
+        let delete = jobs.push(Job::Remove {
+            room_name: "slow-fox".to_string(),
+            path: rooms_dir.join("slow-fox"),
+            base_branch: None,
+            on_leave: None,
+        });
+        results.extend(wait_for(&jobs, 1));
+        results.remove(1);
+        let ids: Vec<JobId> = results.iter().map(|result| result.id).collect();
+        assert_eq!(ids, vec![create, check, delete]);
+
+        match &results[0].outcome {
+            JobOutcome::Created {
+                result: Ok(created),
+                after_create_error: None,
+            } => assert_eq!(created.name, "quick-fox"),
+            outcome => panic!("unexpected outcome: {outcome:?}"),
+        }
+        let worktrees = results[0].worktrees.as_ref().unwrap().as_ref().unwrap();
+        assert_eq!(worktrees.len(), 2);
+
+        match &results[1].outcome {
+            JobOutcome::DeleteChecked {
+                result: Ok(check), ..
+            } => {
+                assert!(!check.dirty.is_dirty);
+                assert_eq!(check.unpushed_commits(), 0);
+            }
+            outcome => panic!("unexpected outcome: {outcome:?}"),
+        }
+        assert!(results[1].worktrees.is_none());
+
+        match &results[2].outcome {
+            JobOutcome::Removed {
+                result: Ok(_),
+                snapshot: Some(snapshot),
+            } => {
+                assert_eq!(snapshot.branch.as_deref(), Some("quick-fox"));
+                assert_eq!(snapshot.changes_ref, None);
+            }
+            outcome => panic!("unexpected outcome: {outcome:?}"),
+        }
+        let worktrees = results[2].worktrees.as_ref().unwrap().as_ref().unwrap();
+        assert_eq!(worktrees.len(), 1);
+        assert_eq!(results[2].room_name.as_deref(), Some("slow-fox"));
+    }
+
+    #[test]
+    fn test_reads_do_not_wait_for_room_operations() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let repo = temp_dir.path().to_path_buf();
+        let rooms_dir = repo.join(".rooms");
+        git(&repo, &["init"]);
+        git(&repo, &["config", "user.email", "test@test.com"]);
+        git(&repo, &["config", "user.name", "Test"]);
+        git(&repo, &["commit", "--allow-empty", "-m", "initial"]);
+        std::fs::create_dir_all(&rooms_dir).unwrap();
+
+        let hooks = Hooks {
+            pre_create: vec!["sleep 2".to_string()],
+            ..Default::default()
+        };
+        let jobs = JobQueue::spawn(repo.clone(), rooms_dir.clone(), hooks);
+        let create = jobs.push(Job::Create {
+            options: CreateRoomOptions {
+                name: Some("quick-fox".to_string()),
+                ..Default::default()
+            },
+            after_create: None,
+        });
+        let refresh = jobs.push(Job::Refresh);
+
+        let results = wait_for(&jobs, 2);
+        let ids: Vec<JobId> = results.iter().map(|result| result.id).collect();
+        assert_eq!(ids, vec![refresh, create]);
+        // The refresh finished before the room was created
+        let worktrees = results[0].worktrees.as_ref().unwrap().as_ref().unwrap();
+        assert_eq!(worktrees.len(), 1);
+    }
+
+    #[test]
+    fn test_remove_runs_on_leave_hooks_first() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let repo = temp_dir.path().to_path_buf();
+        let rooms_dir = repo.join(".rooms");
+        let room = rooms_dir.join("quick-fox");
+        git(&repo, &["init"]);
+        git(&repo, &["config", "user.email", "test@test.com"]);
+        git(&repo, &["config", "user.name", "Test"]);
+        git(&repo, &["commit", "--allow-empty", "-m", "initial"]);
+        std::fs::create_dir_all(&rooms_dir).unwrap();
+
+        let hooks = Hooks {
+            on_leave: vec!["pwd > ../left.txt".to_string()],
+            ..Default::default()
+        };
+        let jobs = JobQueue::spawn(repo.clone(), rooms_dir.clone(), hooks);
+        jobs.push(Job::Create {
+            options: CreateRoomOptions {
+                name: Some("quick-fox".to_string()),
+                ..Default::default()
+            },
+            after_create: None,
+        });
+        wait_for(&jobs, 1);
+
+        jobs.push(Job::Remove {
+            room_name: "quick-fox".to_string(),
+            path: room.clone(),
+            base_branch: None,
+            on_leave: Some(HookContext {
+                room: "quick-fox".to_string(),
+                path: room.clone(),
+                branch: Some("quick-fox".to_string()),
+                primary: repo.clone(),
+                base_branch: None,
+                ports: None,
+            }),
+        });
+        let results = wait_for(&jobs, 1);
+        assert!(matches!(
+            &results[0].outcome,
+            JobOutcome::Removed { result: Ok(_), .. }
+        ));
+        assert!(!room.exists());
+        // The hook ran in the room while it still existed
+        let left = std::fs::read_to_string(rooms_dir.join("left.txt")).unwrap();
+        assert!(left.trim_end().ends_with("quick-fox"));
+    }
+
+    #[test]
+    fn test_deleted_room_restores_changes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let repo = temp_dir.path().to_path_buf();
+        let rooms_dir = repo.join(".rooms");
+        let room = rooms_dir.join("quick-fox");
+        git(&repo, &["init"]);
+        git(&repo, &["config", "user.email", "test@test.com"]);
+        git(&repo, &["config", "user.name", "Test"]);
+        git(&repo, &["commit", "--allow-empty", "-m", "initial"]);
+        std::fs::create_dir_all(&rooms_dir).unwrap();
+
+        let jobs = JobQueue::spawn(repo.clone(), rooms_dir.clone(), Hooks::default());
+        let options = CreateRoomOptions {
+            name: Some("quick-fox".to_string()),
+            ..Default::default()
+        };
+        jobs.push(Job::Create {
+            options: options.clone(),
+            after_create: None,
+        });
+        wait_for(&jobs, 1);
+
+        std::fs::write(room.join("notes.txt"), "unsaved").unwrap();
+        jobs.push(Job::Remove {
+            room_name: "quick-fox".to_string(),
+            path: room.clone(),
+            base_branch: None,
+            on_leave: None,
+        });
+        let results = wait_for(&jobs, 1);
+        let snapshot = match &results[0].outcome {
+            JobOutcome::Removed {
+                result: Ok(_),
+                snapshot: Some(snapshot),
+            } => snapshot.clone(),
+            outcome => panic!("unexpected outcome: {outcome:?}"),
+        };
+        assert!(!room.exists());
+        let changes_ref = snapshot.changes_ref.clone().unwrap();
+        let restore = Job::Create {
+            options: CreateRoomOptions {
+                branch: snapshot.branch,
+                ..options
+            },
+            after_create: Some(AfterCreate::Restore {
+                changes_ref: Some(changes_ref.clone()),
+                head: snapshot.head.clone(),
+            }),
+        };
+
+        // New commits on the branch would show up as reverted changes
+        git(&repo, &["commit", "--allow-empty", "-m", "moved"]);
+        git(&repo, &["branch", "-f", "quick-fox", "HEAD"]);
+        jobs.push(restore.clone());
+        let results = wait_for(&jobs, 1);
+        assert!(matches!(
+            &results[0].outcome,
+            JobOutcome::Created {
+                result: Err(CreateRoomError::BranchMoved(branch)),
+                ..
+            } if branch == "quick-fox"
+        ));
+        assert!(!room.exists());
+
+        git(&repo, &["branch", "-f", "quick-fox", &snapshot.head]);
+        jobs.push(restore);
+        let results = wait_for(&jobs, 1);
+        assert!(matches!(
+            &results[0].outcome,
+            JobOutcome::Created {
+                result: Ok(_),
+                after_create_error: None,
+            }
+        ));
+        assert_eq!(
+            std::fs::read_to_string(room.join("notes.txt")).unwrap(),
+            "unsaved"
+        );
+        // The snapshot is dropped once restored
+        let output = Command::new("git")
+            .args(["rev-parse", "--verify", "--quiet", &changes_ref])
+            .current_dir(&repo)
+            .output()
+            .unwrap();
+        assert!(!output.status.success());
+    }
+}
@@ -2,16 +2,29 @@
 
 mod create;
 mod discovery;
+mod files;
+mod jobs;
 mod model;
 mod naming;
 mod remove;
 mod rename;
+mod status;
+mod trash;
+mod watcher;
 
 pub use create::{CreateRoomError, CreateRoomOptions, CreatedRoom, create_room};
-pub use discovery::{DiscoveryError, discover_rooms};
-pub use model::{RoomInfo, RoomStatus};
+pub use discovery::{DiscoveryError, discover_rooms, rooms_from_worktrees};
+pub use files::{RoomFileError, materialize_files, share_dependency_dirs};
+pub use jobs::{AfterCreate, Job, JobId, JobOutcome, JobQueue, JobResult};
+pub use model::{RoomInfo, RoomStatus, short_sha};
 pub use naming::{
-    generate_room_name, generate_unique_room_name, sanitize_room_name, validate_room_name,
+    generate_room_name, generate_unique_room_name, room_name_from_branch, sanitize_room_name,
+    validate_room_name,
 };
-pub use remove::{DirtyStatus, RemoveRoomError, remove_room};
-pub use rename::{RenameRoomError, rename_room};
+pub use remove::{
+    DeleteCheck, DirtyStatus, RemoveRoomError, RemovedRoom, remove_room, remove_room_with_snapshot,
+};
+pub use rename::{RenameRoomError, RenamedRoom, rename_room};
+pub use status::RoomGitStatus;
+pub use trash::{RoomSnapshot, branch_tip, delete_snapshot, restore_snapshot, snapshot_worktree};
+pub use watcher::WorktreeWatcher;
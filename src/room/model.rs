@@ -20,6 +20,9 @@ pub enum RoomStatus {
     /// Creating worktree/branch.
     Creating,
 
+    /// Running `post_create` hooks as subprocesses.
+    SettingUp,
+
     /// Terminal ready, no active background ops.
     Ready,
 
@@ -38,7 +41,7 @@ pub enum RoomStatus {
 /// This struct represents a room as discovered from `git worktree list`.
 /// Unlike the persisted `Room` struct, this is a lightweight view that
 /// derives its identity from the worktree directory name.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RoomInfo {
     /// Room name (derived from directory name).
     pub name: String,
@@ -46,6 +49,9 @@ pub struct RoomInfo {
     /// Git branch name, if any (None if detached HEAD).
     pub branch: Option<String>,
 
+    /// HEAD commit SHA, if known.
+    pub head: Option<String>,
+
     /// Path to the worktree directory.
     pub path: PathBuf,
 
@@ -62,7 +68,24 @@ pub struct RoomInfo {
     pub is_primary: bool,
 }
 
+/// Number of SHA characters shown for detached rooms.
+const SHORT_SHA_LEN: usize = 7;
+
+/// Abbreviate a commit SHA for display.
+pub fn short_sha(sha: &str) -> &str {
+    &sha[..sha.len().min(SHORT_SHA_LEN)]
+}
+
 impl RoomInfo {
+    /// Branch name, or `detached @ <short sha>` for a detached HEAD.
+    pub fn branch_label(&self) -> String {
+        match (&self.branch, &self.head) {
+            (Some(branch), _) => branch.clone(),
+            (None, Some(head)) => format!("detached @ {}", short_sha(head)),
+            (None, None) => "detached".to_string(),
+        }
+    }
+
     /// Set the room status to Error with a message.
     pub fn set_error(&mut self, message: String) {
         self.status = RoomStatus::Error;
@@ -89,6 +112,7 @@ impl From<&Worktree> for RoomInfo {
         Self {
             name,
             branch: worktree.branch.clone(),
+            head: Some(worktree.head.clone()).filter(|head| !head.is_empty()),
             path: worktree.path.clone(),
             status,
             is_prunable: worktree.is_prunable(),
@@ -149,7 +173,7 @@ mod tests {
     fn test_room_info_from_detached_worktree() {
         let worktree = Worktree {
             path: PathBuf::from("/home/user/repo/.rooms/detached-wt"),
-            head: "abc123".to_string(),
+            head: "abc123def456".to_string(),
             branch: None,
             is_main: false,
             prunable: None,
@@ -160,6 +184,7 @@ mod tests {
 
         assert_eq!(room_info.name, "detached-wt");
         assert_eq!(room_info.branch, None);
+        assert_eq!(room_info.branch_label(), "detached @ abc123d");
         assert_eq!(room_info.status, RoomStatus::Ready);
         assert!(!room_info.is_primary);
     }
@@ -169,6 +194,7 @@ mod tests {
         let mut room_info = RoomInfo {
             name: "test".to_string(),
             branch: Some("test".to_string()),
+            head: None,
             path: PathBuf::from("/test"),
             status: RoomStatus::Ready,
             is_prunable: false,
@@ -190,6 +216,7 @@ mod tests {
         let mut room_info = RoomInfo {
             name: "test".to_string(),
             branch: Some("test".to_string()),
+            head: None,
             path: PathBuf::from("/test"),
             status: RoomStatus::Error,
             is_prunable: false,
@@ -214,4 +241,25 @@ mod tests {
             "\"orphaned\""
         );
     }
+
+    #[test]
+    fn test_room_info_serialization() {
+        let room_info = RoomInfo {
+            name: "quick-fox".to_string(),
+            branch: None,
+            head: None,
+            path: PathBuf::from("/rooms/quick-fox"),
+            status: RoomStatus::Ready,
+            is_prunable: false,
+            last_error: None,
+            is_primary: true,
+        };
+
+        let json = serde_json::to_value(&room_info).unwrap();
+        assert_eq!(json["name"], "quick-fox");
+        assert!(json["branch"].is_null());
+        assert_eq!(json["path"], "/rooms/quick-fox");
+        assert_eq!(json["status"], "ready");
+        assert_eq!(json["is_primary"], true);
+    }
 }
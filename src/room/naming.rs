@@ -61,6 +61,38 @@ where
     format!("room-{}", timestamp)
 }
 
+/// Derive a room name from a branch name, for the room's directory.
+///
+/// The branch is sanitized (`feature/JIRA-123_login` becomes
+/// `feature-jira-123-login`) and a `-2`, `-3`, ... suffix is added if the
+/// name is taken. Falls back to a generated name if nothing usable is left.
+pub fn room_name_from_branch<F>(branch: &str, exists: F) -> String
+where
+    F: Fn(&str) -> bool,
+{
+    let base = sanitize_room_name(branch);
+    if validate_room_name(&base).is_err() {
+        return generate_unique_room_name(exists);
+    }
+    if !exists(&base) {
+        return base;
+    }
+
+    for n in 2..100 {
+        let suffix = format!("-{n}");
+        let mut name = base.clone();
+        name.truncate(40 - suffix.len());
+        while name.ends_with('-') {
+            name.pop();
+        }
+        name.push_str(&suffix);
+        if !exists(&name) {
+            return name;
+        }
+    }
+    generate_unique_room_name(exists)
+}
+
 /// Validate a room name.
 ///
 /// Valid names:
@@ -199,6 +231,23 @@ mod tests {
         assert_eq!(sanitize_room_name("feat/branch"), "feat-branch");
     }
 
+    #[test]
+    fn test_room_name_from_branch() {
+        assert_eq!(
+            room_name_from_branch("feature/JIRA-123_login", |_| false),
+            "feature-jira-123-login"
+        );
+        assert_eq!(
+            room_name_from_branch("fix/a", |name| name == "fix-a"),
+            "fix-a-2"
+        );
+        let long = "x".repeat(50);
+        let taken = "x".repeat(40);
+        let name = room_name_from_branch(&long, |name| name == taken);
+        assert_eq!(name, format!("{}-2", "x".repeat(38)));
+        assert!(validate_room_name(&room_name_from_branch("_/_", |_| false)).is_ok());
+    }
+
     #[test]
     fn test_sanitize_room_name_truncates() {
         let long_name = "a".repeat(50);
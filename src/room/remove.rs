@@ -2,11 +2,17 @@
 
 use std::path::Path;
 
+use serde::Serialize;
 use thiserror::Error;
 
+use crate::config::Hooks;
 use crate::git::command::{CommandError, GitCommand};
-use crate::git::list_worktrees_from;
+use crate::git::{AheadBehind, ahead_behind_from, list_stash_branches_from, list_worktrees_from};
+use crate::hooks::{self, HookContext, HookError};
 use crate::room::discovery::is_worktree_in_rooms_dir;
+use crate::state::PortAllocations;
+
+use super::trash::{RoomSnapshot, delete_snapshot, snapshot_worktree};
 
 #[derive(Error, Debug)]
 pub enum RemoveRoomError {
@@ -21,10 +27,37 @@ pub enum RemoveRoomError {
 
     #[error("git command failed: {0}")]
     GitError(#[from] CommandError),
+
+    #[error("pre_delete hook {0}")]
+    PreDeleteHook(#[source] HookError),
+
+    #[error("failed to save uncommitted changes: {0}")]
+    Snapshot(#[source] CommandError),
+}
+
+impl RemoveRoomError {
+    /// Output of the failing command: git's stderr or the hook's output.
+    pub fn output(&self) -> Option<&str> {
+        match self {
+            Self::WorktreeRemoval(stderr) => Some(stderr),
+            Self::PreDeleteHook(err) => err.output(),
+            Self::Snapshot(CommandError::GitFailed { stderr, .. }) => Some(stderr),
+            _ => None,
+        }
+    }
+}
+
+/// Result of removing a room.
+#[derive(Debug)]
+pub struct RemovedRoom {
+    /// Name of the removed room.
+    pub name: String,
+    /// Set if a `post_delete` hook failed. The worktree is gone regardless.
+    pub post_hook_error: Option<HookError>,
 }
 
 /// Information about uncommitted changes in a worktree.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DirtyStatus {
     /// Whether there are any uncommitted changes.
     pub is_dirty: bool,
@@ -86,6 +119,83 @@ impl DirtyStatus {
     }
 }
 
+/// Everything a deletion would discard or leave behind, checked before confirming it.
+#[derive(Debug, Clone)]
+pub struct DeleteCheck {
+    /// Uncommitted changes in the worktree.
+    pub dirty: DirtyStatus,
+
+    /// Commits ahead/behind the branch's upstream, if it has one.
+    pub upstream: Option<AheadBehind>,
+
+    /// Branch the room was created from, if it differs from the room's branch.
+    pub base_branch: Option<String>,
+
+    /// Commits ahead/behind `base_branch`, if it resolves.
+    pub base: Option<AheadBehind>,
+
+    /// Stash entries made on the room's branch. Stashes are shared by all
+    /// worktrees, so these survive the deletion but are easy to forget.
+    pub stashes: usize,
+}
+
+impl DeleteCheck {
+    /// Check the room at `path`, on `branch`, before deleting it.
+    ///
+    /// Only the dirty check is required; the others are left empty if they fail.
+    pub fn check(
+        path: &Path,
+        branch: Option<&str>,
+        base_branch: Option<&str>,
+    ) -> Result<Self, RemoveRoomError> {
+        let dirty = DirtyStatus::check(path)?;
+        let base_branch = base_branch
+            .filter(|base| Some(*base) != branch)
+            .map(str::to_string);
+        if !path.exists() {
+            // An orphaned room has no HEAD to compare
+            return Ok(Self {
+                dirty,
+                upstream: None,
+                base_branch,
+                base: None,
+                stashes: 0,
+            });
+        }
+
+        let upstream = branch.and_then(|_| ahead_behind_from(path, "@{upstream}").ok().flatten());
+        let base = base_branch
+            .as_deref()
+            .and_then(|base| ahead_behind_from(path, base).ok().flatten());
+        let stashes = branch.map_or(0, |branch| {
+            list_stash_branches_from(path).map_or(0, |stashes| {
+                stashes
+                    .iter()
+                    .filter(|stash| stash.as_deref() == Some(branch))
+                    .count()
+            })
+        });
+
+        Ok(Self {
+            dirty,
+            upstream,
+            base_branch,
+            base,
+            stashes,
+        })
+    }
+
+    /// Commits that only exist in this repository: those ahead of the
+    /// upstream, or ahead of the base branch if the branch was never pushed.
+    pub fn unpushed_commits(&self) -> usize {
+        match (self.upstream, self.base) {
+            (Some(upstream), _) => upstream.ahead,
+            (None, Some(base)) => base.ahead,
+            (None, None) => 0,
+        }
+    }
+}
+
 /// Remove a room's worktree.
 ///
 /// This removes the git worktree but does NOT delete the branch.
@@ -134,13 +244,17 @@ pub fn remove_worktree_force<P: AsRef<Path>, R: AsRef<Path>>(
 
 /// Remove a room by name.
 ///
-/// Returns the removed room's name on success.
+/// `pre_delete` hooks run in the worktree first; a failing hook aborts the
+/// removal. `post_delete` hooks run in the repository root afterwards. Both
+/// see `base_branch`, the room's recorded base branch.
 pub fn remove_room(
     repo_root: &Path,
     rooms_dir: &Path,
     room_name: &str,
+    base_branch: Option<&str>,
     force: bool,
-) -> Result<String, RemoveRoomError> {
+    hooks: &Hooks,
+) -> Result<RemovedRoom, RemoveRoomError> {
     let worktrees = list_worktrees_from(repo_root)?;
     let rooms_dir_canonical = rooms_dir
         .canonicalize()
@@ -154,13 +268,72 @@ pub fn remove_room(
         })
         .ok_or_else(|| RemoveRoomError::NotFound(room_name.to_string()))?;
 
+    let context = HookContext {
+        room: room_name.to_string(),
+        path: worktree.path.clone(),
+        branch: worktree.branch.clone(),
+        primary: worktrees
+            .iter()
+            .find(|worktree| worktree.is_main)
+            .map_or_else(|| repo_root.to_path_buf(), |worktree| worktree.path.clone()),
+        base_branch: base_branch.map(str::to_string),
+        ports: PortAllocations::load_from_rooms_dir(rooms_dir)
+            .ok()
+            .and_then(|allocations| allocations.get(room_name)),
+    };
+    // An orphaned room has no worktree left to run in.
+    let cwd = if worktree.path.exists() {
+        worktree.path.as_path()
+    } else {
+        repo_root
+    };
+    hooks::run_commands(&hooks.pre_delete, cwd, &context)
+        .map_err(RemoveRoomError::PreDeleteHook)?;
+
     if force {
         remove_worktree_force(repo_root, &worktree.path)?;
     } else {
         remove_worktree(repo_root, &worktree.path)?;
     }
 
-    Ok(room_name.to_string())
+    // The room is gone either way; a stale block only wastes a few ports.
+    let _ = PortAllocations::update_in_rooms_dir(rooms_dir, |allocations| {
+        allocations.release(room_name);
+        Ok(())
+    });
+
+    Ok(RemovedRoom {
+        name: room_name.to_string(),
+        post_hook_error: hooks::run_commands(&hooks.post_delete, repo_root, &context).err(),
+    })
+}
+
+/// Remove a room after saving its state, so the deletion can be undone.
+///
+/// The worktree at `path` is snapshotted with [`snapshot_worktree`] first;
+/// the room is kept if that fails. The snapshot is `None` when the worktree
+/// was already gone.
+pub fn remove_room_with_snapshot(
+    repo_root: &Path,
+    rooms_dir: &Path,
+    room_name: &str,
+    path: &Path,
+    base_branch: Option<&str>,
+    force: bool,
+    hooks: &Hooks,
+) -> Result<(RemovedRoom, Option<RoomSnapshot>), RemoveRoomError> {
+    let snapshot =
+        snapshot_worktree(repo_root, path, room_name).map_err(RemoveRoomError::Snapshot)?;
+    match remove_room(repo_root, rooms_dir, room_name, base_branch, force, hooks) {
+        Ok(removed) => Ok((removed, snapshot)),
+        Err(e) => {
+            // The room and its changes are still there
+            if let Some(changes_ref) = snapshot.as_ref().and_then(|s| s.changes_ref.as_deref()) {
+                let _ = delete_snapshot(repo_root, changes_ref);
+            }
+            Err(e)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -254,6 +427,42 @@ mod tests {
         assert!(!status.is_dirty);
     }
 
+    #[test]
+    fn test_delete_check_counts_unpushed_commits_and_stashes() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let git = |args: &[&str]| {
+            let output = Command::new("git")
+                .args(args)
+                .current_dir(&repo_path)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {args:?} failed");
+        };
+        git(&["branch", "-M", "main"]);
+        git(&["checkout", "-q", "-b", "feature"]);
+        git(&["commit", "--allow-empty", "-m", "one"]);
+        git(&["commit", "--allow-empty", "-m", "two"]);
+        fs::write(repo_path.join("wip.txt"), "wip").unwrap();
+        git(&["stash", "push", "--include-untracked"]);
+
+        let check = DeleteCheck::check(&repo_path, Some("feature"), Some("main")).unwrap();
+        assert!(!check.dirty.is_dirty);
+        assert_eq!(check.upstream, None);
+        assert_eq!(check.base_branch.as_deref(), Some("main"));
+        assert_eq!(check.unpushed_commits(), 2);
+        assert_eq!(check.stashes, 1);
+
+        // Once pushed, commits ahead of the base branch are safe
+        git(&["branch", "pushed"]);
+        git(&["branch", "--set-upstream-to", "pushed"]);
+        let check = DeleteCheck::check(&repo_path, Some("feature"), Some("main")).unwrap();
+        assert_eq!(check.unpushed_commits(), 0);
+        assert_eq!(check.base.unwrap().ahead, 2);
+
+        let check = DeleteCheck::check(&repo_path.join("missing"), Some("feature"), None).unwrap();
+        assert_eq!(check.unpushed_commits(), 0);
+    }
+
     #[test]
     fn test_remove_room_removes_worktree() {
         let (_temp_dir, repo_path) = setup_test_repo();
@@ -273,8 +482,97 @@ mod tests {
             .output()
             .unwrap();
 
-        let result = remove_room(&repo_path, &rooms_dir, "remove-me", true);
+        let result = remove_room(
+            &repo_path,
+            &rooms_dir,
+            "remove-me",
+            None,
+            true,
+            &Hooks::default(),
+        );
         assert!(result.is_ok());
         assert!(!worktree_path.exists());
     }
+
+    #[test]
+    fn test_remove_room_locked_reports_stderr() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let rooms_dir = repo_path.join(".rooms");
+        std::fs::create_dir_all(&rooms_dir).unwrap();
+
+        let worktree_path = rooms_dir.join("locked");
+        Command::new("git")
+            .args([
+                "worktree",
+                "add",
+                "--lock",
+                "-b",
+                "locked",
+                &worktree_path.to_string_lossy(),
+            ])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+
+        let err = remove_room(
+            &repo_path,
+            &rooms_dir,
+            "locked",
+            None,
+            true,
+            &Hooks::default(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, RemoveRoomError::WorktreeRemoval(_)));
+        assert!(err.output().unwrap().contains("locked"));
+        assert!(worktree_path.exists());
+    }
+
+    #[test]
+    fn test_remove_room_runs_delete_hooks() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let rooms_dir = repo_path.join(".rooms");
+        std::fs::create_dir_all(&rooms_dir).unwrap();
+
+        let worktree_path = rooms_dir.join("hooked");
+        Command::new("git")
+            .args([
+                "worktree",
+                "add",
+                "-b",
+                "hooked",
+                &worktree_path.to_string_lossy(),
+            ])
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+
+        let blocking = Hooks {
+            pre_delete: vec!["exit 1".to_string()],
+            ..Default::default()
+        };
+        let result = remove_room(&repo_path, &rooms_dir, "hooked", None, true, &blocking);
+        assert!(matches!(result, Err(RemoveRoomError::PreDeleteHook(_))));
+        assert!(worktree_path.exists());
+
+        let block = PortAllocations::update_in_rooms_dir(&rooms_dir, |allocations| {
+            allocations.allocate("hooked", &crate::config::Ports::default())
+        })
+        .unwrap();
+        let hooks = Hooks {
+            pre_delete: vec![
+                "echo \"$ROOMS_BRANCH $ROOMS_BASE_BRANCH $PORT\" > ../pre-delete".to_string(),
+            ],
+            post_delete: vec!["exit 2".to_string()],
+            ..Default::default()
+        };
+        let removed =
+            remove_room(&repo_path, &rooms_dir, "hooked", Some("main"), true, &hooks).unwrap();
+        assert!(!worktree_path.exists());
+        assert!(removed.post_hook_error.is_some());
+        let branch = fs::read_to_string(rooms_dir.join("pre-delete")).unwrap();
+        assert_eq!(branch, format!("hooked main {}\n", block.start));
+        let ports = PortAllocations::load_from_rooms_dir(&rooms_dir).unwrap();
+        assert_eq!(ports.get("hooked"), None);
+    }
 }
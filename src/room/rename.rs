@@ -1,13 +1,16 @@
 #![allow(dead_code)]
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use thiserror::Error;
 
+use crate::config::Hooks;
 use crate::git::command::GitCommand;
 use crate::git::list_worktrees_from;
+use crate::hooks::{self, HookContext, HookError};
 use crate::room::discovery::is_worktree_in_rooms_dir;
 use crate::room::naming::validate_room_name;
+use crate::state::PortAllocations;
 
 #[derive(Error, Debug)]
 pub enum RenameRoomError {
@@ -30,18 +33,33 @@ pub enum RenameRoomError {
     WorktreeMove(String),
 }
 
+/// Result of renaming a room.
+#[derive(Debug)]
+pub struct RenamedRoom {
+    /// New room name.
+    pub name: String,
+    /// New worktree path.
+    pub path: PathBuf,
+    /// Set if a `post_rename` hook failed. The worktree was moved regardless.
+    pub post_hook_error: Option<HookError>,
+}
+
 /// Rename a room.
 ///
 /// This changes:
 /// - The worktree directory (via `git worktree move`)
 ///
-/// The git branch name remains unchanged.
+/// The git branch name remains unchanged. `post_rename` hooks run in the
+/// moved worktree afterwards, with `base_branch` as the room's recorded
+/// base branch.
 pub fn rename_room(
     repo_root: &Path,
     rooms_dir: &Path,
     current_name: &str,
     new_name: &str,
-) -> Result<String, RenameRoomError> {
+    base_branch: Option<&str>,
+    hooks: &Hooks,
+) -> Result<RenamedRoom, RenameRoomError> {
     // Validate new name
     validate_room_name(new_name).map_err(RenameRoomError::InvalidName)?;
 
@@ -68,14 +86,14 @@ pub fn rename_room(
     }
 
     // Find the room to get its current path
-    let old_path = worktrees
+    let worktree = worktrees
         .iter()
         .find(|worktree| {
             is_worktree_in_rooms_dir(worktree, &rooms_dir_canonical)
                 && worktree.name() == Some(current_name)
         })
-        .map(|worktree| worktree.path.clone())
         .ok_or_else(|| RenameRoomError::NotFound(current_name.to_string()))?;
+    let old_path = worktree.path.clone();
     let new_path = rooms_dir.join(new_name);
 
     // Check if destination path already exists on filesystem
@@ -100,7 +118,32 @@ pub fn rename_room(
         return Err(RenameRoomError::WorktreeMove(result.stderr));
     }
 
-    Ok(current_name.to_string())
+    // The worktree already moved, so a failure here only loses the old block.
+    let ports = PortAllocations::update_in_rooms_dir(rooms_dir, |allocations| {
+        allocations.rename(current_name, new_name);
+        Ok(allocations.get(new_name))
+    })
+    .ok()
+    .flatten();
+
+    let context = HookContext {
+        room: new_name.to_string(),
+        path: new_path.clone(),
+        branch: worktree.branch.clone(),
+        primary: worktrees
+            .iter()
+            .find(|worktree| worktree.is_main)
+            .map_or_else(|| repo_root.to_path_buf(), |worktree| worktree.path.clone()),
+        base_branch: base_branch.map(str::to_string),
+        ports,
+    };
+    let post_hook_error = hooks::run_commands(&hooks.post_rename, &new_path, &context).err();
+
+    Ok(RenamedRoom {
+        name: new_name.to_string(),
+        path: new_path,
+        post_hook_error,
+    })
 }
 
 #[cfg(test)]
@@ -146,7 +189,14 @@ mod tests {
         let rooms_dir = repo_root.join(".rooms");
         std::fs::create_dir_all(&rooms_dir).unwrap();
 
-        let result = rename_room(&repo_root, &rooms_dir, "nonexistent", "new-name");
+        let result = rename_room(
+            &repo_root,
+            &rooms_dir,
+            "nonexistent",
+            "new-name",
+            None,
+            &Hooks::default(),
+        );
         assert!(matches!(result, Err(RenameRoomError::NotFound(_))));
     }
 
@@ -156,7 +206,14 @@ mod tests {
         let repo_root = temp_dir.path();
         let rooms_dir = temp_dir.path();
 
-        let result = rename_room(repo_root, rooms_dir, "old-name", "Invalid Name");
+        let result = rename_room(
+            repo_root,
+            rooms_dir,
+            "old-name",
+            "Invalid Name",
+            None,
+            &Hooks::default(),
+        );
         assert!(matches!(result, Err(RenameRoomError::InvalidName(_))));
     }
 
@@ -179,7 +236,14 @@ mod tests {
             .output()
             .unwrap();
 
-        let result = rename_room(&repo_root, &rooms_dir, "room-a", "room-b");
+        let result = rename_room(
+            &repo_root,
+            &rooms_dir,
+            "room-a",
+            "room-b",
+            None,
+            &Hooks::default(),
+        );
         assert!(matches!(result, Err(RenameRoomError::NameExists(_))));
     }
 
@@ -189,7 +253,14 @@ mod tests {
         let repo_root = temp_dir.path();
         let rooms_dir = temp_dir.path();
 
-        let result = rename_room(repo_root, rooms_dir, "my-room", "my-room");
+        let result = rename_room(
+            repo_root,
+            rooms_dir,
+            "my-room",
+            "my-room",
+            None,
+            &Hooks::default(),
+        );
         assert!(matches!(result, Err(RenameRoomError::SameName)));
     }
 
@@ -214,9 +285,24 @@ mod tests {
             .unwrap();
 
         // Rename the room
-        let result = rename_room(&repo_path, &rooms_dir, "old-name", "new-name");
+        let hooks = Hooks {
+            post_rename: vec!["echo \"$ROOMS_ROOM $ROOMS_BASE_BRANCH\" > renamed".to_string()],
+            ..Default::default()
+        };
+        let result = rename_room(
+            &repo_path,
+            &rooms_dir,
+            "old-name",
+            "new-name",
+            Some("main"),
+            &hooks,
+        );
         assert!(result.is_ok(), "rename failed: {:?}", result.err());
-        assert_eq!(result.unwrap(), "old-name");
+        let renamed = result.unwrap();
+        assert_eq!(renamed.path, rooms_dir.join("new-name"));
+        assert!(renamed.post_hook_error.is_none());
+        let marker = std::fs::read_to_string(renamed.path.join("renamed")).unwrap();
+        assert_eq!(marker, "new-name main\n");
 
         // Verify filesystem was updated
         assert!(!old_path.exists());
@@ -247,7 +333,14 @@ mod tests {
         let new_path = rooms_dir.join("new-name");
         std::fs::create_dir_all(&new_path).unwrap();
 
-        let result = rename_room(&repo_path, &rooms_dir, "old-name", "new-name");
+        let result = rename_room(
+            &repo_path,
+            &rooms_dir,
+            "old-name",
+            "new-name",
+            None,
+            &Hooks::default(),
+        );
         assert!(matches!(result, Err(RenameRoomError::PathExists(_))));
     }
 }
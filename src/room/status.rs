@@ -0,0 +1,102 @@
+//! Git status of a room shown in the sidebar: uncommitted changes, commits
+//! ahead/behind its upstream and base branch, and stashes.
+
+use std::path::Path;
+
+use crate::git::{AheadBehind, ahead_behind_from};
+
+use super::remove::DirtyStatus;
+
+/// Git status of a room at the time it was checked.
+#[derive(Debug, Clone, Default)]
+pub struct RoomGitStatus {
+    /// Uncommitted changes, if the check succeeded.
+    pub dirty: Option<DirtyStatus>,
+
+    /// Commits ahead/behind the branch's upstream, if it has one.
+    pub upstream: Option<AheadBehind>,
+
+    /// Commits ahead/behind the room's base branch, if it has one.
+    pub base: Option<AheadBehind>,
+
+    /// Stash entries made on the room's branch.
+    pub stashes: usize,
+}
+
+impl RoomGitStatus {
+    /// Check the room at `path`, on `branch`, against `base_branch`.
+    ///
+    /// `stash_branches` is the repository-wide list from
+    /// `list_stash_branches_from`, so it's only read once for all rooms.
+    /// Checks that fail leave their field empty.
+    pub fn check(
+        path: &Path,
+        branch: Option<&str>,
+        base_branch: Option<&str>,
+        stash_branches: &[Option<String>],
+    ) -> Self {
+        let base = base_branch
+            .filter(|base| Some(*base) != branch)
+            .and_then(|base| ahead_behind_from(path, base).ok().flatten());
+        let upstream = branch.and_then(|_| ahead_behind_from(path, "@{upstream}").ok().flatten());
+        let stashes = branch.map_or(0, |branch| {
+            stash_branches
+                .iter()
+                .filter(|stash| stash.as_deref() == Some(branch))
+                .count()
+        });
+
+        Self {
+            dirty: DirtyStatus::check(path).ok(),
+            upstream,
+            base,
+            stashes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+    }
+
+    #[test]
+    fn test_check_room_status() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let repo = temp_dir.path();
+        git(repo, &["init", "-b", "feature"]);
+        git(repo, &["config", "user.email", "test@test.com"]);
+        git(repo, &["config", "user.name", "Test"]);
+        git(repo, &["commit", "--allow-empty", "-m", "initial"]);
+        git(repo, &["branch", "main"]);
+        git(repo, &["commit", "--allow-empty", "-m", "feature work"]);
+        std::fs::write(repo.join("notes.txt"), "todo").unwrap();
+
+        let stashes = vec![Some("feature".to_string()), Some("main".to_string()), None];
+        let status = RoomGitStatus::check(repo, Some("feature"), Some("main"), &stashes);
+
+        assert_eq!(status.dirty.unwrap().untracked_count, 1);
+        assert_eq!(status.upstream, None);
+        assert_eq!(
+            status.base,
+            Some(AheadBehind {
+                ahead: 1,
+                behind: 0
+            })
+        );
+        assert_eq!(status.stashes, 1);
+
+        // A room on its base branch has nothing to compare
+        let status = RoomGitStatus::check(repo, Some("main"), Some("main"), &stashes);
+        assert_eq!(status.base, None);
+    }
+}
@@ -0,0 +1,276 @@
+//! Snapshots of uncommitted work taken before a room is deleted.
+//!
+//! Rooms are removed with `git worktree remove --force`, which throws away
+//! modified and untracked files. Before that, the worktree's changes are
+//! committed to a hidden ref under `refs/rooms/trash/`, the way `git stash`
+//! does, so an undo can recreate the room and put the files back. Ignored
+//! files such as `node_modules` are not saved.
+
+use std::fs;
+use std::path::Path;
+
+use crate::git::command::{CommandError, GitCommand};
+use crate::git::find_remote_branch_from;
+
+/// Prefix of the refs holding snapshots of deleted rooms.
+const TRASH_REF_PREFIX: &str = "refs/rooms/trash";
+
+/// Identity for snapshot commits, so they work without `user.name` configured.
+const SNAPSHOT_AUTHOR: (&str, &str) = ("rooms", "rooms@localhost");
+
+/// A room's git state just before it was deleted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoomSnapshot {
+    /// Branch checked out in the room; `None` for a detached HEAD.
+    pub branch: Option<String>,
+    /// Commit checked out in the room.
+    pub head: String,
+    /// Ref of a commit holding the room's uncommitted and untracked files,
+    /// or `None` if the room was clean.
+    pub changes_ref: Option<String>,
+}
+
+/// Save the state of the worktree at `worktree_path` before deleting it.
+///
+/// Returns `None` when the worktree is already gone. Staged and unstaged
+/// changes are saved together; the real index is left untouched.
+///
+/// # Errors
+///
+/// Returns an error if git fails to read the worktree or write the snapshot.
+pub fn snapshot_worktree(
+    repo_root: &Path,
+    worktree_path: &Path,
+    room_name: &str,
+) -> Result<Option<RoomSnapshot>, CommandError> {
+    if !worktree_path.exists() {
+        return Ok(None);
+    }
+
+    let head = GitCommand::new("rev-parse")
+        .arg("HEAD")
+        .current_dir(worktree_path)
+        .run_checked()?
+        .stdout;
+    let branch = GitCommand::new("symbolic-ref")
+        .args(&["--quiet", "--short", "HEAD"])
+        .current_dir(worktree_path)
+        .run()?;
+    let branch = branch.success().then_some(branch.stdout);
+
+    let status = GitCommand::new("status")
+        .arg("--porcelain")
+        .current_dir(worktree_path)
+        .run_checked()?;
+    if status.stdout.is_empty() {
+        return Ok(Some(RoomSnapshot {
+            branch,
+            head,
+            changes_ref: None,
+        }));
+    }
+
+    let tree = write_worktree_tree(worktree_path)?;
+    let commit = GitCommand::new("commit-tree")
+        .args(&[
+            &tree,
+            "-p",
+            &head,
+            "-m",
+            &format!("rooms: changes in {room_name}"),
+        ])
+        .env("GIT_AUTHOR_NAME", SNAPSHOT_AUTHOR.0)
+        .env("GIT_AUTHOR_EMAIL", SNAPSHOT_AUTHOR.1)
+        .env("GIT_COMMITTER_NAME", SNAPSHOT_AUTHOR.0)
+        .env("GIT_COMMITTER_EMAIL", SNAPSHOT_AUTHOR.1)
+        .current_dir(worktree_path)
+        .run_checked()?
+        .stdout;
+
+    // Timestamped, so deleting a room twice under one name keeps both
+    let changes_ref = format!(
+        "{TRASH_REF_PREFIX}/{room_name}/{}",
+        chrono::Utc::now().timestamp_millis()
+    );
+    GitCommand::new("update-ref")
+        .args(&[&changes_ref, &commit])
+        .current_dir(repo_root)
+        .run_checked()?;
+
+    Ok(Some(RoomSnapshot {
+        branch,
+        head,
+        changes_ref: Some(changes_ref),
+    }))
+}
+
+/// Write a tree of every tracked and untracked file in the worktree.
+///
+/// Uses a copy of the index so staged files the worktree deleted are
+/// handled like `git add -A` would, without changing what's staged.
+fn write_worktree_tree(worktree_path: &Path) -> Result<String, CommandError> {
+    let git_path = |name: &str| -> Result<std::path::PathBuf, CommandError> {
+        let result = GitCommand::new("rev-parse")
+            .args(&["--git-path", name])
+            .current_dir(worktree_path)
+            .run_checked()?;
+        Ok(worktree_path.join(result.stdout))
+    };
+    let index = git_path("index")?;
+    let snapshot_index = git_path("rooms-snapshot-index")?;
+    // Without a copy, `git add -A` starts from an empty index; same tree, just slower.
+    let _ = fs::copy(&index, &snapshot_index);
+
+    let tree = GitCommand::new("add")
+        .arg("-A")
+        .env("GIT_INDEX_FILE", &snapshot_index)
+        .current_dir(worktree_path)
+        .run_checked()
+        .and_then(|_| {
+            GitCommand::new("write-tree")
+                .env("GIT_INDEX_FILE", &snapshot_index)
+                .current_dir(worktree_path)
+                .run_checked()
+        });
+    let _ = fs::remove_file(&snapshot_index);
+    Ok(tree?.stdout)
+}
+
+/// Put the files saved in `changes_ref` back into the worktree at `worktree_path`.
+///
+/// The worktree ends up matching the snapshot; the changes are left unstaged.
+///
+/// # Errors
+///
+/// Returns an error if git fails, e.g. when the ref no longer exists.
+pub fn restore_snapshot(worktree_path: &Path, changes_ref: &str) -> Result<(), CommandError> {
+    GitCommand::new("restore")
+        .args(&["--source", changes_ref, "--worktree", "--", "."])
+        .current_dir(worktree_path)
+        .run_checked()?;
+    Ok(())
+}
+
+/// The commit a deleted room on `branch` would be recreated at.
+///
+/// That's the local branch's tip, or a remote-tracking branch's when only a
+/// remote has it. Returns `None` if neither exists: the branch is then
+/// recreated at the snapshot's commit.
+///
+/// # Errors
+///
+/// Returns an error if git fails to execute.
+pub fn branch_tip(repo_root: &Path, branch: &str) -> Result<Option<String>, CommandError> {
+    if let Some(tip) = rev_parse(repo_root, &format!("refs/heads/{branch}"))? {
+        return Ok(Some(tip));
+    }
+    match find_remote_branch_from(repo_root, branch)? {
+        Some(remote) => rev_parse(repo_root, &remote),
+        None => Ok(None),
+    }
+}
+
+fn rev_parse(repo_root: &Path, refname: &str) -> Result<Option<String>, CommandError> {
+    let result = GitCommand::new("rev-parse")
+        .args(&["--verify", "--quiet", &format!("{refname}^{{commit}}")])
+        .current_dir(repo_root)
+        .run()?;
+    Ok(result.success().then_some(result.stdout))
+}
+
+/// Delete a snapshot ref once it has been restored or is no longer wanted.
+///
+/// # Errors
+///
+/// Returns an error if git fails to delete the ref.
+pub fn delete_snapshot(repo_root: &Path, changes_ref: &str) -> Result<(), CommandError> {
+    GitCommand::new("update-ref")
+        .args(&["-d", changes_ref])
+        .current_dir(repo_root)
+        .run_checked()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    #[test]
+    fn test_snapshot_and_restore() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let repo = temp_dir.path().join("repo");
+        let room = temp_dir.path().join("quick-fox");
+        fs::create_dir(&repo).unwrap();
+        git(&repo, &["init", "-b", "main"]);
+        git(&repo, &["config", "user.email", "test@test.com"]);
+        git(&repo, &["config", "user.name", "Test"]);
+        fs::write(repo.join("tracked.txt"), "one").unwrap();
+        fs::write(repo.join("gone.txt"), "gone").unwrap();
+        git(&repo, &["add", "."]);
+        git(&repo, &["commit", "-m", "initial"]);
+        git(
+            &repo,
+            &["worktree", "add", "-b", "quick-fox", room.to_str().unwrap()],
+        );
+
+        let clean = snapshot_worktree(&repo, &room, "quick-fox")
+            .unwrap()
+            .unwrap();
+        assert_eq!(clean.branch.as_deref(), Some("quick-fox"));
+        assert_eq!(clean.head, git(&repo, &["rev-parse", "HEAD"]));
+        assert_eq!(clean.changes_ref, None);
+
+        fs::write(room.join("tracked.txt"), "two").unwrap();
+        fs::write(room.join("staged.txt"), "staged").unwrap();
+        fs::write(room.join("untracked.txt"), "new").unwrap();
+        fs::remove_file(room.join("gone.txt")).unwrap();
+        git(&room, &["add", "staged.txt"]);
+        let status = git(&room, &["status", "--porcelain"]);
+
+        let snapshot = snapshot_worktree(&repo, &room, "quick-fox")
+            .unwrap()
+            .unwrap();
+        let changes_ref = snapshot.changes_ref.unwrap();
+        assert!(changes_ref.starts_with("refs/rooms/trash/quick-fox/"));
+        // Taking the snapshot leaves the room as it was
+        assert_eq!(git(&room, &["status", "--porcelain"]), status);
+
+        git(
+            &repo,
+            &["worktree", "remove", "--force", room.to_str().unwrap()],
+        );
+        git(
+            &repo,
+            &["worktree", "add", room.to_str().unwrap(), "quick-fox"],
+        );
+        restore_snapshot(&room, &changes_ref).unwrap();
+        assert_eq!(fs::read_to_string(room.join("tracked.txt")).unwrap(), "two");
+        assert_eq!(
+            fs::read_to_string(room.join("staged.txt")).unwrap(),
+            "staged"
+        );
+        assert_eq!(
+            fs::read_to_string(room.join("untracked.txt")).unwrap(),
+            "new"
+        );
+        assert!(!room.join("gone.txt").exists());
+
+        delete_snapshot(&repo, &changes_ref).unwrap();
+        assert!(restore_snapshot(&room, &changes_ref).is_err());
+        assert_eq!(
+            snapshot_worktree(&repo, &temp_dir.path().join("missing"), "missing").unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! Background watcher for worktrees changed outside of rooms.
+//!
+//! Worktrees added, removed or switched with plain git in another terminal
+//! should show up without a restart. A thread polls a cheap fingerprint of
+//! git's worktree metadata and the rooms directory, and only runs
+//! `git worktree list` when it changes, so the UI thread never waits on git.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use crate::git::command::CommandError;
+use crate::git::{Worktree, get_git_common_dir_from, list_worktrees_from};
+
+/// Result of listing worktrees after a change was noticed.
+pub type WorktreeListResult = Result<Vec<Worktree>, CommandError>;
+
+/// Handle to a background thread that watches a repository's worktrees.
+///
+/// The thread stops when the handle is dropped.
+pub struct WorktreeWatcher {
+    receiver: mpsc::Receiver<WorktreeListResult>,
+    stop: Arc<AtomicBool>,
+}
+
+impl WorktreeWatcher {
+    /// Start watching the worktrees of `repo_root` and the entries of
+    /// `rooms_dir`, checking every `interval`.
+    pub fn spawn(repo_root: PathBuf, rooms_dir: PathBuf, interval: Duration) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+
+        thread::spawn(move || {
+            let Ok(common_dir) = get_git_common_dir_from(&repo_root) else {
+                return;
+            };
+            let mut last = fingerprint(&common_dir, &rooms_dir);
+            while !thread_stop.load(Ordering::Relaxed) {
+                thread::sleep(interval);
+                let current = fingerprint(&common_dir, &rooms_dir);
+                if current == last {
+                    continue;
+                }
+                last = current;
+                if tx.send(list_worktrees_from(&repo_root)).is_err() {
+                    return;
+                }
+            }
+        });
+
+        Self { receiver: rx, stop }
+    }
+
+    /// The newest worktree list, if anything changed since the last call.
+    pub fn poll(&self) -> Option<WorktreeListResult> {
+        self.receiver.try_iter().last()
+    }
+}
+
+impl Drop for WorktreeWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Snapshot of what `git worktree list` depends on, sorted by path.
+///
+/// Covers the primary worktree's HEAD, each linked worktree's HEAD, gitdir
+/// and lock files under `.git/worktrees`, and the names of the rooms
+/// directory's subdirectories. Files there (state, ports, logs and their
+/// temp and lock files) are left out, as rooms rewrites them itself.
+/// Directory modification times aren't used: git touches them for every
+/// index update.
+fn fingerprint(common_dir: &Path, rooms_dir: &Path) -> Vec<(PathBuf, String)> {
+    let mut entries = vec![read_entry(&common_dir.join("HEAD"))];
+    for dir in list_dir(&common_dir.join("worktrees")) {
+        for file in ["HEAD", "gitdir", "locked"] {
+            entries.push(read_entry(&dir.join(file)));
+        }
+    }
+    entries.extend(
+        list_dir(rooms_dir)
+            .into_iter()
+            .filter(|path| path.is_dir())
+            .map(|path| (path, String::new())),
+    );
+    entries.sort();
+    entries
+}
+
+fn read_entry(path: &Path) -> (PathBuf, String) {
+    (
+        path.to_path_buf(),
+        fs::read_to_string(path).unwrap_or_default(),
+    )
+}
+
+fn list_dir(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+    }
+
+    #[test]
+    fn test_fingerprint_tracks_worktrees() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let repo = temp_dir.path().join("repo");
+        let rooms_dir = temp_dir.path().join("rooms");
+        fs::create_dir_all(&repo).unwrap();
+        fs::create_dir_all(&rooms_dir).unwrap();
+        git(&repo, &["init"]);
+        git(&repo, &["config", "user.email", "test@test.com"]);
+        git(&repo, &["config", "user.name", "Test"]);
+        git(&repo, &["commit", "--allow-empty", "-m", "initial"]);
+        let common_dir = repo.join(".git");
+
+        let before = fingerprint(&common_dir, &rooms_dir);
+        // Index updates alone don't count as a change
+        git(&repo, &["status"]);
+        assert_eq!(fingerprint(&common_dir, &rooms_dir), before);
+        // Neither do the files rooms keeps next to the rooms
+        for file in [
+            "state.json",
+            "ports.json.lock",
+            "ports.json.1.0.tmp",
+            "events.log",
+        ] {
+            fs::write(rooms_dir.join(file), "{}").unwrap();
+        }
+        assert_eq!(fingerprint(&common_dir, &rooms_dir), before);
+
+        git(
+            &repo,
+            &["worktree", "add", "-q", "-b", "side", "../rooms/side"],
+        );
+        let added = fingerprint(&common_dir, &rooms_dir);
+        assert_ne!(added, before);
+
+        git(&rooms_dir.join("side"), &["checkout", "-q", "--detach"]);
+        assert_ne!(fingerprint(&common_dir, &rooms_dir), added);
+    }
+
+    #[test]
+    fn test_watcher_sends_worktrees_on_change() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let repo = temp_dir.path().join("repo");
+        let rooms_dir = temp_dir.path().join("rooms");
+        fs::create_dir_all(&repo).unwrap();
+        fs::create_dir_all(&rooms_dir).unwrap();
+        git(&repo, &["init"]);
+        git(&repo, &["config", "user.email", "test@test.com"]);
+        git(&repo, &["config", "user.name", "Test"]);
+        git(&repo, &["commit", "--allow-empty", "-m", "initial"]);
+
+        let watcher =
+            WorktreeWatcher::spawn(repo.clone(), rooms_dir.clone(), Duration::from_millis(10));
+        // Let the watcher take its first snapshot
+        thread::sleep(Duration::from_millis(100));
+        assert!(watcher.poll().is_none());
+
+        git(
+            &repo,
+            &["worktree", "add", "-q", "-b", "side", "../rooms/side"],
+        );
+        // The change may be noticed partway through `worktree add`
+        let mut count = 0;
+        for _ in 0..100 {
+            if let Some(result) = watcher.poll() {
+                count = result.unwrap().len();
+                if count == 2 {
+                    break;
+                }
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(count, 2);
+    }
+}
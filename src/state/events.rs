@@ -23,6 +23,12 @@ pub enum EventType {
     PostCreateCompleted,
     /// Post-create commands failed.
     PostCreateFailed,
+    /// A room's shell process exited.
+    ShellExited,
+    /// A room service was started.
+    ServiceStarted,
+    /// A room service exited.
+    ServiceExited,
     /// An error occurred.
     Error,
 }
@@ -154,6 +160,30 @@ impl EventLog {
         let _ = self.log(event);
     }
 
+    /// Log a shell process in a terminal tab exiting.
+    pub fn log_shell_exited(&self, room_name: &str, tab_name: &str, exit_code: u32) {
+        let event = Event::new(EventType::ShellExited)
+            .with_room(room_name)
+            .with_details(format!("{}: exit code {}", tab_name, exit_code));
+        let _ = self.log(event);
+    }
+
+    /// Log a service starting.
+    pub fn log_service_started(&self, room_name: &str, service_name: &str) {
+        let event = Event::new(EventType::ServiceStarted)
+            .with_room(room_name)
+            .with_details(service_name);
+        let _ = self.log(event);
+    }
+
+    /// Log a service exiting.
+    pub fn log_service_exited(&self, room_name: &str, service_name: &str, exit_code: u32) {
+        let event = Event::new(EventType::ServiceExited)
+            .with_room(room_name)
+            .with_details(format!("{}: exit code {}", service_name, exit_code));
+        let _ = self.log(event);
+    }
+
     /// Log an error event.
     pub fn log_error(&self, room_name: Option<&str>, error: &str) {
         let mut event = Event::new(EventType::Error).with_details(error);
@@ -0,0 +1,58 @@
+//! Lock files guarding read-modify-write updates of files in the rooms
+//! directory, shared by the TUI and headless `rooms` commands.
+
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+/// How long to wait for another process or thread to release a lock.
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A lock older than this was left behind by a process that died holding it.
+const STALE_LOCK_AGE: Duration = Duration::from_secs(30);
+
+/// Exclusive lock held through a lock file, released when dropped.
+pub(super) struct FileLock {
+    path: PathBuf,
+}
+
+impl FileLock {
+    /// Create the lock file at `path`, waiting while someone else holds it.
+    ///
+    /// Fails with [`io::ErrorKind::TimedOut`] if it isn't released in time.
+    pub(super) fn acquire(path: PathBuf) -> io::Result<Self> {
+        let deadline = Instant::now() + LOCK_TIMEOUT;
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(_) => return Ok(Self { path }),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                    if lock_is_stale(&path) {
+                        let _ = fs::remove_file(&path);
+                        continue;
+                    }
+                    if Instant::now() >= deadline {
+                        return Err(io::ErrorKind::TimedOut.into());
+                    }
+                    thread::sleep(Duration::from_millis(10));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn lock_is_stale(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age > STALE_LOCK_AGE)
+}
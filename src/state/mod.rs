@@ -2,17 +2,24 @@
 #![allow(dead_code)]
 
 mod events;
+mod lock;
+mod ports;
 mod transient;
 
 pub use events::EventLog;
+use lock::FileLock;
+pub use ports::{PortAllocations, PortBlock, PortsError};
 #[allow(unused_imports)]
 pub use transient::{TransientRoomState, TransientStateStore};
 
 // Re-export RoomStatus from room::model for backward compatibility
 pub use crate::room::RoomStatus;
 
+use crate::room::{RoomInfo, short_sha};
+
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
@@ -21,6 +28,12 @@ use uuid::Uuid;
 /// State file name.
 pub const STATE_FILE: &str = "state.json";
 
+/// Lock file held while `state.json` is reloaded, changed and written back.
+const LOCK_FILE: &str = "state.json.lock";
+
+/// Number of deleted rooms kept for undo.
+pub const TRASH_LIMIT: usize = 10;
+
 #[derive(Error, Debug)]
 pub enum StateError {
     #[error("failed to read state file: {0}")]
@@ -34,10 +47,17 @@ pub enum StateError {
         path: String,
         source: std::io::Error,
     },
+
+    #[error("state file is locked by another process: {}", .0.display())]
+    Locked(PathBuf),
 }
 
+/// Lock on `state.json`, held from reloading the state until it's saved.
+#[must_use]
+pub struct StateLock(FileLock);
+
 /// A managed workspace backed by a git worktree.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Room {
     /// Unique identifier for this room.
     pub id: Uuid,
@@ -45,8 +65,9 @@ pub struct Room {
     /// User-given or generated name.
     pub name: String,
 
-    /// Git branch name.
-    pub branch: String,
+    /// Git branch name; `None` for a detached HEAD.
+    #[serde(default, deserialize_with = "deserialize_branch")]
+    pub branch: Option<String>,
 
     /// Path to the worktree directory.
     pub path: PathBuf,
@@ -64,11 +85,19 @@ pub struct Room {
     /// Last error message if status is Error.
     #[serde(default)]
     pub last_error: Option<String>,
+
+    /// Branch the room's branch was created from, if it was newly created.
+    #[serde(default)]
+    pub base_branch: Option<String>,
+
+    /// Free-form notes written by the user.
+    #[serde(default)]
+    pub notes: Option<String>,
 }
 
 impl Room {
     /// Create a new room with the given name, branch, and path.
-    pub fn new(name: String, branch: String, path: PathBuf) -> Self {
+    pub fn new(name: String, branch: Option<String>, path: PathBuf) -> Self {
         let now = Utc::now();
         Self {
             id: Uuid::new_v4(),
@@ -79,6 +108,8 @@ impl Room {
             last_used_at: now,
             status: RoomStatus::Creating,
             last_error: None,
+            base_branch: None,
+            notes: None,
         }
     }
 
@@ -100,12 +131,144 @@ impl Room {
     }
 }
 
+/// What [`RoomsState::reconcile`] changed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Reconciled {
+    /// Rooms newly marked as orphaned.
+    pub orphaned: usize,
+    /// Records dropped because git no longer lists their worktree.
+    pub pruned: usize,
+    /// Whether anything changed, so the state needs saving.
+    pub changed: bool,
+}
+
+/// A deleted room that can be brought back.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TrashedRoom {
+    /// Name the room had.
+    pub name: String,
+
+    /// Branch the room had checked out; `None` for a detached room.
+    pub branch: Option<String>,
+
+    /// Commit checked out when the room was deleted.
+    pub head: String,
+
+    /// Hidden ref holding the room's uncommitted changes, if it had any.
+    #[serde(default)]
+    pub changes_ref: Option<String>,
+
+    /// Base branch recorded for the room.
+    #[serde(default)]
+    pub base_branch: Option<String>,
+
+    /// Notes the room had.
+    #[serde(default)]
+    pub notes: Option<String>,
+
+    /// When the room was deleted.
+    pub deleted_at: DateTime<Utc>,
+}
+
+impl TrashedRoom {
+    /// Branch name, or `detached @ <short sha>` for a detached room.
+    pub fn branch_label(&self) -> String {
+        match &self.branch {
+            Some(branch) => branch.clone(),
+            None => format!("detached @ {}", short_sha(&self.head)),
+        }
+    }
+}
+
+/// Order in which rooms are listed within each sidebar section.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    /// Alphabetically by room name.
+    #[default]
+    Name,
+
+    /// Most recently used first.
+    RecentlyUsed,
+
+    /// Most recently created first.
+    Created,
+}
+
+impl SortOrder {
+    /// The next sort order in the cycle.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Name => Self::RecentlyUsed,
+            Self::RecentlyUsed => Self::Created,
+            Self::Created => Self::Name,
+        }
+    }
+
+    /// Human-readable label for status messages.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::RecentlyUsed => "recently used",
+            Self::Created => "creation time",
+        }
+    }
+}
+
+/// UI layout saved on quit and restored on the next launch.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct SessionLayout {
+    /// Rooms that had a running shell, in sidebar order.
+    pub active_rooms: Vec<String>,
+
+    /// Terminal tab names per room.
+    pub tabs: BTreeMap<String, Vec<String>>,
+
+    /// Index of the active tab per room.
+    pub active_tabs: BTreeMap<String, usize>,
+
+    /// Name of the selected room.
+    pub selected_room: Option<String>,
+
+    /// Whether the terminal (rather than the sidebar) had focus.
+    pub terminal_focused: bool,
+
+    /// Whether the sidebar was visible.
+    pub sidebar_visible: bool,
+}
+
+impl Default for SessionLayout {
+    fn default() -> Self {
+        Self {
+            active_rooms: Vec::new(),
+            tabs: BTreeMap::new(),
+            active_tabs: BTreeMap::new(),
+            selected_room: None,
+            terminal_focused: false,
+            sidebar_visible: true,
+        }
+    }
+}
+
 /// Persistent state for all rooms in a repository.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct RoomsState {
     /// All tracked rooms.
     #[serde(default)]
     pub rooms: Vec<Room>,
+
+    /// Sidebar sort order chosen by the user.
+    #[serde(default)]
+    pub sort_order: SortOrder,
+
+    /// Session layout from the last run.
+    #[serde(default)]
+    pub layout: SessionLayout,
+
+    /// Recently deleted rooms, newest first.
+    #[serde(default)]
+    pub trash: Vec<TrashedRoom>,
 }
 
 impl RoomsState {
@@ -130,6 +293,27 @@ impl RoomsState {
         Self::load(state_path)
     }
 
+    /// Lock `state.json` in `rooms_dir` and reload it into `self`.
+    ///
+    /// Picks up changes other `rooms` processes made since the state was
+    /// loaded, such as a `rooms delete` from the command line, so saving
+    /// doesn't overwrite them. Keep the lock until the changed state is
+    /// saved. If the file can't be locked or read, `self` is left as it was.
+    pub fn reload_locked<P: AsRef<Path>>(&mut self, rooms_dir: P) -> Result<StateLock, StateError> {
+        let rooms_dir = rooms_dir.as_ref();
+        fs::create_dir_all(rooms_dir).map_err(|e| StateError::CreateDir {
+            path: rooms_dir.to_string_lossy().to_string(),
+            source: e,
+        })?;
+        let lock_path = rooms_dir.join(LOCK_FILE);
+        let lock = FileLock::acquire(lock_path.clone()).map_err(|e| match e.kind() {
+            std::io::ErrorKind::TimedOut => StateError::Locked(lock_path),
+            _ => e.into(),
+        })?;
+        *self = Self::load_from_rooms_dir(rooms_dir)?;
+        Ok(StateLock(lock))
+    }
+
     /// Save state to a JSON file atomically.
     ///
     /// Writes to a temporary file first, then renames to ensure atomicity.
@@ -218,6 +402,131 @@ impl RoomsState {
     pub fn find_by_path(&self, path: &Path) -> Option<&Room> {
         self.rooms.iter().find(|r| r.path == path)
     }
+
+    /// Record a newly created room, replacing any stale record with the same name.
+    pub fn record_created(
+        &mut self,
+        name: &str,
+        branch: Option<&str>,
+        path: &Path,
+        base_branch: Option<String>,
+    ) {
+        self.remove_by_name(name);
+        let mut room = Room::new(
+            name.to_string(),
+            branch.map(str::to_string),
+            path.to_path_buf(),
+        );
+        room.base_branch = base_branch;
+        room.set_ready();
+        self.add_room(room);
+    }
+
+    /// Record a room rename, keeping its metadata.
+    pub fn record_renamed(&mut self, old_name: &str, new_name: &str, new_path: &Path) {
+        self.remove_by_name(new_name);
+        if let Some(room) = self.find_by_name_mut(old_name) {
+            room.name = new_name.to_string();
+            room.path = new_path.to_path_buf();
+        }
+    }
+
+    /// Keep a deleted room for undo, replacing an older one with the same name.
+    ///
+    /// Returns the entries that no longer fit in the trash, so their
+    /// snapshot refs can be deleted.
+    pub fn record_trashed(&mut self, room: TrashedRoom) -> Vec<TrashedRoom> {
+        let mut dropped = self
+            .take_trashed(&room.name)
+            .into_iter()
+            .collect::<Vec<_>>();
+        self.trash.insert(0, room);
+        if self.trash.len() > TRASH_LIMIT {
+            dropped.extend(self.trash.drain(TRASH_LIMIT..));
+        }
+        dropped
+    }
+
+    /// Remove a deleted room from the trash.
+    pub fn take_trashed(&mut self, name: &str) -> Option<TrashedRoom> {
+        let index = self.trash.iter().position(|room| room.name == name)?;
+        Some(self.trash.remove(index))
+    }
+
+    /// Mark a room as used now. Returns false if the room isn't tracked.
+    pub fn touch(&mut self, name: &str) -> bool {
+        match self.find_by_name_mut(name) {
+            Some(room) => {
+                room.touch();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Reconcile tracked rooms with rooms discovered from `git worktree list`.
+    ///
+    /// Worktrees without a record are tracked using the directory's creation
+    /// time, known rooms get their branch and path refreshed, records of
+    /// worktrees git no longer lists are dropped, and records whose worktree
+    /// is missing on disk are marked Orphaned via `validate_paths`.
+    pub fn reconcile(&mut self, discovered: &[RoomInfo]) -> Reconciled {
+        let mut changed = false;
+        for info in discovered {
+            let branch = info.branch.clone();
+            match self.find_by_name_mut(&info.name) {
+                Some(room) => {
+                    if room.branch != branch || room.path != info.path {
+                        room.branch = branch;
+                        room.path = info.path.clone();
+                        changed = true;
+                    }
+                    let restored = room.status == RoomStatus::Orphaned && info.path.exists();
+                    if restored || room.status == RoomStatus::Creating {
+                        room.set_ready();
+                        changed = true;
+                    }
+                }
+                None => {
+                    let mut room = Room::new(info.name.clone(), branch, info.path.clone());
+                    if let Some(created_at) = directory_created_at(&info.path) {
+                        room.created_at = created_at;
+                        room.last_used_at = created_at;
+                    }
+                    room.set_ready();
+                    self.add_room(room);
+                    changed = true;
+                }
+            }
+        }
+
+        let tracked = self.rooms.len();
+        self.rooms
+            .retain(|room| discovered.iter().any(|info| info.name == room.name));
+        let pruned = tracked - self.rooms.len();
+
+        let orphaned = self.validate_paths();
+        Reconciled {
+            orphaned,
+            pruned,
+            changed: changed || pruned > 0 || orphaned > 0,
+        }
+    }
+}
+
+/// Read a room's branch, treating the empty string older versions saved
+/// for detached rooms as no branch.
+fn deserialize_branch<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    Ok(Option::<String>::deserialize(deserializer)?.filter(|branch| !branch.is_empty()))
+}
+
+/// Best-effort creation time of a directory (falls back to modification time).
+fn directory_created_at(path: &Path) -> Option<DateTime<Utc>> {
+    let metadata = fs::metadata(path).ok()?;
+    let time = metadata.created().or_else(|_| metadata.modified()).ok()?;
+    Some(DateTime::<Utc>::from(time))
 }
 
 #[cfg(test)]
@@ -228,12 +537,12 @@ mod tests {
     fn test_room_new() {
         let room = Room::new(
             "test-room".to_string(),
-            "test-branch".to_string(),
+            Some("test-branch".to_string()),
             PathBuf::from("/path/to/room"),
         );
 
         assert_eq!(room.name, "test-room");
-        assert_eq!(room.branch, "test-branch");
+        assert_eq!(room.branch.as_deref(), Some("test-branch"));
         assert_eq!(room.status, RoomStatus::Creating);
         assert!(room.last_error.is_none());
     }
@@ -242,7 +551,7 @@ mod tests {
     fn test_room_set_error() {
         let mut room = Room::new(
             "test".to_string(),
-            "test".to_string(),
+            Some("test".to_string()),
             PathBuf::from("/test"),
         );
 
@@ -256,7 +565,7 @@ mod tests {
     fn test_room_set_ready() {
         let mut room = Room::new(
             "test".to_string(),
-            "test".to_string(),
+            Some("test".to_string()),
             PathBuf::from("/test"),
         );
         room.set_error("error".to_string());
@@ -277,7 +586,7 @@ mod tests {
         let mut state = RoomsState::default();
         let room = Room::new(
             "my-room".to_string(),
-            "my-branch".to_string(),
+            Some("my-branch".to_string()),
             PathBuf::from("/rooms/my-room"),
         );
         let room_id = room.id;
@@ -295,12 +604,12 @@ mod tests {
         let mut state = RoomsState::default();
         state.add_room(Room::new(
             "room1".to_string(),
-            "branch1".to_string(),
+            Some("branch1".to_string()),
             PathBuf::from("/r1"),
         ));
         state.add_room(Room::new(
             "room2".to_string(),
-            "branch2".to_string(),
+            Some("branch2".to_string()),
             PathBuf::from("/r2"),
         ));
 
@@ -320,7 +629,7 @@ mod tests {
         let mut state = RoomsState::default();
         state.add_room(Room::new(
             "persisted-room".to_string(),
-            "persisted-branch".to_string(),
+            Some("persisted-branch".to_string()),
             PathBuf::from("/rooms/persisted"),
         ));
         state.save(&state_path).unwrap();
@@ -329,7 +638,7 @@ mod tests {
         let loaded = RoomsState::load(&state_path).unwrap();
         assert_eq!(loaded.rooms.len(), 1);
         assert_eq!(loaded.rooms[0].name, "persisted-room");
-        assert_eq!(loaded.rooms[0].branch, "persisted-branch");
+        assert_eq!(loaded.rooms[0].branch.as_deref(), Some("persisted-branch"));
     }
 
     #[test]
@@ -351,7 +660,11 @@ mod tests {
 
     #[test]
     fn test_room_status_serialization() {
-        let room = Room::new("test".to_string(), "test".to_string(), PathBuf::from("/t"));
+        let room = Room::new(
+            "test".to_string(),
+            Some("test".to_string()),
+            PathBuf::from("/t"),
+        );
 
         let json = serde_json::to_string(&room).unwrap();
         assert!(json.contains("\"status\":\"creating\""));
@@ -370,7 +683,7 @@ mod tests {
         // Add a room with a non-existent path
         let mut room = Room::new(
             "missing-room".to_string(),
-            "missing-branch".to_string(),
+            Some("missing-branch".to_string()),
             PathBuf::from("/this/path/does/not/exist"),
         );
         room.status = RoomStatus::Ready;
@@ -379,7 +692,7 @@ mod tests {
         // Add a room with an existing path (temp directory)
         let mut existing_room = Room::new(
             "existing-room".to_string(),
-            "existing-branch".to_string(),
+            Some("existing-branch".to_string()),
             temp_dir.path().to_path_buf(),
         );
         existing_room.status = RoomStatus::Ready;
@@ -398,13 +711,227 @@ mod tests {
         );
     }
 
+    fn make_room_info(name: &str, path: &Path) -> RoomInfo {
+        RoomInfo {
+            name: name.to_string(),
+            branch: Some(name.to_string()),
+            head: None,
+            path: path.to_path_buf(),
+            status: RoomStatus::Ready,
+            is_prunable: false,
+            last_error: None,
+            is_primary: false,
+        }
+    }
+
+    #[test]
+    fn test_reconcile_tracks_new_rooms_and_flags_orphans() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut state = RoomsState::default();
+        let gone = Path::new("/this/path/does/not/exist");
+        state.record_created("gone", Some("gone"), gone, None);
+        state.record_created("forgotten", Some("forgotten"), gone, None);
+
+        let reconciled = state.reconcile(&[
+            make_room_info("new-room", temp_dir.path()),
+            make_room_info("gone", gone),
+        ]);
+
+        assert_eq!(
+            reconciled,
+            Reconciled {
+                orphaned: 1,
+                pruned: 1,
+                changed: true,
+            }
+        );
+        assert_eq!(
+            state.find_by_name("gone").unwrap().status,
+            RoomStatus::Orphaned
+        );
+        assert!(state.find_by_name("forgotten").is_none());
+        let tracked = state.find_by_name("new-room").unwrap();
+        assert_eq!(tracked.status, RoomStatus::Ready);
+        assert_eq!(tracked.branch.as_deref(), Some("new-room"));
+
+        // Nothing left to change
+        let discovered = [
+            make_room_info("new-room", temp_dir.path()),
+            make_room_info("gone", gone),
+        ];
+        assert_eq!(state.reconcile(&discovered), Reconciled::default());
+    }
+
+    #[test]
+    fn test_reconcile_keeps_existing_metadata() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut state = RoomsState::default();
+        state.record_created(
+            "kept",
+            Some("kept"),
+            temp_dir.path(),
+            Some("main".to_string()),
+        );
+        state.find_by_name_mut("kept").unwrap().notes = Some("wip".to_string());
+        let created_at = state.find_by_name("kept").unwrap().created_at;
+
+        state.reconcile(&[make_room_info("kept", temp_dir.path())]);
+
+        let room = state.find_by_name("kept").unwrap();
+        assert_eq!(room.created_at, created_at);
+        assert_eq!(room.base_branch.as_deref(), Some("main"));
+        assert_eq!(room.notes.as_deref(), Some("wip"));
+        assert_eq!(state.rooms.len(), 1);
+    }
+
+    #[test]
+    fn test_reload_locked_keeps_changes_made_elsewhere() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut state = RoomsState::default();
+        state.record_created("kept", Some("kept"), temp_dir.path(), None);
+        state.save_to_rooms_dir(temp_dir.path()).unwrap();
+
+        // Another process deletes the room meanwhile
+        let mut other = RoomsState::load_from_rooms_dir(temp_dir.path()).unwrap();
+        let metadata = other.remove_by_name("kept").unwrap();
+        other.record_trashed(TrashedRoom {
+            name: metadata.name,
+            branch: metadata.branch,
+            head: "abc123".to_string(),
+            changes_ref: Some("refs/rooms/trash/kept/1".to_string()),
+            base_branch: None,
+            notes: None,
+            deleted_at: Utc::now(),
+        });
+        other.save_to_rooms_dir(temp_dir.path()).unwrap();
+
+        let lock = state.reload_locked(temp_dir.path()).unwrap();
+        assert!(temp_dir.path().join(LOCK_FILE).exists());
+        state.sort_order = SortOrder::Created;
+        state.save_to_rooms_dir(temp_dir.path()).unwrap();
+        drop(lock);
+        assert!(!temp_dir.path().join(LOCK_FILE).exists());
+
+        let saved = RoomsState::load_from_rooms_dir(temp_dir.path()).unwrap();
+        assert!(saved.find_by_name("kept").is_none());
+        assert_eq!(saved.trash[0].name, "kept");
+        assert_eq!(saved.sort_order, SortOrder::Created);
+    }
+
+    #[test]
+    fn test_record_renamed_moves_metadata() {
+        let mut state = RoomsState::default();
+        state.record_created("old", Some("feature"), Path::new("/rooms/old"), None);
+        state.find_by_name_mut("old").unwrap().notes = Some("keep me".to_string());
+
+        state.record_renamed("old", "new", Path::new("/rooms/new"));
+
+        assert!(state.find_by_name("old").is_none());
+        let room = state.find_by_name("new").unwrap();
+        assert_eq!(room.path, PathBuf::from("/rooms/new"));
+        assert_eq!(room.branch.as_deref(), Some("feature"));
+        assert_eq!(room.notes.as_deref(), Some("keep me"));
+    }
+
+    #[test]
+    fn test_load_state_without_new_fields() {
+        let json = r#"{"rooms": [{
+            "id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "name": "legacy",
+            "branch": "legacy",
+            "path": "/rooms/legacy",
+            "created_at": "2025-01-01T00:00:00Z",
+            "last_used_at": "2025-01-02T00:00:00Z"
+        }]}"#;
+
+        let state: RoomsState = serde_json::from_str(json).unwrap();
+        assert_eq!(state.sort_order, SortOrder::Name);
+        assert_eq!(state.rooms[0].branch.as_deref(), Some("legacy"));
+        assert!(state.rooms[0].base_branch.is_none());
+        assert!(state.rooms[0].notes.is_none());
+
+        // Detached rooms used to be saved with an empty branch
+        let detached = json.replace(r#""branch": "legacy""#, r#""branch": """#);
+        let state: RoomsState = serde_json::from_str(&detached).unwrap();
+        assert_eq!(state.rooms[0].branch, None);
+        let missing = json.replace(r#""branch": "legacy","#, "");
+        let state: RoomsState = serde_json::from_str(&missing).unwrap();
+        assert_eq!(state.rooms[0].branch, None);
+    }
+
+    #[test]
+    fn test_session_layout_roundtrip() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut state = RoomsState::default();
+        assert!(state.layout.sidebar_visible);
+
+        state.layout = SessionLayout {
+            active_rooms: vec!["a".to_string(), "b".to_string()],
+            tabs: BTreeMap::from([(
+                "b".to_string(),
+                vec!["shell".to_string(), "server".to_string()],
+            )]),
+            active_tabs: BTreeMap::from([("b".to_string(), 1)]),
+            selected_room: Some("b".to_string()),
+            terminal_focused: true,
+            sidebar_visible: false,
+        };
+        state.save_to_rooms_dir(temp.path()).unwrap();
+
+        let loaded = RoomsState::load_from_rooms_dir(temp.path()).unwrap();
+        assert_eq!(loaded.layout, state.layout);
+    }
+
+    #[test]
+    fn test_trash_replaces_same_name_and_keeps_limit() {
+        let trashed = |name: &str| TrashedRoom {
+            name: name.to_string(),
+            branch: Some(name.to_string()),
+            head: "abc123".to_string(),
+            changes_ref: None,
+            base_branch: None,
+            notes: None,
+            deleted_at: Utc::now(),
+        };
+        let mut state = RoomsState::default();
+        for i in 0..TRASH_LIMIT {
+            assert!(
+                state
+                    .record_trashed(trashed(&format!("room-{i}")))
+                    .is_empty()
+            );
+        }
+
+        let dropped = state.record_trashed(trashed("room-3"));
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].name, "room-3");
+        assert_eq!(state.trash.len(), TRASH_LIMIT);
+        assert_eq!(state.trash[0].name, "room-3");
+
+        let dropped = state.record_trashed(trashed("room-new"));
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].name, "room-0");
+        assert_eq!(state.trash[0].name, "room-new");
+
+        assert_eq!(state.take_trashed("room-5").unwrap().name, "room-5");
+        assert!(state.take_trashed("room-5").is_none());
+        assert_eq!(state.trash.len(), TRASH_LIMIT - 1);
+    }
+
+    #[test]
+    fn test_sort_order_cycles() {
+        assert_eq!(SortOrder::Name.next(), SortOrder::RecentlyUsed);
+        assert_eq!(SortOrder::RecentlyUsed.next(), SortOrder::Created);
+        assert_eq!(SortOrder::Created.next(), SortOrder::Name);
+    }
+
     #[test]
     fn test_validate_paths_doesnt_double_count() {
         let mut state = RoomsState::default();
 
         let mut room = Room::new(
             "orphan".to_string(),
-            "orphan".to_string(),
+            Some("orphan".to_string()),
             PathBuf::from("/nonexistent"),
         );
         room.status = RoomStatus::Orphaned; // Already orphaned
@@ -0,0 +1,293 @@
+//! Per-room port blocks, persisted in `ports.json` next to `state.json`.
+//!
+//! Each room gets a contiguous block of ports when it is created so that the
+//! same app can run in several worktrees without port conflicts. Blocks are
+//! released when the room is removed.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::config::Ports;
+
+use super::lock::FileLock;
+
+/// Port allocations file name.
+pub const PORTS_FILE: &str = "ports.json";
+
+/// Lock file held while `ports.json` is read, changed and written back.
+const LOCK_FILE: &str = "ports.json.lock";
+
+/// Makes temp file names unique between threads of one process.
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Error, Debug)]
+pub enum PortsError {
+    #[error("failed to access ports file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("failed to parse ports file: {0}")]
+    Parse(#[from] serde_json::Error),
+
+    #[error("no free block of {0} ports left")]
+    Exhausted(u16),
+
+    #[error("ports file is locked by another process: {}", .0.display())]
+    Locked(PathBuf),
+}
+
+/// Consecutive ports reserved for one room.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortBlock {
+    /// First port in the block.
+    pub start: u16,
+    /// Number of ports in the block.
+    pub count: u16,
+}
+
+impl PortBlock {
+    /// Ports in the block, in order.
+    pub fn ports(&self) -> impl Iterator<Item = u16> + use<> {
+        let end = u32::from(self.start) + u32::from(self.count);
+        (u32::from(self.start)..end).map(|port| port as u16)
+    }
+
+    fn overlaps(&self, other: &PortBlock) -> bool {
+        let end = u32::from(self.start) + u32::from(self.count);
+        let other_end = u32::from(other.start) + u32::from(other.count);
+        u32::from(self.start) < other_end && u32::from(other.start) < end
+    }
+}
+
+/// Port blocks keyed by room name.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PortAllocations {
+    #[serde(default)]
+    rooms: BTreeMap<String, PortBlock>,
+}
+
+impl PortAllocations {
+    /// Load allocations from a rooms directory.
+    ///
+    /// Returns no allocations if the file doesn't exist.
+    pub fn load_from_rooms_dir<P: AsRef<Path>>(rooms_dir: P) -> Result<Self, PortsError> {
+        let path = rooms_dir.as_ref().join(PORTS_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Save allocations to a rooms directory atomically.
+    ///
+    /// Use [`PortAllocations::update_in_rooms_dir`] to change allocations
+    /// other processes may be changing too.
+    pub fn save_to_rooms_dir<P: AsRef<Path>>(&self, rooms_dir: P) -> Result<(), PortsError> {
+        fs::create_dir_all(&rooms_dir)?;
+        let path = rooms_dir.as_ref().join(PORTS_FILE);
+        let temp_path = rooms_dir.as_ref().join(format!(
+            "{PORTS_FILE}.{}.{}.tmp",
+            process::id(),
+            TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&temp_path, serde_json::to_string_pretty(self)?)?;
+        if let Err(e) = fs::rename(&temp_path, path) {
+            let _ = fs::remove_file(&temp_path);
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    /// Load the allocations, apply `update`, and save them back.
+    ///
+    /// Holds a lock file for the whole read-modify-write, so rooms created
+    /// at the same time, even by another `rooms` process, get different blocks.
+    pub fn update_in_rooms_dir<P: AsRef<Path>, R>(
+        rooms_dir: P,
+        update: impl FnOnce(&mut Self) -> Result<R, PortsError>,
+    ) -> Result<R, PortsError> {
+        fs::create_dir_all(&rooms_dir)?;
+        let lock_path = rooms_dir.as_ref().join(LOCK_FILE);
+        let _lock = FileLock::acquire(lock_path.clone()).map_err(|e| match e.kind() {
+            io::ErrorKind::TimedOut => PortsError::Locked(lock_path),
+            _ => e.into(),
+        })?;
+        let mut allocations = Self::load_from_rooms_dir(&rooms_dir)?;
+        let result = update(&mut allocations)?;
+        allocations.save_to_rooms_dir(&rooms_dir)?;
+        Ok(result)
+    }
+
+    /// Reserve a block for each of `room_names` that has none.
+    ///
+    /// Returns the blocks of every room, including ones reserved earlier.
+    pub fn reserve_in_rooms_dir<P: AsRef<Path>>(
+        rooms_dir: P,
+        room_names: &[String],
+        config: &Ports,
+    ) -> Result<BTreeMap<String, PortBlock>, PortsError> {
+        Self::update_in_rooms_dir(rooms_dir, |allocations| {
+            for room_name in room_names {
+                allocations.allocate(room_name, config)?;
+            }
+            Ok(allocations.rooms.clone())
+        })
+    }
+
+    /// The room's block, if it has one.
+    pub fn get(&self, room_name: &str) -> Option<PortBlock> {
+        self.rooms.get(room_name).copied()
+    }
+
+    /// The room's block, reserving the first free one if it has none.
+    ///
+    /// A block is free when no other room holds any of its ports and none of
+    /// them is in use on localhost.
+    pub fn allocate(&mut self, room_name: &str, config: &Ports) -> Result<PortBlock, PortsError> {
+        if let Some(block) = self.get(room_name) {
+            return Ok(block);
+        }
+
+        let count = config.per_room;
+        let mut start = u32::from(config.start);
+        while start + u32::from(count) <= u32::from(u16::MAX) + 1 {
+            let block = PortBlock {
+                start: start as u16,
+                count,
+            };
+            start += u32::from(count);
+            if self.rooms.values().any(|other| other.overlaps(&block)) {
+                continue;
+            }
+            if !block.ports().all(port_is_free) {
+                continue;
+            }
+            self.rooms.insert(room_name.to_string(), block);
+            return Ok(block);
+        }
+        Err(PortsError::Exhausted(count))
+    }
+
+    /// Release the room's block.
+    pub fn release(&mut self, room_name: &str) -> Option<PortBlock> {
+        self.rooms.remove(room_name)
+    }
+
+    /// Move a block to the room's new name.
+    pub fn rename(&mut self, old_name: &str, new_name: &str) {
+        if let Some(block) = self.rooms.remove(old_name) {
+            self.rooms.insert(new_name.to_string(), block);
+        }
+    }
+}
+
+fn port_is_free(port: u16) -> bool {
+    TcpListener::bind(("127.0.0.1", port)).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn ports(start: u16, per_room: u16) -> Ports {
+        Ports { start, per_room }
+    }
+
+    #[test]
+    fn test_allocate_assigns_consecutive_blocks() {
+        let mut allocations = PortAllocations::default();
+        let config = ports(41000, 5);
+
+        let a = allocations.allocate("a", &config).unwrap();
+        let b = allocations.allocate("b", &config).unwrap();
+        assert_eq!(a.count, 5);
+        assert!(b.start >= a.start + 5);
+        assert_eq!(allocations.allocate("a", &config).unwrap(), a);
+
+        allocations.release("a");
+        let c = allocations.allocate("c", &config).unwrap();
+        assert_eq!(c, a);
+    }
+
+    #[test]
+    fn test_allocate_skips_ports_in_use() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let busy = listener.local_addr().unwrap().port();
+        let mut allocations = PortAllocations::default();
+
+        let block = allocations.allocate("a", &ports(busy, 1)).unwrap();
+        assert_ne!(block.start, busy);
+    }
+
+    #[test]
+    fn test_allocate_exhausted() {
+        let mut allocations = PortAllocations::default();
+        let config = ports(u16::MAX - 1, 4);
+        assert!(matches!(
+            allocations.allocate("a", &config),
+            Err(PortsError::Exhausted(4))
+        ));
+    }
+
+    #[test]
+    fn test_update_persists_renames() {
+        let dir = tempfile::tempdir().unwrap();
+        let block = PortAllocations::update_in_rooms_dir(dir.path(), |allocations| {
+            allocations.allocate("old", &ports(42000, 3))
+        })
+        .unwrap();
+        PortAllocations::update_in_rooms_dir(dir.path(), |allocations| {
+            allocations.rename("old", "new");
+            Ok(())
+        })
+        .unwrap();
+
+        let loaded = PortAllocations::load_from_rooms_dir(dir.path()).unwrap();
+        assert_eq!(loaded.get("old"), None);
+        assert_eq!(loaded.get("new"), Some(block));
+        assert_eq!(block.ports().collect::<Vec<_>>().len(), 3);
+    }
+
+    #[test]
+    fn test_concurrent_updates_get_different_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let handles: Vec<_> = (0..8)
+            .map(|index| {
+                let rooms_dir = dir.path().to_path_buf();
+                thread::spawn(move || {
+                    PortAllocations::update_in_rooms_dir(&rooms_dir, |allocations| {
+                        allocations.allocate(&format!("room-{index}"), &ports(43000, 2))
+                    })
+                    .unwrap()
+                })
+            })
+            .collect();
+        let mut starts: Vec<u16> = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap().start)
+            .collect();
+        starts.sort();
+        starts.dedup();
+        assert_eq!(starts.len(), 8);
+
+        let reserved = PortAllocations::reserve_in_rooms_dir(
+            dir.path(),
+            &["room-0".to_string(), "extra".to_string()],
+            &ports(43000, 2),
+        )
+        .unwrap();
+        assert_eq!(reserved.len(), 9);
+        assert!(!dir.path().join(LOCK_FILE).exists());
+        // No temp files left behind
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
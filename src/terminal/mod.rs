@@ -1,4 +1,9 @@
 pub mod debug_log;
 mod session;
+mod tabs;
 
-pub use session::PtySession;
+pub use session::{PtySession, user_shell};
+pub use tabs::{DEFAULT_TAB_NAME, TabKind, TabSet};
+
+/// Terminal tabs for one room.
+pub type RoomTabs = TabSet<PtySession>;
@@ -1,9 +1,10 @@
+use std::collections::BTreeMap;
 use std::io::{Read, Write};
 use std::path::Path;
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::thread;
 
-use portable_pty::{CommandBuilder, PtyPair, PtySize, native_pty_system};
+use portable_pty::{Child, CommandBuilder, PtyPair, PtySize, native_pty_system};
 use thiserror::Error;
 
 use super::debug_log;
@@ -20,10 +21,18 @@ pub enum SessionError {
     Write(String),
 }
 
+/// The user's login shell, falling back to `/bin/sh`.
+pub fn user_shell() -> String {
+    std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string())
+}
+
 /// A PTY session for a room.
 pub struct PtySession {
     pair: PtyPair,
     writer: Box<dyn Write + Send>,
+    child: Box<dyn Child + Send + Sync>,
+    /// Exit code of the shell, once it has exited.
+    exit_code: Option<u32>,
     output_rx: Receiver<Vec<u8>>,
     /// The vt100 parser maintains complete terminal state
     pub parser: vt100::Parser,
@@ -32,7 +41,43 @@ pub struct PtySession {
 
 impl PtySession {
     /// Create a new PTY session with the given size and working directory.
-    pub fn new<P: AsRef<Path>>(cols: u16, rows: u16, cwd: P) -> Result<Self, SessionError> {
+    ///
+    /// `env` is added to the shell's environment.
+    pub fn new<P: AsRef<Path>>(
+        cols: u16,
+        rows: u16,
+        cwd: P,
+        env: &BTreeMap<String, String>,
+    ) -> Result<Self, SessionError> {
+        let mut cmd = CommandBuilder::new(user_shell());
+        cmd.cwd(cwd.as_ref());
+        for (key, value) in env {
+            cmd.env(key, value);
+        }
+        Self::spawn(cols, rows, cmd)
+    }
+
+    /// Create a PTY session running `command` through the user's shell.
+    ///
+    /// The session ends when the command exits.
+    pub fn with_command<P: AsRef<Path>>(
+        cols: u16,
+        rows: u16,
+        cwd: P,
+        command: &str,
+        env: &BTreeMap<String, String>,
+    ) -> Result<Self, SessionError> {
+        let mut cmd = CommandBuilder::new(user_shell());
+        cmd.arg("-c");
+        cmd.arg(command);
+        cmd.cwd(cwd.as_ref());
+        for (key, value) in env {
+            cmd.env(key, value);
+        }
+        Self::spawn(cols, rows, cmd)
+    }
+
+    fn spawn(cols: u16, rows: u16, cmd: CommandBuilder) -> Result<Self, SessionError> {
         let pty_system = native_pty_system();
 
         let pair = pty_system
@@ -44,11 +89,7 @@ impl PtySession {
             })
             .map_err(|e| SessionError::PtyOpen(e.to_string()))?;
 
-        let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
-        let mut cmd = CommandBuilder::new(&shell);
-        cmd.cwd(cwd.as_ref());
-
-        let _child = pair
+        let child = pair
             .slave
             .spawn_command(cmd)
             .map_err(|e| SessionError::SpawnShell(e.to_string()))?;
@@ -87,6 +128,8 @@ impl PtySession {
         Ok(Self {
             pair,
             writer,
+            child,
+            exit_code: None,
             output_rx: rx,
             parser: vt100::Parser::new(rows, cols, 1000), // rows, cols, scrollback
             _reader_thread: reader_thread,
@@ -101,6 +144,50 @@ impl PtySession {
         }
     }
 
+    /// Check whether the shell process has exited.
+    ///
+    /// Returns the exit code the first time the exit is observed and `None`
+    /// otherwise; use `exit_code()` for the recorded value.
+    pub fn poll_exit(&mut self) -> Option<u32> {
+        if self.exit_code.is_some() {
+            return None;
+        }
+        match self.child.try_wait() {
+            Ok(Some(status)) => {
+                let code = status.exit_code();
+                debug_log::log_debug(&format!("SESSION_EXIT: code={}", code));
+                self.exit_code = Some(code);
+                Some(code)
+            }
+            _ => None,
+        }
+    }
+
+    /// Exit code of the shell, if it has exited.
+    pub fn exit_code(&self) -> Option<u32> {
+        self.exit_code
+    }
+
+    /// Whether the shell process has exited.
+    pub fn has_exited(&self) -> bool {
+        self.exit_code.is_some()
+    }
+
+    /// Terminate the process if it is still running, and reap it.
+    ///
+    /// The process gets a short grace period after SIGHUP before it is
+    /// killed, so this returns quickly.
+    pub fn kill(&mut self) {
+        if self.exit_code.is_some() {
+            return;
+        }
+        if self.child.kill().is_ok()
+            && let Ok(status) = self.child.wait()
+        {
+            self.exit_code = Some(status.exit_code());
+        }
+    }
+
     /// Get the screen from the parser.
     pub fn screen(&self) -> &vt100::Screen {
         self.parser.screen()
@@ -138,3 +225,63 @@ impl PtySession {
         self.parser.screen_mut().set_size(rows, cols);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_poll_exit_reports_exit_code_once() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut session = PtySession::new(80, 24, temp.path(), &BTreeMap::new()).unwrap();
+        assert!(!session.has_exited());
+
+        session.write(b"exit 3\n").unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let mut code = None;
+        while code.is_none() && Instant::now() < deadline {
+            session.process_output();
+            code = session.poll_exit();
+            thread::sleep(Duration::from_millis(20));
+        }
+
+        assert_eq!(code, Some(3));
+        assert_eq!(session.exit_code(), Some(3));
+        assert!(session.has_exited());
+        assert_eq!(session.poll_exit(), None);
+    }
+
+    #[test]
+    fn test_kill_reaps_the_process() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut session =
+            PtySession::with_command(80, 24, temp.path(), "sleep 30", &BTreeMap::new()).unwrap();
+        assert!(!session.has_exited());
+
+        session.kill();
+        assert!(session.has_exited());
+        assert_eq!(session.poll_exit(), None);
+        // Killing it again does nothing
+        session.kill();
+    }
+
+    #[test]
+    fn test_with_command_passes_env() {
+        let temp = tempfile::tempdir().unwrap();
+        let env = BTreeMap::from([("ROOMS_TEST_CODE".to_string(), "7".to_string())]);
+        let mut session =
+            PtySession::with_command(80, 24, temp.path(), "exit $ROOMS_TEST_CODE", &env).unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let mut code = None;
+        while code.is_none() && Instant::now() < deadline {
+            session.process_output();
+            code = session.poll_exit();
+            thread::sleep(Duration::from_millis(20));
+        }
+
+        assert_eq!(code, Some(7));
+    }
+}
@@ -0,0 +1,225 @@
+//! Named terminal tabs for a room.
+
+/// Default name for the first tab in a room.
+pub const DEFAULT_TAB_NAME: &str = "shell";
+
+/// What runs in a tab.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TabKind {
+    /// An interactive shell.
+    #[default]
+    Shell,
+    /// A configured service; the tab name is the service name.
+    Service,
+}
+
+/// A named terminal within a room.
+pub struct Tab<S> {
+    /// Tab label shown in the main scene title.
+    pub name: String,
+    /// What runs in the tab.
+    pub kind: TabKind,
+    /// The terminal session.
+    pub session: S,
+}
+
+/// Ordered set of tabs with one active tab.
+///
+/// A `TabSet` is never empty: closing the last tab consumes the set.
+pub struct TabSet<S> {
+    tabs: Vec<Tab<S>>,
+    active: usize,
+}
+
+impl<S> TabSet<S> {
+    /// Create a tab set with a single tab.
+    pub fn new(name: impl Into<String>, session: S) -> Self {
+        Self {
+            tabs: vec![Tab {
+                name: name.into(),
+                kind: TabKind::Shell,
+                session,
+            }],
+            active: 0,
+        }
+    }
+
+    /// Add a tab after the existing ones and make it active.
+    pub fn push(&mut self, name: impl Into<String>, session: S) {
+        self.tabs.push(Tab {
+            name: name.into(),
+            kind: TabKind::Shell,
+            session,
+        });
+        self.active = self.tabs.len() - 1;
+    }
+
+    /// Add a service tab after the existing ones without changing the active tab.
+    pub fn push_service(&mut self, name: impl Into<String>, session: S) {
+        self.tabs.push(Tab {
+            name: name.into(),
+            kind: TabKind::Service,
+            session,
+        });
+    }
+
+    /// The active tab.
+    pub fn active(&self) -> &Tab<S> {
+        &self.tabs[self.active]
+    }
+
+    /// The active tab, mutably.
+    pub fn active_mut(&mut self) -> &mut Tab<S> {
+        &mut self.tabs[self.active]
+    }
+
+    /// Index of the active tab.
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    /// Make the tab at `index` active. Returns false if out of range.
+    pub fn select(&mut self, index: usize) -> bool {
+        if index >= self.tabs.len() {
+            return false;
+        }
+        self.active = index;
+        true
+    }
+
+    /// Activate the next tab, wrapping around.
+    pub fn select_next(&mut self) {
+        self.active = (self.active + 1) % self.tabs.len();
+    }
+
+    /// Activate the previous tab, wrapping around.
+    pub fn select_previous(&mut self) {
+        self.active = (self.active + self.tabs.len() - 1) % self.tabs.len();
+    }
+
+    /// Close the active tab.
+    ///
+    /// Returns the remaining tabs, or `None` if that was the last tab.
+    pub fn close_active(mut self) -> Option<Self> {
+        if self.tabs.len() == 1 {
+            return None;
+        }
+        self.tabs.remove(self.active);
+        self.active = self.active.min(self.tabs.len() - 1);
+        Some(self)
+    }
+
+    /// The tab at `index`, if any.
+    pub fn get(&self, index: usize) -> Option<&Tab<S>> {
+        self.tabs.get(index)
+    }
+
+    /// The tab at `index`, mutably.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut Tab<S>> {
+        self.tabs.get_mut(index)
+    }
+
+    /// Index of the tab with this name.
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.tabs.iter().position(|tab| tab.name == name)
+    }
+
+    /// Number of tabs.
+    pub fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    /// Iterate over tabs in order.
+    pub fn iter(&self) -> impl Iterator<Item = &Tab<S>> {
+        self.tabs.iter()
+    }
+
+    /// Iterate mutably over tabs in order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Tab<S>> {
+        self.tabs.iter_mut()
+    }
+
+    /// Whether a tab with this name exists.
+    pub fn contains(&self, name: &str) -> bool {
+        self.position(name).is_some()
+    }
+
+    /// A tab name based on `base` that isn't used yet (`base`, `base-2`, ...).
+    pub fn unique_name(&self, base: &str) -> String {
+        if !self.contains(base) {
+            return base.to_string();
+        }
+        (2..)
+            .map(|n| format!("{base}-{n}"))
+            .find(|name| !self.contains(name))
+            .expect("unbounded range always yields a free name")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names<S>(tabs: &TabSet<S>) -> Vec<&str> {
+        tabs.iter().map(|tab| tab.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_push_activates_new_tab() {
+        let mut tabs = TabSet::new("shell", 1);
+        tabs.push("server", 2);
+        assert_eq!(names(&tabs), vec!["shell", "server"]);
+        assert_eq!(tabs.active_index(), 1);
+        assert_eq!(tabs.active().session, 2);
+    }
+
+    #[test]
+    fn test_push_service_keeps_active_tab() {
+        let mut tabs = TabSet::new("shell", ());
+        tabs.push_service("web", ());
+        assert_eq!(tabs.active().name, "shell");
+        assert_eq!(tabs.position("web"), Some(1));
+        assert_eq!(tabs.get(1).unwrap().kind, TabKind::Service);
+        assert_eq!(tabs.active().kind, TabKind::Shell);
+    }
+
+    #[test]
+    fn test_select_wraps_around() {
+        let mut tabs = TabSet::new("a", ());
+        tabs.push("b", ());
+        tabs.push("c", ());
+
+        tabs.select_next();
+        assert_eq!(tabs.active().name, "a");
+        tabs.select_previous();
+        assert_eq!(tabs.active().name, "c");
+        assert!(tabs.select(1));
+        assert_eq!(tabs.active().name, "b");
+        assert!(!tabs.select(3));
+        assert_eq!(tabs.active().name, "b");
+    }
+
+    #[test]
+    fn test_close_active() {
+        let mut tabs = TabSet::new("a", ());
+        tabs.push("b", ());
+        tabs.push("c", ());
+
+        let tabs = tabs.close_active().unwrap();
+        assert_eq!(names(&tabs), vec!["a", "b"]);
+        assert_eq!(tabs.active().name, "b");
+
+        let tabs = tabs.close_active().unwrap();
+        assert_eq!(names(&tabs), vec!["a"]);
+        assert!(tabs.close_active().is_none());
+    }
+
+    #[test]
+    fn test_unique_name() {
+        let mut tabs = TabSet::new("shell", ());
+        assert_eq!(tabs.unique_name("server"), "server");
+        assert_eq!(tabs.unique_name("shell"), "shell-2");
+        tabs.push("shell-2", ());
+        assert_eq!(tabs.unique_name("shell"), "shell-3");
+    }
+}
@@ -1,9 +1,9 @@
 // Allow dead code for fields that will be used in later implementation steps
 #![allow(dead_code)]
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc;
 use std::thread;
 use std::time::{Duration, Instant};
@@ -22,28 +22,43 @@ use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Style};
 use ratatui::widgets::{Block, Borders, Paragraph};
 
-use crate::config::Config;
-use crate::git::prune_worktrees_from;
+use crate::config::{Config, HookMode, Hooks};
+use crate::git::command::CommandError;
+use crate::git::{
+    BisectMark, BisectStatus, Worktree, list_stash_branches_from, list_worktrees_from,
+};
+use crate::hooks::{self, HookContext, HookError};
 use crate::room::{
-    CreateRoomError, CreateRoomOptions, CreatedRoom, DirtyStatus, RoomInfo, RoomStatus,
-    create_room, discover_rooms, generate_unique_room_name, remove_room, rename_room,
-    sanitize_room_name, validate_room_name,
+    AfterCreate, CreateRoomError, CreateRoomOptions, CreatedRoom, DeleteCheck, Job, JobId,
+    JobOutcome, JobQueue, RemoveRoomError, RemovedRoom, RenameRoomError, RenamedRoom,
+    RoomGitStatus, RoomInfo, RoomSnapshot, RoomStatus, WorktreeWatcher, generate_unique_room_name,
+    room_name_from_branch, rooms_from_worktrees, sanitize_room_name, short_sha, validate_room_name,
+};
+use crate::state::{
+    EventLog, PortAllocations, PortBlock, Room, RoomsState, SessionLayout, SortOrder,
+    TransientStateStore, TrashedRoom,
 };
-use crate::state::{EventLog, TransientStateStore};
-use crate::terminal::PtySession;
+use crate::terminal::{DEFAULT_TAB_NAME, PtySession, RoomTabs, TabKind, TabSet};
 
 use super::clipboard::{copy_to_clipboard, paste_from_clipboard};
-use super::confirm::{ConfirmState, render_confirm};
+use super::confirm::{ConfirmState, Confirmed, render_confirm};
 use super::context_menu::{ContextMenuItem, ContextMenuState};
 use super::help::render_help;
 use super::main_scene::render_main_scene;
-use super::prompt::{PromptState, render_prompt};
+use super::panes::{Pane, PaneLayout, SplitDirection};
+use super::prompt::{NewRoom, PromptState, TextInput, render_prompt};
 use super::selection::{Selection, SelectionBounds};
-use super::sidebar::render_sidebar;
+use super::sidebar::{bisect_label, render_sidebar};
 
 /// Maximum scrollback lines for the PTY terminal.
 const SCROLLBACK_LINES: usize = 1000;
 
+/// How often room git statuses are re-checked in the background.
+const GIT_STATUS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How often the worktree watcher looks for changes made outside rooms.
+const WORKTREE_WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Which panel currently has focus.
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum Focus {
@@ -73,11 +88,39 @@ pub(crate) enum PendingRoomStatus {
     Failed(String),
 }
 
+/// Status of a room's service, shown as a sidebar badge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ServiceStatus {
+    Running,
+    /// Exited with code 0.
+    Exited,
+    /// Exited with a non-zero code.
+    Failed(u32),
+}
+
+/// Which services `start_services` should (re)start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ServiceStart {
+    /// Services with `autostart`, skipping running ones.
+    Autostart,
+    /// All services, skipping running ones.
+    All,
+    /// All services, restarting running ones.
+    Restart,
+}
+
 #[derive(Debug, Clone)]
 struct PendingRoom {
     name: String,
-    branch: String,
+    /// Branch name (None for a detached room).
+    branch: Option<String>,
     path: PathBuf,
+    /// Base branch picked for the room, kept for retries.
+    base_branch: Option<String>,
+    /// Commit-ish of a detached room, kept for retries.
+    commit: Option<String>,
+    /// Git work to do once the room exists, kept for retries.
+    after_create: Option<AfterCreate>,
     status: PendingRoomStatus,
 }
 
@@ -89,7 +132,8 @@ impl PendingRoom {
         };
         RoomInfo {
             name: self.name.clone(),
-            branch: Some(self.branch.clone()),
+            branch: self.branch.clone(),
+            head: None,
             path: self.path.clone(),
             status,
             is_prunable: false,
@@ -99,13 +143,13 @@ impl PendingRoom {
     }
 }
 
-struct CreateHandle {
-    receiver: mpsc::Receiver<CreateRoomResult>,
+struct SetupHandle {
+    receiver: mpsc::Receiver<SetupResult>,
 }
 
-struct CreateRoomResult {
+struct SetupResult {
     room_name: String,
-    result: Result<CreatedRoom, CreateRoomError>,
+    result: Result<(), HookError>,
 }
 
 /// Application state for the TUI.
@@ -122,9 +166,16 @@ pub struct App {
     /// Discovered rooms from git worktrees.
     pub rooms: Vec<RoomInfo>,
 
+    /// Worktrees from the last `git worktree list`; rooms are rebuilt from
+    /// these when only in-memory state changed.
+    worktrees: Vec<Worktree>,
+
     /// Transient state store for in-memory room states.
     pub transient: TransientStateStore,
 
+    /// Persistent room metadata (`state.json` in the rooms directory).
+    pub state: RoomsState,
+
     /// Primary worktree path.
     pub primary_worktree: PathBuf,
 
@@ -155,8 +206,8 @@ pub struct App {
     /// Current confirmation dialog state.
     pub confirm: ConfirmState,
 
-    /// PTY sessions per room (keyed by room name).
-    pub sessions: HashMap<String, PtySession>,
+    /// Terminal tabs per room (keyed by room name).
+    pub sessions: HashMap<String, RoomTabs>,
 
     /// Scrollback offset for the current session (0 = at bottom, >0 = scrolled up).
     pub scrollback_offset: usize,
@@ -185,11 +236,50 @@ pub struct App {
     /// Context menu state for PTY selection.
     context_menu: Option<ContextMenuState>,
 
+    /// Split panes in the main scene.
+    pub panes: PaneLayout,
+
     /// Rooms being created in the background.
     pending_rooms: HashMap<String, PendingRoom>,
 
-    /// Handles for in-progress room creation tasks.
-    create_handles: Vec<CreateHandle>,
+    /// Background queue for git operations on rooms.
+    jobs: JobQueue,
+
+    /// Rooms with a queued or running job, and the job's id.
+    room_jobs: HashMap<String, JobId>,
+
+    /// Refresh requested with `R`, reported in the status bar when done.
+    announced_refresh: Option<JobId>,
+
+    /// Handles for `post_create` hooks running as subprocesses.
+    setup_handles: Vec<SetupHandle>,
+
+    /// Captured output of failed `post_create` hooks, keyed by room name.
+    setup_failures: HashMap<String, String>,
+
+    /// Output of failed deletions (git's stderr or hook output), keyed by room name.
+    delete_failures: HashMap<String, String>,
+
+    /// Progress of rooms with a `git bisect` in progress, keyed by room name.
+    bisects: HashMap<String, BisectStatus>,
+
+    /// Port blocks reserved in `ports.json`, keyed by room name.
+    port_blocks: HashMap<String, PortBlock>,
+
+    /// Rooms with a port reservation queued, so it's only queued once.
+    ports_requested: HashSet<String>,
+
+    /// Git status of each room from the last background check, keyed by room name.
+    git_statuses: HashMap<String, RoomGitStatus>,
+
+    /// Receiver for the background git status check in progress, if any.
+    git_status_handle: Option<mpsc::Receiver<HashMap<String, RoomGitStatus>>>,
+
+    /// When the last git status check started; `None` checks on the next tick.
+    git_status_checked_at: Option<Instant>,
+
+    /// Watcher for worktrees changed outside rooms, started with the TUI.
+    worktree_watcher: Option<WorktreeWatcher>,
 
     /// Animation phase for creating-room indicator.
     creation_blink_phase: u8,
@@ -210,24 +300,72 @@ impl App {
         let event_log = EventLog::new(&rooms_dir);
         let transient = TransientStateStore::new();
 
+        let mut state = match RoomsState::load_from_rooms_dir(&rooms_dir) {
+            Ok(state) => state,
+            Err(e) => {
+                event_log.log_error(None, &format!("Failed to load room state: {}", e));
+                RoomsState::default()
+            }
+        };
+        let mut status_message = None;
+
         // Discover rooms from git worktrees
-        let rooms =
-            match discover_rooms(&repo_root, &rooms_dir, Some(&primary_worktree), &transient) {
-                Ok(rooms) => rooms,
+        let (worktrees, rooms) = match list_worktrees_from(&repo_root) {
+            Ok(worktrees) => {
+                let rooms = rooms_from_worktrees(
+                    &worktrees,
+                    &rooms_dir,
+                    Some(&primary_worktree),
+                    &transient,
+                );
+                let reconciled = update_saved_state(&mut state, &rooms_dir, &event_log, |state| {
+                    state.reconcile(&rooms)
+                });
+                if reconciled.orphaned > 0 {
+                    let message = format!(
+                        "{} tracked room(s) orphaned: worktree missing",
+                        reconciled.orphaned
+                    );
+                    event_log.log_error(None, &message);
+                    status_message = Some(message);
+                }
+                (worktrees, rooms)
+            }
+            Err(e) => {
+                // Log the error for debugging - the app will start with empty rooms
+                event_log.log_error(None, &format!("Failed to discover rooms at startup: {}", e));
+                (Vec::new(), Vec::new())
+            }
+        };
+        // Shells restored right after startup need their ports
+        let room_names: Vec<String> = rooms
+            .iter()
+            .filter(|room| !room.is_prunable)
+            .map(|room| room.name.clone())
+            .collect();
+        let port_blocks =
+            match PortAllocations::reserve_in_rooms_dir(&rooms_dir, &room_names, &config.ports) {
+                Ok(blocks) => blocks.into_iter().collect(),
                 Err(e) => {
-                    // Log the error for debugging - the app will start with empty rooms
-                    event_log
-                        .log_error(None, &format!("Failed to discover rooms at startup: {}", e));
-                    Vec::new()
+                    event_log.log_error(None, &format!("failed to reserve ports: {e}"));
+                    HashMap::new()
                 }
             };
+        let hooks = if skip_hooks {
+            Hooks::default()
+        } else {
+            config.hooks.clone()
+        };
+        let jobs = JobQueue::spawn(repo_root.clone(), rooms_dir.clone(), hooks);
 
         let mut app = Self {
             repo_root,
             rooms_dir,
             config,
             rooms,
+            worktrees,
             transient,
+            state,
             primary_worktree,
             selected_index: 0,
             focus: Focus::default(),
@@ -235,7 +373,7 @@ impl App {
             main_scene_visible: true,
             show_help: false,
             should_quit: false,
-            status_message: None,
+            status_message,
             prompt: PromptState::default(),
             confirm: ConfirmState::default(),
             sessions: HashMap::new(),
@@ -248,8 +386,21 @@ impl App {
             selection_dragging: false,
             selection_anchor: None,
             context_menu: None,
+            panes: PaneLayout::default(),
             pending_rooms: HashMap::new(),
-            create_handles: Vec::new(),
+            jobs,
+            room_jobs: HashMap::new(),
+            announced_refresh: None,
+            setup_handles: Vec::new(),
+            setup_failures: HashMap::new(),
+            delete_failures: HashMap::new(),
+            bisects: HashMap::new(),
+            port_blocks,
+            ports_requested: HashSet::new(),
+            git_statuses: HashMap::new(),
+            git_status_handle: None,
+            git_status_checked_at: None,
+            worktree_watcher: None,
             creation_blink_phase: 0,
             creation_blink_tick: Instant::now(),
         };
@@ -260,43 +411,99 @@ impl App {
 
     /// Refresh the rooms list from git worktrees.
     ///
-    /// This re-discovers rooms by calling `git worktree list` and merging
-    /// with transient state. The current selection is preserved if the
-    /// selected room still exists.
-    ///
-    /// Returns `true` if the refresh succeeded, `false` if it failed.
-    pub fn refresh_rooms(&mut self) -> bool {
-        let selected_name = self.rooms.get(self.selected_index).map(|r| r.name.clone());
+    /// `git worktree list` runs on the job queue; the rooms are rebuilt when
+    /// it finishes, keeping the selection. A refresh that is already queued
+    /// is reused. Returns the id of the refresh job.
+    pub fn refresh_rooms(&mut self) -> JobId {
+        self.jobs.push(Job::Refresh)
+    }
 
-        match discover_rooms(
-            &self.repo_root,
+    /// Rebuild the rooms list from the last listed worktrees.
+    ///
+    /// Used when only in-memory state changed, such as pending rooms or
+    /// transient statuses, so no git command is needed.
+    fn rebuild_rooms(&mut self) {
+        let rooms = rooms_from_worktrees(
+            &self.worktrees,
             &self.rooms_dir,
             Some(&self.primary_worktree),
             &self.transient,
-        ) {
-            Ok(rooms) => {
-                self.rooms = merge_pending_rooms(rooms, &self.pending_rooms);
-                self.sort_rooms_for_sidebar();
+        );
+        self.apply_rooms(rooms);
+    }
 
-                // Restore selection if the room still exists
-                if let Some(name) = selected_name
-                    && let Some(idx) = self.rooms.iter().position(|r| r.name == name)
-                {
-                    self.selected_index = idx;
-                }
+    /// Keep freshly listed worktrees and rebuild the rooms from them.
+    fn apply_worktrees(&mut self, worktrees: Vec<Worktree>) {
+        self.worktrees = worktrees;
+        self.rebuild_rooms();
+    }
 
-                // Ensure selected_index is valid for the current rooms list
-                if self.rooms.is_empty() {
-                    self.selected_index = 0;
-                } else if self.selected_index >= self.rooms.len() {
-                    self.selected_index = self.rooms.len() - 1;
-                }
-                true
-            }
-            Err(e) => {
-                self.status_message = Some(format!("Failed to refresh rooms: {}", e));
-                false
-            }
+    /// Replace the room list with freshly discovered rooms, keeping the selection.
+    fn apply_rooms(&mut self, rooms: Vec<RoomInfo>) {
+        let selected_name = self.rooms.get(self.selected_index).map(|r| r.name.clone());
+        let selected_trash = self
+            .selected_index
+            .checked_sub(self.rooms.len())
+            .filter(|index| *index < self.state.trash.len());
+
+        self.update_state(|state| state.reconcile(&rooms));
+        self.rooms = merge_pending_rooms(rooms, &self.pending_rooms);
+        self.sort_rooms_for_sidebar();
+        self.reserve_missing_ports();
+        self.refresh_bisects();
+        // Rooms may have changed; check their git status again soon
+        self.git_status_checked_at = None;
+
+        // Restore selection if the room still exists
+        if let Some(name) = selected_name
+            && let Some(idx) = self.rooms.iter().position(|r| r.name == name)
+        {
+            self.selected_index = idx;
+        } else if let Some(index) = selected_trash {
+            self.selected_index = self.rooms.len() + index;
+        }
+
+        self.clamp_selection();
+    }
+
+    /// Queue port reservations for rooms that showed up without a block,
+    /// such as worktrees added with plain git.
+    fn reserve_missing_ports(&mut self) {
+        let room_names: Vec<String> = self
+            .rooms
+            .iter()
+            .filter(|room| !room.is_prunable && !self.pending_rooms.contains_key(&room.name))
+            .filter(|room| {
+                !self.port_blocks.contains_key(&room.name)
+                    && !self.ports_requested.contains(&room.name)
+            })
+            .map(|room| room.name.clone())
+            .collect();
+        if room_names.is_empty() {
+            return;
+        }
+        self.ports_requested.extend(room_names.iter().cloned());
+        self.jobs.push(Job::ReservePorts {
+            room_names,
+            config: self.config.ports.clone(),
+        });
+    }
+
+    /// Apply worktree changes made outside rooms, such as `git worktree add`
+    /// in another terminal.
+    fn poll_worktree_watcher(&mut self) {
+        let Some(result) = self
+            .worktree_watcher
+            .as_ref()
+            .and_then(WorktreeWatcher::poll)
+        else {
+            return;
+        };
+        match result {
+            Ok(worktrees) => self.apply_worktrees(worktrees),
+            Err(e) => self
+                .event_log
+                .log_error(None, &format!("Failed to refresh rooms: {e}")),
         }
     }
 
@@ -306,6 +513,7 @@ impl App {
             self.sessions.keys().cloned().collect();
         let primary_canonical = self.primary_worktree.canonicalize().ok();
         let primary_normalized = normalize_path_for_compare(&self.primary_worktree);
+        let state = &self.state;
 
         self.rooms.sort_by(|a, b| {
             let a_primary =
@@ -315,11 +523,13 @@ impl App {
             let a_key = (
                 room_section_rank_with_active(a, &active_rooms),
                 if a_primary { 0 } else { 1 },
+                std::cmp::Reverse(room_sort_time(state, a)),
                 a.name.to_lowercase(),
             );
             let b_key = (
                 room_section_rank_with_active(b, &active_rooms),
                 if b_primary { 0 } else { 1 },
+                std::cmp::Reverse(room_sort_time(state, b)),
                 b.name.to_lowercase(),
             );
             a_key.cmp(&b_key)
@@ -331,12 +541,21 @@ impl App {
             self.selected_index = idx;
         }
 
-        // Ensure selected_index is valid for the current rooms list
-        if self.rooms.is_empty() {
-            self.selected_index = 0;
-        } else if self.selected_index >= self.rooms.len() {
-            self.selected_index = self.rooms.len() - 1;
-        }
+        self.clamp_selection();
+    }
+
+    /// Get persisted metadata for a room, if it is tracked.
+    pub fn room_metadata(&self, room: &RoomInfo) -> Option<&Room> {
+        self.state.find_by_name(&room.name)
+    }
+
+    /// Persist room metadata, logging any failure.
+    /// Apply `update` to the latest saved state and save it.
+    ///
+    /// Other `rooms` processes may have changed `state.json` since it was
+    /// loaded; see [`update_saved_state`].
+    fn update_state<T>(&mut self, update: impl FnOnce(&mut RoomsState) -> T) -> T {
+        update_saved_state(&mut self.state, &self.rooms_dir, &self.event_log, update)
     }
 
     pub fn room_section(&self, room: &RoomInfo) -> RoomSection {
@@ -375,8 +594,20 @@ impl App {
         // Ensure cursor is shown initially
         terminal.show_cursor()?;
 
+        // Restore sessions from the previous run once the PTY size is known
+        let size = terminal.size()?;
+        self.last_size = (size.width, size.height);
+        self.restore_layout();
+
+        self.worktree_watcher = Some(WorktreeWatcher::spawn(
+            self.repo_root.clone(),
+            self.rooms_dir.clone(),
+            WORKTREE_WATCH_INTERVAL,
+        ));
+
         // Main loop
         let result = self.main_loop(&mut terminal);
+        self.save_layout();
 
         // Restore terminal
         disable_raw_mode()?;
@@ -388,6 +619,15 @@ impl App {
         )?;
         terminal.show_cursor()?;
 
+        // Leave open rooms before their shells go away with the app
+        let room_names: Vec<String> = self.sessions.keys().cloned().collect();
+        for room_name in room_names {
+            if let Some((commands, context)) = self.on_leave_hooks(&room_name) {
+                eprintln!("Running on_leave hooks for {room_name}...");
+                run_on_leave(&commands, &context, &self.event_log);
+            }
+        }
+
         result
     }
 
@@ -397,11 +637,15 @@ impl App {
     ) -> io::Result<()> {
         loop {
             // Process PTY output for all sessions
-            for session in self.sessions.values_mut() {
-                session.process_output();
+            for tab in self.sessions.values_mut().flat_map(|tabs| tabs.iter_mut()) {
+                tab.session.process_output();
             }
+            self.poll_shell_exits();
 
-            self.poll_create_rooms();
+            self.poll_jobs();
+            self.poll_room_setups();
+            self.poll_git_statuses();
+            self.poll_worktree_watcher();
             self.update_creation_blink();
 
             // Update terminal size and resize PTY sessions if needed
@@ -409,23 +653,31 @@ impl App {
             let size = terminal.size()?;
             self.last_size = (size.width, size.height);
             let (cols, rows) = self.calculate_pty_size();
-            for session in self.sessions.values_mut() {
-                // resize() already checks if dimensions changed and skips if same
-                session.resize(cols, rows);
+            self.sync_panes();
+            let pinned_sizes = self.pinned_pane_sizes();
+            let current = self.current_pane();
+            for (room_name, tabs) in self.sessions.iter_mut() {
+                for (index, tab) in tabs.iter_mut().enumerate() {
+                    let is_current = current
+                        .as_ref()
+                        .is_some_and(|pane| &pane.room_name == room_name && pane.tab == index);
+                    let (cols, rows) = pinned_sizes
+                        .iter()
+                        .find(|(pane, _)| &pane.room_name == room_name && pane.tab == index)
+                        .filter(|_| !is_current)
+                        .map_or((cols, rows), |(_, size)| *size);
+                    // resize() already checks if dimensions changed and skips if same
+                    tab.session.resize(cols, rows);
+                }
             }
 
             // Apply scrollback offset to the current session (only if changed)
-            if let Some(room_info) = self.selected_room_info() {
-                let offset = self.scrollback_offset;
-                // Only update if offset changed
-                if offset != self.prev_scrollback_offset {
-                    // Clone is necessary to avoid holding a borrow while mutably accessing sessions
-                    let room_name = room_info.name.clone();
-                    if let Some(session) = self.sessions.get_mut(&room_name) {
-                        session.screen_mut().set_scrollback(offset);
-                    }
-                    self.prev_scrollback_offset = offset;
+            let offset = self.scrollback_offset;
+            if offset != self.prev_scrollback_offset {
+                if let Some(session) = self.current_session_mut() {
+                    session.screen_mut().set_scrollback(offset);
                 }
+                self.prev_scrollback_offset = offset;
             }
 
             // Draw UI
@@ -533,13 +785,13 @@ impl App {
         {
             let screen = session.screen();
 
-            // Calculate which area is the main scene
-            let main_area = Self::get_main_scene_area(
+            // Calculate which area is the current pane of the main scene
+            let main_area = self.current_pane_area(Self::get_main_scene_area(
                 area,
                 &chunks,
                 self.sidebar_visible,
                 self.main_scene_visible,
-            );
+            ));
 
             // Calculate inner area (subtract borders)
             let inner = Rect {
@@ -579,6 +831,43 @@ impl App {
         }
     }
 
+    /// The main scene area for the last known terminal size.
+    fn main_scene_area(&self) -> Rect {
+        let area = Rect {
+            x: 0,
+            y: 0,
+            width: self.last_size.0,
+            height: self.last_size.1,
+        };
+        let chunks = self.calculate_layout(area);
+        Self::get_main_scene_area(area, &chunks, self.sidebar_visible, self.main_scene_visible)
+    }
+
+    /// The area of the focused pane within the main scene area.
+    fn current_pane_area(&self, main_area: Rect) -> Rect {
+        self.panes
+            .rects(main_area)
+            .get(self.panes.focused_index())
+            .copied()
+            .unwrap_or(main_area)
+    }
+
+    /// PTY sizes for panes that don't have focus.
+    fn pinned_pane_sizes(&self) -> Vec<(Pane, (u16, u16))> {
+        let rects = self.panes.rects(self.main_scene_area());
+        self.panes
+            .iter()
+            .zip(rects)
+            .enumerate()
+            .filter(|(index, _)| *index != self.panes.focused_index())
+            .map(|(_, (pane, rect))| {
+                let cols = rect.width.saturating_sub(2).max(10);
+                let rows = rect.height.saturating_sub(2).max(5);
+                (pane.clone(), (cols, rows))
+            })
+            .collect()
+    }
+
     /// Calculate the PTY size based on current terminal size and sidebar visibility.
     /// This must match the actual rendered area inside the terminal block (with borders).
     /// We replicate exactly what render does: calculate_layout() then block.inner().
@@ -592,9 +881,13 @@ impl App {
         };
         let chunks = self.calculate_layout(area);
 
-        // Get the main scene area using the shared helper method
-        let main_area =
-            Self::get_main_scene_area(area, &chunks, self.sidebar_visible, self.main_scene_visible);
+        // Get the current pane's area using the shared helper methods
+        let main_area = self.current_pane_area(Self::get_main_scene_area(
+            area,
+            &chunks,
+            self.sidebar_visible,
+            self.main_scene_visible,
+        ));
 
         // Calculate inner area after block borders (Borders::ALL subtracts 2 from each dimension)
         let inner_width = main_area.width.saturating_sub(2);
@@ -640,6 +933,21 @@ impl App {
             return;
         }
 
+        // Alt+arrows move focus between split panes
+        if key.modifiers.contains(KeyModifiers::ALT) && self.panes.is_split() {
+            match key.code {
+                KeyCode::Left | KeyCode::Up => {
+                    self.focus_pane(false);
+                    return;
+                }
+                KeyCode::Right | KeyCode::Down => {
+                    self.focus_pane(true);
+                    return;
+                }
+                _ => {}
+            }
+        }
+
         // When focused on MainScene (PTY), forward most keys to the terminal
         // Ctrl+B focuses sidebar (and shows it if hidden), Ctrl+T toggles terminal
         if self.focus == Focus::MainScene {
@@ -704,20 +1012,80 @@ impl App {
                     return;
                 }
 
-                if let Some((room_name, branch_name)) = self.prompt.advance() {
+                if let PromptState::RenameTab { room_name, input } = &self.prompt {
+                    let room_name = room_name.clone();
+                    let new_name = input.value.clone();
+                    self.prompt = PromptState::None;
+                    self.apply_tab_rename(&room_name, &new_name);
+                    return;
+                }
+
+                if let PromptState::EditNotes { room_name, input } = &self.prompt {
+                    let room_name = room_name.clone();
+                    let notes = input.get_value();
+                    self.prompt = PromptState::None;
+                    self.apply_room_notes(&room_name, notes);
+                    return;
+                }
+
+                if let PromptState::BisectBad { input } = &self.prompt {
+                    let bad = input.get_value().unwrap_or_else(|| "HEAD".to_string());
+                    self.prompt = PromptState::BisectGood {
+                        bad,
+                        input: TextInput::new("e.g. v1.2.0"),
+                    };
+                    return;
+                }
+
+                if let PromptState::BisectGood { bad, input } = &self.prompt {
+                    let bad = bad.clone();
+                    let good = input.get_value();
+                    self.prompt = PromptState::None;
+                    if let Some(good) = good {
+                        self.create_room_bisect(bad, good);
+                    }
+                    return;
+                }
+
+                if let PromptState::DetachedCommit { input } = &self.prompt {
+                    let commit = input.get_value();
+                    self.prompt = PromptState::None;
+                    if let Some(commit) = commit {
+                        self.create_room_detached(commit);
+                    }
+                    return;
+                }
+
+                if let Some(new_room) = self.prompt.advance() {
                     // Prompt complete, create the room
-                    self.create_room_interactive(room_name, branch_name);
+                    self.create_room_interactive(new_room);
+                }
+            }
+            KeyCode::Up => {
+                if let Some(picker) = self.prompt.picker() {
+                    picker.select_previous();
+                }
+            }
+            KeyCode::Down => {
+                if let Some(picker) = self.prompt.picker() {
+                    picker.select_next();
                 }
             }
             KeyCode::Backspace => {
                 if let Some(input) = self.prompt.current_input() {
                     input.backspace();
                 }
+                if let Some(picker) = self.prompt.picker() {
+                    picker.filter_changed();
+                }
             }
             KeyCode::Delete => {
                 if let Some(input) = self.prompt.current_input() {
                     input.delete();
                 }
+                if let Some(picker) = self.prompt.picker() {
+                    picker.filter_changed();
+                }
             }
             KeyCode::Left => {
                 if let Some(input) = self.prompt.current_input() {
@@ -743,6 +1111,9 @@ impl App {
                 if let Some(input) = self.prompt.current_input() {
                     input.insert(c);
                 }
+                if let Some(picker) = self.prompt.picker() {
+                    picker.filter_changed();
+                }
             }
             _ => {}
         }
@@ -775,19 +1146,39 @@ impl App {
                     self.retry_pending_room(&name);
                     return;
                 }
+                if room.status == RoomStatus::SettingUp {
+                    self.status_message = Some(
+                        "Room is still setting up. You'll be connected when ready.".to_string(),
+                    );
+                    return;
+                }
+                if room.status == RoomStatus::Deleting {
+                    self.status_message = Some("Room is being deleted".to_string());
+                    return;
+                }
+                if self.setup_failures.contains_key(&room.name) {
+                    // The worktree is fine; let the user fix the setup by hand.
+                    let name = room.name.clone();
+                    self.setup_failures.remove(&name);
+                    self.transient.remove(&name);
+                    self.rebuild_rooms();
+                    self.enter_selected_room(false);
+                    return;
+                }
+                if self.delete_failures.contains_key(&room.name) && room.path.exists() {
+                    // The deletion failed before the worktree went away
+                    let name = room.name.clone();
+                    self.delete_failures.remove(&name);
+                    self.transient.remove(&name);
+                    self.rebuild_rooms();
+                    self.enter_selected_room(false);
+                    return;
+                }
 
                 if self.room_section(room) == RoomSection::Failed {
                     if room.is_prunable {
-                        match prune_worktrees_from(&self.repo_root) {
-                            Ok(_) => {
-                                self.refresh_rooms();
-                                self.status_message = Some("Ran git worktree prune".to_string());
-                            }
-                            Err(e) => {
-                                self.status_message =
-                                    Some(format!("Failed to prune worktrees: {e}"));
-                            }
-                        }
+                        self.jobs.push(Job::Prune);
+                        self.status_message = Some("Pruning worktrees...".to_string());
                     } else {
                         self.status_message = Some("Cannot open failed worktree".to_string());
                     }
@@ -798,11 +1189,32 @@ impl App {
                 self.enter_selected_room(false);
             }
             KeyCode::Char('a') => {
-                self.prompt = PromptState::start_room_creation();
+                // Branches are offered once they're listed
+                self.jobs.push(Job::ListBranches);
+                self.prompt =
+                    PromptState::start_room_creation(Vec::new(), self.config.base_branch.clone());
             }
             KeyCode::Char('A') => {
                 self.create_room_silent();
             }
+            KeyCode::Char('C') => {
+                self.prompt = PromptState::start_detached_creation();
+            }
+            KeyCode::Char('B') => {
+                self.prompt = PromptState::start_bisect();
+            }
+            KeyCode::Char('g') => {
+                self.mark_bisect(BisectMark::Good);
+            }
+            KeyCode::Char('b') => {
+                self.mark_bisect(BisectMark::Bad);
+            }
+            KeyCode::Char('S') => {
+                self.mark_bisect(BisectMark::Skip);
+            }
+            KeyCode::Char('G') => {
+                self.reset_bisect();
+            }
             KeyCode::Char('d') | KeyCode::Delete | KeyCode::Backspace => {
                 let Some(room) = self.selected_room_info() else {
                     return;
@@ -812,7 +1224,7 @@ impl App {
                     self.pending_room_status(room),
                     Some(PendingRoomStatus::Failed(_))
                 );
-                if self.check_room_creating_and_notify(&room_name) {
+                if self.check_room_busy_and_notify(&room_name) {
                     return;
                 }
                 if is_failed {
@@ -821,9 +1233,13 @@ impl App {
                     );
                     return;
                 }
-                self.start_room_deletion();
+                self.start_room_deletion(false);
             }
             KeyCode::Char('D') => {
+                if self.selected_trashed_room().is_some() {
+                    self.discard_trashed_room();
+                    return;
+                }
                 let Some(room) = self.selected_room_info() else {
                     return;
                 };
@@ -832,29 +1248,90 @@ impl App {
                     self.pending_room_status(room),
                     Some(PendingRoomStatus::Failed(_))
                 );
-                if self.check_room_creating_and_notify(&room_name) {
+                if self.check_room_busy_and_notify(&room_name) {
                     return;
                 }
                 if is_failed {
                     self.remove_pending_room(&room_name);
                     return;
                 }
-                self.delete_room_immediate();
+                self.start_room_deletion(true);
             }
             KeyCode::Char('r') => {
                 let Some(room) = self.selected_room_info() else {
                     return;
                 };
                 let room_name = room.name.clone();
-                if self.check_room_creating_and_notify(&room_name) {
+                if self.check_room_busy_and_notify(&room_name) {
                     return;
                 }
                 self.start_room_rename();
             }
             KeyCode::Char('R') => {
-                if self.refresh_rooms() {
-                    self.status_message = Some("Rooms refreshed".to_string());
+                self.announced_refresh = Some(self.refresh_rooms());
+                self.status_message = Some("Refreshing rooms...".to_string());
+            }
+            KeyCode::Char('c') => {
+                self.cancel_selected_job();
+            }
+            KeyCode::Char('u') => {
+                self.restore_trashed_room();
+            }
+            KeyCode::Char('o') => {
+                self.update_state(|state| state.sort_order = state.sort_order.next());
+                self.sort_rooms_for_sidebar();
+                self.status_message = Some(format!("Sorted by {}", self.state.sort_order.label()));
+            }
+            KeyCode::Char('n') => {
+                let Some(room) = self.selected_room_info() else {
+                    return;
+                };
+                if self.pending_room_status(room).is_some() {
+                    self.status_message =
+                        Some("Notes are available once the room is created".to_string());
+                    return;
+                }
+                let room_name = room.name.clone();
+                let notes = self
+                    .room_metadata(room)
+                    .and_then(|metadata| metadata.notes.clone());
+                self.prompt = PromptState::start_notes_edit(room_name, notes);
+            }
+            KeyCode::Char('t') => {
+                let Some(room) = self.selected_room_info() else {
+                    return;
+                };
+                if self.pending_room_status(room).is_some()
+                    || self.room_section(room) == RoomSection::Failed
+                {
+                    self.status_message = Some("Cannot open a terminal in this room".to_string());
+                    return;
                 }
+                self.open_tab();
+            }
+            KeyCode::Char('T') => {
+                self.start_tab_rename();
+            }
+            KeyCode::Char('x') => {
+                self.close_tab();
+            }
+            KeyCode::Char(']') => {
+                self.switch_tab(|tabs| tabs.select_next());
+            }
+            KeyCode::Char('[') => {
+                self.switch_tab(|tabs| tabs.select_previous());
+            }
+            KeyCode::Char('|') => {
+                self.split_pane(SplitDirection::Vertical);
+            }
+            KeyCode::Char('-') => {
+                self.split_pane(SplitDirection::Horizontal);
+            }
+            KeyCode::Char('X') => {
+                self.close_pane();
+            }
+            KeyCode::Char('s') => {
+                self.restart_selected_services();
             }
             _ => {}
         }
@@ -865,6 +1342,17 @@ impl App {
             return;
         }
 
+        // Alt+1..9 switches tabs
+        if key.modifiers.contains(KeyModifiers::ALT)
+            && let KeyCode::Char(c @ '1'..='9') = key.code
+        {
+            let index = c as usize - '1' as usize;
+            self.switch_tab(|tabs| {
+                tabs.select(index);
+            });
+            return;
+        }
+
         // Handle scrollback navigation keys (don't forward to PTY)
         match key.code {
             KeyCode::PageUp => {
@@ -889,6 +1377,17 @@ impl App {
             _ => {}
         }
 
+        // A finished shell can't take input; Enter starts a new one
+        if self
+            .current_session()
+            .is_some_and(|session| session.has_exited())
+        {
+            if key.code == KeyCode::Enter {
+                self.enter_selected_room(false);
+            }
+            return;
+        }
+
         // Convert key event to bytes and send to PTY
         let bytes = match key.code {
             KeyCode::Char(c) => {
@@ -967,6 +1466,19 @@ impl App {
                 }
             }
             MouseEventKind::Down(crossterm::event::MouseButton::Left) => {
+                // Clicking another pane focuses it
+                if let Some(index) = self.pane_at(mouse.column, mouse.row)
+                    && index != self.panes.focused_index()
+                {
+                    self.sync_panes();
+                    self.reset_scrollback();
+                    self.clear_selection();
+                    if let Some(pane) = self.panes.focus(index).cloned() {
+                        self.select_pane(&pane);
+                    }
+                    self.focus = Focus::MainScene;
+                    return;
+                }
                 let position = self.mouse_to_screen_position(mouse);
                 if let Some((row, col)) = position {
                     if !self.selection_contains(row, col) {
@@ -1031,9 +1543,27 @@ impl App {
         }
     }
 
-    /// Get total number of selectable items (rooms).
+    /// Get total number of selectable items (rooms, then recently deleted rooms).
     pub fn total_items(&self) -> usize {
-        self.rooms.len()
+        self.rooms.len() + self.state.trash.len()
+    }
+
+    /// Keep the selection within the selectable items.
+    fn clamp_selection(&mut self) {
+        self.selected_index = self
+            .selected_index
+            .min(self.total_items().saturating_sub(1));
+    }
+
+    /// Get the selected recently deleted room, if one is selected.
+    pub fn selected_trashed_room(&self) -> Option<&TrashedRoom> {
+        let index = self.selected_index.checked_sub(self.rooms.len())?;
+        self.state.trash.get(index)
+    }
+
+    /// Recently deleted rooms that can be restored, newest first.
+    pub fn trashed_rooms(&self) -> &[TrashedRoom] {
+        &self.state.trash
     }
 
     /// Get the currently selected room (RoomInfo), if any.
@@ -1047,9 +1577,9 @@ impl App {
             .map(|pending| &pending.status)
     }
 
-    /// Checks if a room is currently being created.
-    /// Returns true and sets a status message if the room is creating.
-    fn check_room_creating_and_notify(&mut self, room_name: &str) -> bool {
+    /// Checks if a room is being created, set up, deleted or has another job running.
+    /// Returns true and sets a status message if the room is busy.
+    fn check_room_busy_and_notify(&mut self, room_name: &str) -> bool {
         if let Some(pending) = self.pending_rooms.get(room_name)
             && matches!(pending.status, PendingRoomStatus::Creating)
         {
@@ -1057,6 +1587,20 @@ impl App {
                 Some("Room is still creating. Please wait for it to finish.".to_string());
             return true;
         }
+        if self.transient.get_status(room_name) == Some(&RoomStatus::SettingUp) {
+            self.status_message =
+                Some("Room is still setting up. Please wait for it to finish.".to_string());
+            return true;
+        }
+        if self.transient.get_status(room_name) == Some(&RoomStatus::Deleting) {
+            self.status_message =
+                Some("Room is being deleted. Press c to cancel if it hasn't started.".to_string());
+            return true;
+        }
+        if self.room_jobs.contains_key(room_name) {
+            self.status_message = Some("Room is busy. Please wait for it to finish.".to_string());
+            return true;
+        }
         false
     }
 
@@ -1066,6 +1610,10 @@ impl App {
             name: None,
             branch: None,
             base_branch: self.config.base_branch.clone(),
+            commit: None,
+            ports: self.config.ports.clone(),
+            files: self.config.files.clone(),
+            dependency_dirs: self.config.dependency_dirs.clone(),
         };
 
         match self.prepare_room_create(options) {
@@ -1078,14 +1626,50 @@ impl App {
         };
     }
 
-    /// Create a new room with user-provided name and branch.
-    fn create_room_interactive(&mut self, room_name: Option<String>, branch_name: Option<String>) {
+    /// Create a new room with the name, branch and base picked in the prompt.
+    ///
+    /// A typed branch name is checked with git on the job queue first.
+    fn create_room_interactive(&mut self, new_room: NewRoom) {
         let options = CreateRoomOptions {
-            name: room_name,
-            branch: branch_name,
-            base_branch: self.config.base_branch.clone(),
+            name: new_room.name,
+            branch: new_room.branch,
+            base_branch: new_room.base_branch,
+            commit: None,
+            ports: self.config.ports.clone(),
+            files: self.config.files.clone(),
+            dependency_dirs: self.config.dependency_dirs.clone(),
         };
 
+        if options.branch.is_some() {
+            self.jobs.push(Job::CheckBranchName { options });
+            return;
+        }
+        self.create_room_checked(options);
+    }
+
+    /// Continue creating a room once git has checked its branch name.
+    fn finish_branch_name_check(
+        &mut self,
+        mut options: CreateRoomOptions,
+        result: Result<Option<String>, CommandError>,
+    ) {
+        let candidate = options.branch.take().unwrap_or_default();
+        match result {
+            Ok(Some(branch)) => {
+                options.branch = Some(branch);
+                self.create_room_checked(options);
+            }
+            Ok(None) => {
+                self.status_message = Some(format!("Invalid branch name: '{candidate}'"));
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to check branch name: {e}"));
+            }
+        }
+    }
+
+    /// Create a room whose branch name, if any, git has already checked.
+    fn create_room_checked(&mut self, options: CreateRoomOptions) {
         match self.prepare_room_create(options) {
             Ok((options, creating_room)) => {
                 self.start_room_creation(options, creating_room);
@@ -1096,23 +1680,75 @@ impl App {
         };
     }
 
-    fn enter_selected_room(&mut self, run_post_create: bool) {
-        if !self.main_scene_visible {
-            self.main_scene_visible = true;
-        }
-
-        let (cols, rows) = self.calculate_pty_size();
-        let created_session = match self.get_or_create_session(cols, rows) {
-            Some(created) => created,
-            None => return,
+    /// Create a detached room at `bad` and start bisecting it against `good`.
+    fn create_room_bisect(&mut self, bad: String, good: String) {
+        let name = room_name_from_branch(&format!("bisect-{bad}"), |name| {
+            self.rooms.iter().any(|room| room.name == name) || self.pending_rooms.contains_key(name)
+        });
+        let options = CreateRoomOptions {
+            name: Some(name),
+            branch: None,
+            base_branch: None,
+            commit: Some(bad),
+            ports: self.config.ports.clone(),
+            files: self.config.files.clone(),
+            dependency_dirs: self.config.dependency_dirs.clone(),
         };
 
-        self.focus = Focus::MainScene;
-
-        let post_create = self.config.hooks.post_create.clone();
-        let post_enter = self.config.hooks.post_enter.clone();
-
-        if run_post_create {
+        match self.prepare_room_create(options) {
+            Ok((options, mut creating_room)) => {
+                creating_room.after_create = Some(AfterCreate::Bisect { good });
+                self.start_room_creation(options, creating_room);
+            }
+            Err(message) => {
+                self.status_message = Some(message);
+            }
+        };
+    }
+
+    /// Create a room with a detached HEAD at `commit`, named after it.
+    fn create_room_detached(&mut self, commit: String) {
+        let options = CreateRoomOptions {
+            name: None,
+            branch: None,
+            base_branch: None,
+            commit: Some(commit),
+            ports: self.config.ports.clone(),
+            files: self.config.files.clone(),
+            dependency_dirs: self.config.dependency_dirs.clone(),
+        };
+
+        match self.prepare_room_create(options) {
+            Ok((options, creating_room)) => {
+                self.start_room_creation(options, creating_room);
+            }
+            Err(message) => {
+                self.status_message = Some(message);
+            }
+        };
+    }
+
+    fn enter_selected_room(&mut self, run_post_create: bool) {
+        if !self.main_scene_visible {
+            self.main_scene_visible = true;
+        }
+
+        let (cols, rows) = self.calculate_pty_size();
+        let created_session = match self.get_or_create_session(cols, rows) {
+            Some(created) => created,
+            None => return,
+        };
+
+        self.focus = Focus::MainScene;
+
+        if let Some(room_name) = self.selected_room_info().map(|room| room.name.clone()) {
+            self.update_state(|state| state.touch(&room_name));
+        }
+
+        let post_create = self.config.hooks.post_create.clone();
+        let post_enter = self.config.hooks.post_enter.clone();
+
+        if run_post_create {
             self.run_hook_commands(&post_create);
         }
         if created_session {
@@ -1136,6 +1772,10 @@ impl App {
             .collect::<std::collections::HashSet<_>>();
         let exists = |name: &str| existing_names.contains(name) || creating_names.contains(name);
 
+        // Branches follow git's own rules, checked by `Job::CheckBranchName`;
+        // only room names are sanitized
+        let requested_branch = options.branch.take();
+
         let name = match options.name.take() {
             Some(candidate) => {
                 let sanitized = sanitize_room_name(&candidate);
@@ -1146,27 +1786,29 @@ impl App {
                 }
                 sanitized
             }
-            None => generate_unique_room_name(|name| exists(name)),
+            None => match options.commit.as_ref().or(requested_branch.as_ref()) {
+                Some(branch) => room_name_from_branch(branch, |name| exists(name)),
+                None => generate_unique_room_name(|name| exists(name)),
+            },
         };
 
-        let branch = match options.branch.take() {
-            Some(candidate) => {
-                let sanitized = sanitize_room_name(&candidate);
-                validate_room_name(&sanitized)
-                    .map_err(|err| format!("Invalid branch name: {err}"))?;
-                sanitized
-            }
-            None => name.clone(),
+        // Detached rooms have no branch
+        let branch = match options.commit {
+            Some(_) => None,
+            None => Some(requested_branch.unwrap_or_else(|| name.clone())),
         };
 
         let creating_room = PendingRoom {
             name: name.clone(),
             branch: branch.clone(),
             path: self.rooms_dir.join(&name),
+            base_branch: options.base_branch.clone(),
+            commit: options.commit.clone(),
+            after_create: None,
             status: PendingRoomStatus::Creating,
         };
         options.name = Some(name);
-        options.branch = Some(branch);
+        options.branch = branch;
 
         Ok((options, creating_room))
     }
@@ -1178,74 +1820,374 @@ impl App {
             return;
         }
 
+        let after_create = creating_room.after_create.clone();
         self.pending_rooms
             .insert(creating_room.name.clone(), creating_room);
-        self.refresh_rooms();
+        self.transient.set_status(&room_name, RoomStatus::Creating);
+        self.rebuild_rooms();
         if let Some(idx) = self.rooms.iter().position(|room| room.name == room_name) {
             self.selected_index = idx;
         }
 
         self.status_message = Some(format!("Creating room: {room_name}"));
 
+        let id = self.jobs.push(Job::Create {
+            options,
+            after_create,
+        });
+        self.room_jobs.insert(room_name, id);
+    }
+
+    /// Apply the results of finished background jobs.
+    ///
+    /// Worktrees listed by a job are applied before its outcome, so rooms
+    /// it created or removed are already up to date.
+    fn poll_jobs(&mut self) {
+        for result in self.jobs.poll() {
+            if let Some(room_name) = &result.room_name
+                && self.room_jobs.get(room_name) == Some(&result.id)
+            {
+                self.room_jobs.remove(room_name);
+            }
+            match result.worktrees {
+                Some(Ok(worktrees)) => self.apply_worktrees(worktrees),
+                Some(Err(e)) => {
+                    let message = format!("Failed to refresh rooms: {e}");
+                    self.event_log.log_error(None, &message);
+                    if self.announced_refresh == Some(result.id) {
+                        self.announced_refresh = None;
+                        self.status_message = Some(message);
+                    }
+                }
+                None => {}
+            }
+
+            let room_name = result.room_name.unwrap_or_default();
+            match result.outcome {
+                JobOutcome::Created {
+                    result,
+                    after_create_error,
+                } => self.finish_room_creation(&room_name, result, after_create_error),
+                JobOutcome::Removed { result, snapshot } => {
+                    self.finish_room_deletion(&room_name, result, snapshot)
+                }
+                JobOutcome::Renamed(result) => self.finish_room_rename(&room_name, result),
+                JobOutcome::DeleteChecked { result, immediate } => {
+                    self.confirm_room_deletion(&room_name, result, immediate)
+                }
+                JobOutcome::SnapshotsDiscarded(Err(e)) => self.event_log.log_error(
+                    None,
+                    &format!("failed to discard deleted room changes: {e}"),
+                ),
+                JobOutcome::SnapshotsDiscarded(Ok(())) => {}
+                JobOutcome::PortsReserved(Ok(blocks)) => {
+                    self.ports_requested.clear();
+                    self.port_blocks = blocks.into_iter().collect();
+                }
+                // The rooms stay requested, so this isn't retried on every refresh
+                JobOutcome::PortsReserved(Err(e)) => self
+                    .event_log
+                    .log_error(None, &format!("failed to reserve ports: {e}")),
+                JobOutcome::BranchNameChecked { options, result } => {
+                    self.finish_branch_name_check(options, result)
+                }
+                JobOutcome::BranchesListed(Ok(branches)) => self.prompt.set_branches(branches),
+                JobOutcome::BranchesListed(Err(e)) => self
+                    .event_log
+                    .log_error(None, &format!("failed to list branches: {e}")),
+                JobOutcome::BisectsChecked(statuses) => self.apply_bisects(statuses),
+                JobOutcome::BisectMarked { mark, result } => {
+                    self.finish_bisect_mark(&room_name, mark, result)
+                }
+                JobOutcome::BisectReset(result) => self.finish_bisect_reset(&room_name, result),
+                JobOutcome::Pruned(result) => {
+                    self.status_message = Some(match result {
+                        Ok(()) => "Ran git worktree prune".to_string(),
+                        Err(e) => format!("Failed to prune worktrees: {e}"),
+                    });
+                }
+                JobOutcome::Refreshed => {
+                    if self.announced_refresh == Some(result.id) {
+                        self.announced_refresh = None;
+                        self.status_message = Some("Rooms refreshed".to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Cancel the selected room's job if it hasn't started yet.
+    fn cancel_selected_job(&mut self) {
+        let Some(room_name) = self.selected_room_info().map(|room| room.name.clone()) else {
+            return;
+        };
+        let Some(&id) = self.room_jobs.get(&room_name) else {
+            self.status_message = Some(format!("Nothing queued for {room_name}"));
+            return;
+        };
+        let Some(job) = self.jobs.cancel(id) else {
+            self.status_message = Some(format!(
+                "Can't cancel: {room_name} is already being worked on"
+            ));
+            return;
+        };
+
+        self.room_jobs.remove(&room_name);
+        let action = match job {
+            Job::Create { .. } => {
+                self.pending_rooms.remove(&room_name);
+                self.transient.remove(&room_name);
+                "creating"
+            }
+            Job::Remove { .. } => {
+                self.transient.remove(&room_name);
+                "deleting"
+            }
+            Job::Rename { .. } => "renaming",
+            Job::CheckDelete { .. } => "checking",
+            Job::DiscardSnapshots { .. } => "discarding",
+            Job::BisectMark { .. } => "marking",
+            Job::BisectReset { .. } => "ending the bisect in",
+            Job::ReservePorts { .. }
+            | Job::CheckBranchName { .. }
+            | Job::ListBranches
+            | Job::CheckBisects { .. }
+            | Job::Prune
+            | Job::Refresh => "refreshing",
+        };
+        self.rebuild_rooms();
+        self.status_message = Some(format!("Cancelled {action} room: {room_name}"));
+    }
+
+    fn finish_room_creation(
+        &mut self,
+        room_name: &str,
+        result: Result<CreatedRoom, CreateRoomError>,
+        after_create_error: Option<String>,
+    ) {
+        self.transient.remove(room_name);
+        match result {
+            Ok(created) => {
+                let restored = matches!(
+                    self.pending_rooms
+                        .remove(room_name)
+                        .and_then(|pending| pending.after_create),
+                    Some(AfterCreate::Restore { .. })
+                );
+                self.event_log.log_room_created(&created.name);
+                self.port_blocks.insert(created.name.clone(), created.ports);
+                self.update_state(|state| {
+                    state.record_created(
+                        &created.name,
+                        created.branch.as_deref(),
+                        &created.path,
+                        created.base_branch.clone(),
+                    );
+                    if restored
+                        && let Some(trashed) = state.take_trashed(&created.name)
+                        && let Some(room) = state.find_by_name_mut(&created.name)
+                    {
+                        room.base_branch = trashed.base_branch;
+                        room.notes = trashed.notes;
+                    }
+                });
+                if let Some(err) = &created.files_error {
+                    self.event_log
+                        .log_error(Some(&created.name), &err.to_string());
+                }
+                if let Some(message) = &after_create_error {
+                    self.event_log.log_error(Some(&created.name), message);
+                }
+                let setting_up = self.start_room_setup(&created);
+                self.rebuild_rooms();
+                if let Some(idx) = self.rooms.iter().position(|room| room.name == created.name) {
+                    self.selected_index = idx;
+                    if !setting_up {
+                        self.enter_selected_room(true);
+                    }
+                } else {
+                    self.event_log.log_error(
+                        Some(&created.name),
+                        "Room created but not found in worktree list after refresh",
+                    );
+                }
+                self.status_message = Some(if let Some(err) = &created.files_error {
+                    format!("Created room {} but {err}", created.name)
+                } else if let Some(err) = after_create_error {
+                    format!("Created room {} but {err}", created.name)
+                } else if setting_up {
+                    format!("Setting up room: {}", created.name)
+                } else if restored {
+                    format!("Restored room: {}", created.name)
+                } else if let Some(upstream) = &created.upstream {
+                    format!("Created room: {} (tracking {upstream})", created.name)
+                } else {
+                    format!("Created room: {}", created.name)
+                });
+            }
+            Err(err) => {
+                let error_message = err.to_string();
+                let full_message = format!("Failed to create room: {error_message}");
+                self.status_message = Some(full_message.clone());
+                self.event_log.log_error(Some(room_name), &full_message);
+                if let Some(pending_room) = self.pending_rooms.get_mut(room_name) {
+                    pending_room.status = PendingRoomStatus::Failed(error_message);
+                }
+                self.rebuild_rooms();
+            }
+        }
+    }
+
+    /// Hooks to run around room operations, honouring `--no-hooks`.
+    fn lifecycle_hooks(&self) -> Hooks {
+        if self.skip_hooks {
+            Hooks::default()
+        } else {
+            self.config.hooks.clone()
+        }
+    }
+
+    /// Describe a room to hook commands and shells.
+    fn hook_context(&self, room_name: &str) -> Option<HookContext> {
+        let room = self.rooms.iter().find(|room| room.name == room_name)?;
+        Some(HookContext {
+            room: room.name.clone(),
+            path: room.path.clone(),
+            branch: room.branch.clone(),
+            primary: self.primary_worktree.clone(),
+            base_branch: self.base_branch(room_name),
+            ports: self.room_ports(room_name),
+        })
+    }
+
+    /// The branch the room was created from, as recorded in state.
+    fn base_branch(&self, room_name: &str) -> Option<String> {
+        self.state
+            .find_by_name(room_name)
+            .and_then(|metadata| metadata.base_branch.clone())
+    }
+
+    /// The room's port block.
+    ///
+    /// Blocks are reserved when a room is created or first listed, so this
+    /// never touches `ports.json`; a room whose reservation is still queued
+    /// has none yet.
+    fn room_ports(&self, room_name: &str) -> Option<PortBlock> {
+        self.port_blocks.get(room_name).copied()
+    }
+
+    /// `ROOMS_*` environment variables for a room's terminals.
+    fn room_env(&self, room_name: &str) -> BTreeMap<String, String> {
+        self.hook_context(room_name)
+            .map(|context| context.env())
+            .unwrap_or_default()
+    }
+
+    /// `on_leave` commands for a room and the context to run them in, if any are configured.
+    fn on_leave_hooks(&self, room_name: &str) -> Option<(Vec<String>, HookContext)> {
+        let commands = self.lifecycle_hooks().on_leave;
+        if commands.is_empty() {
+            return None;
+        }
+        Some((commands, self.hook_context(room_name)?))
+    }
+
+    /// Context for the `on_leave` hooks a removal or rename runs first, if the
+    /// room has open terminals.
+    fn leave_context(&self, room_name: &str) -> Option<HookContext> {
+        if !self.sessions.contains_key(room_name) {
+            return None;
+        }
+        self.hook_context(room_name)
+    }
+
+    /// Start `post_create` hooks in the background when `hooks.mode` is `process`.
+    ///
+    /// Returns false if the hooks should be typed into the room's shell instead.
+    fn start_room_setup(&mut self, created: &CreatedRoom) -> bool {
+        let commands = self.config.hooks.post_create.clone();
+        if self.config.hooks.mode != HookMode::Process || self.skip_hooks || commands.is_empty() {
+            return false;
+        }
+
+        self.transient
+            .set_status(&created.name, RoomStatus::SettingUp);
+        self.setup_failures.remove(&created.name);
+        self.event_log
+            .log_post_create_started(&created.name, commands.len());
+
         let (tx, rx) = mpsc::channel();
-        let repo_root = self.repo_root.clone();
-        let rooms_dir = self.rooms_dir.clone();
-        let options = options.clone();
-        let handle_room_name = room_name.clone();
+        let context = HookContext {
+            room: created.name.clone(),
+            path: created.path.clone(),
+            branch: created.branch.clone(),
+            primary: self.primary_worktree.clone(),
+            base_branch: created.base_branch.clone(),
+            ports: self.room_ports(&created.name),
+        };
         thread::spawn(move || {
-            let result = create_room(&repo_root, &rooms_dir, options);
-            let _ = tx.send(CreateRoomResult {
-                room_name: handle_room_name,
+            let result = hooks::run_commands(&commands, &context.path, &context);
+            let _ = tx.send(SetupResult {
+                room_name: context.room,
                 result,
             });
         });
 
-        self.create_handles.push(CreateHandle { receiver: rx });
+        self.setup_handles.push(SetupHandle { receiver: rx });
+        true
     }
 
-    fn poll_create_rooms(&mut self) {
+    fn poll_room_setups(&mut self) {
         let mut completed = Vec::new();
-        for (index, handle) in self.create_handles.iter().enumerate() {
+        for (index, handle) in self.setup_handles.iter().enumerate() {
             if let Ok(result) = handle.receiver.try_recv() {
                 completed.push((index, result));
             }
         }
 
         for (index, result) in completed.into_iter().rev() {
-            self.create_handles.remove(index);
+            self.setup_handles.remove(index);
+            let room_name = result.room_name;
 
             match result.result {
-                Ok(created) => {
-                    self.pending_rooms.remove(&result.room_name);
-                    self.event_log.log_room_created(&created.name);
-                    self.refresh_rooms();
-                    if let Some(idx) = self.rooms.iter().position(|room| room.name == created.name)
+                Ok(()) => {
+                    self.transient.remove(&room_name);
+                    self.event_log.log_post_create_completed(&room_name);
+                    self.rebuild_rooms();
+                    self.status_message = Some(format!("Room ready: {room_name}"));
+                    if self
+                        .selected_room_info()
+                        .is_some_and(|room| room.name == room_name)
                     {
-                        self.selected_index = idx;
-                        self.enter_selected_room(true);
-                    } else {
-                        self.event_log.log_error(
-                            Some(&created.name),
-                            "Room created but not found in worktree list after refresh",
-                        );
+                        self.enter_selected_room(false);
                     }
-                    self.status_message = Some(format!("Created room: {}", created.name));
                 }
                 Err(err) => {
-                    let error_message = err.to_string();
-                    let full_message = format!("Failed to create room: {error_message}");
-                    self.status_message = Some(full_message.clone());
                     self.event_log
-                        .log_error(Some(&result.room_name), &full_message);
-                    if let Some(pending_room) = self.pending_rooms.get_mut(&result.room_name) {
-                        pending_room.status = PendingRoomStatus::Failed(error_message);
-                    }
-                    self.refresh_rooms();
+                        .log_post_create_failed(&room_name, &err.to_string());
+                    self.setup_failures.insert(
+                        room_name.clone(),
+                        err.output().unwrap_or_default().to_string(),
+                    );
+                    self.transient
+                        .set_error(&room_name, format!("post_create hook {err}"));
+                    self.rebuild_rooms();
+                    self.status_message = Some(format!("Setup failed for {room_name}: {err}"));
                 }
             }
         }
     }
 
+    /// Output captured from the room's failed `post_create` hooks, if setup failed.
+    pub fn setup_failure_output(&self, room: &RoomInfo) -> Option<&str> {
+        self.setup_failures.get(&room.name).map(String::as_str)
+    }
+
+    /// Output of the room's last failed deletion, if it failed.
+    pub fn delete_failure_output(&self, room: &RoomInfo) -> Option<&str> {
+        self.delete_failures.get(&room.name).map(String::as_str)
+    }
+
     fn retry_pending_room(&mut self, room_name: &str) {
         let Some(mut pending_room) = self.pending_rooms.remove(room_name) else {
             return;
@@ -1254,27 +2196,36 @@ impl App {
         pending_room.status = PendingRoomStatus::Creating;
         let options = CreateRoomOptions {
             name: Some(pending_room.name.clone()),
-            branch: Some(pending_room.branch.clone()),
-            base_branch: self.config.base_branch.clone(),
+            branch: pending_room.branch.clone(),
+            base_branch: pending_room.base_branch.clone(),
+            commit: pending_room.commit.clone(),
+            ports: self.config.ports.clone(),
+            files: self.config.files.clone(),
+            dependency_dirs: self.config.dependency_dirs.clone(),
         };
         self.start_room_creation(options, pending_room);
     }
 
     fn remove_pending_room(&mut self, room_name: &str) {
         if self.pending_rooms.remove(room_name).is_some() {
-            self.refresh_rooms();
+            self.rebuild_rooms();
             self.status_message = Some(format!("Removed failed room: {room_name}"));
         }
     }
 
     fn update_creation_blink(&mut self) {
-        // Only update animation if there are actually rooms being created
-        let has_creating_rooms = self
-            .pending_rooms
-            .values()
-            .any(|room| matches!(room.status, PendingRoomStatus::Creating));
-
-        if !has_creating_rooms {
+        // Only update animation if rooms are actually being created or deleted
+        let has_busy_rooms = !self.setup_handles.is_empty()
+            || self
+                .pending_rooms
+                .values()
+                .any(|room| matches!(room.status, PendingRoomStatus::Creating))
+            || self
+                .rooms
+                .iter()
+                .any(|room| room.status == RoomStatus::Deleting);
+
+        if !has_busy_rooms {
             return;
         }
 
@@ -1289,26 +2240,301 @@ impl App {
         if self.skip_hooks || commands.is_empty() {
             return;
         }
+        let Some(context) = self
+            .selected_room_info()
+            .and_then(|room| self.hook_context(&room.name))
+        else {
+            return;
+        };
 
         for command in commands {
-            if command.ends_with('\n') {
-                self.write_to_pty(command.as_bytes(), false);
+            let line = hook_command_line(&context.expand(command));
+            self.write_to_pty(line.as_bytes(), false);
+        }
+    }
+
+    /// Detect shells that have exited since the last poll.
+    fn poll_shell_exits(&mut self) {
+        let mut exited = Vec::new();
+        for (room_name, tabs) in self.sessions.iter_mut() {
+            for tab in tabs.iter_mut() {
+                if let Some(code) = tab.session.poll_exit() {
+                    exited.push((room_name.clone(), tab.name.clone(), tab.kind, code));
+                }
+            }
+        }
+
+        for (room_name, tab_name, kind, code) in exited {
+            match kind {
+                TabKind::Shell => {
+                    self.event_log.log_shell_exited(&room_name, &tab_name, code);
+                    self.status_message = Some(format!(
+                        "Shell '{}' in '{}' exited with code {} - press Enter to restart",
+                        tab_name, room_name, code
+                    ));
+                }
+                TabKind::Service => {
+                    self.event_log
+                        .log_service_exited(&room_name, &tab_name, code);
+                    self.status_message = Some(format!(
+                        "Service '{}' in '{}' exited with code {} - press s to restart",
+                        tab_name, room_name, code
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Start a room's configured services as background tabs.
+    ///
+    /// Services live alongside the room's shells, so the room must already
+    /// have a session.
+    fn start_services(&mut self, room_name: &str, room_path: &Path, mode: ServiceStart) {
+        let (cols, rows) = self.calculate_pty_size();
+        let services = self.config.services.clone();
+        let room_env = self.room_env(room_name);
+        let mut started = 0;
+
+        for service in &services {
+            if mode == ServiceStart::Autostart && !service.autostart {
+                continue;
+            }
+            let Some(tabs) = self.sessions.get_mut(room_name) else {
+                return;
+            };
+            let existing = tabs.position(&service.name);
+            if let Some(tab) = existing.and_then(|index| tabs.get(index)) {
+                if tab.kind != TabKind::Service {
+                    self.status_message = Some(format!(
+                        "Cannot start service '{}': a tab with that name exists",
+                        service.name
+                    ));
+                    continue;
+                }
+                if mode != ServiceStart::Restart && !tab.session.has_exited() {
+                    continue;
+                }
+            }
+
+            let mut env = room_env.clone();
+            env.extend(service.env.clone());
+            match PtySession::with_command(cols, rows, room_path, &service.command, &env) {
+                Ok(session) => {
+                    match existing.and_then(|index| tabs.get_mut(index)) {
+                        Some(tab) => {
+                            tab.session.kill();
+                            tab.session = session;
+                        }
+                        None => tabs.push_service(service.name.clone(), session),
+                    }
+                    self.event_log.log_service_started(room_name, &service.name);
+                    started += 1;
+                }
+                Err(e) => {
+                    let message = format!("Failed to start service '{}': {}", service.name, e);
+                    self.event_log.log_error(Some(room_name), &message);
+                    self.status_message = Some(message);
+                }
+            }
+        }
+
+        if started > 0 && mode != ServiceStart::Autostart {
+            self.status_message = Some(format!("Started {} service(s) in {}", started, room_name));
+        }
+    }
+
+    /// (Re)start all services in the selected room, opening a shell first if needed.
+    fn restart_selected_services(&mut self) {
+        if self.config.services.is_empty() {
+            self.status_message = Some("No services configured in .roomsrc.json".to_string());
+            return;
+        }
+        let Some(room) = self.selected_room_info() else {
+            return;
+        };
+        if self.pending_room_status(room).is_some()
+            || self.room_section(room) == RoomSection::Failed
+        {
+            self.status_message = Some("Cannot start services in this room".to_string());
+            return;
+        }
+        let room_name = room.name.clone();
+        let room_path = room.path.clone();
+
+        let mode = if self.sessions.contains_key(&room_name) {
+            ServiceStart::Restart
+        } else {
+            self.enter_selected_room(false);
+            ServiceStart::All
+        };
+        self.start_services(&room_name, &room_path, mode);
+    }
+
+    /// Statuses of a room's running or finished services, in tab order.
+    pub(crate) fn service_statuses(&self, room: &RoomInfo) -> Vec<(&str, ServiceStatus)> {
+        let Some(tabs) = self.sessions.get(&room.name) else {
+            return Vec::new();
+        };
+        tabs.iter()
+            .filter(|tab| tab.kind == TabKind::Service)
+            .map(|tab| {
+                let status = match tab.session.exit_code() {
+                    None => ServiceStatus::Running,
+                    Some(0) => ServiceStatus::Exited,
+                    Some(code) => ServiceStatus::Failed(code),
+                };
+                (tab.name.as_str(), status)
+            })
+            .collect()
+    }
+
+    /// Exit code of the room's active tab, if its shell has exited.
+    pub fn session_exit_code(&self, room: &RoomInfo) -> Option<u32> {
+        self.sessions.get(&room.name)?.active().session.exit_code()
+    }
+
+    /// Terminal tabs for a room, if it has any.
+    pub fn room_tabs(&self, room: &RoomInfo) -> Option<&RoomTabs> {
+        self.sessions.get(&room.name)
+    }
+
+    /// Re-spawn shells and restore selection, focus and sidebar visibility
+    /// from the layout saved on the previous quit.
+    fn restore_layout(&mut self) {
+        let layout = self.state.layout.clone();
+
+        if self.config.restore.sessions {
+            let (cols, rows) = self.calculate_pty_size();
+            let post_enter = if self.config.restore.post_enter && !self.skip_hooks {
+                self.config.hooks.post_enter.clone()
             } else {
-                let mut line = command.clone();
-                line.push('\n');
-                self.write_to_pty(line.as_bytes(), false);
+                Vec::new()
+            };
+            let restorable: Vec<(String, PathBuf)> = restorable_rooms(&layout, &self.rooms)
+                .into_iter()
+                .map(|room| (room.name.clone(), room.path.clone()))
+                .collect();
+
+            for (room_name, room_path) in restorable {
+                let tab_names = layout
+                    .tabs
+                    .get(&room_name)
+                    .filter(|names| !names.is_empty())
+                    .cloned()
+                    .unwrap_or_else(|| vec![DEFAULT_TAB_NAME.to_string()]);
+
+                let env = self.room_env(&room_name);
+                let post_enter_lines: Vec<String> = self
+                    .hook_context(&room_name)
+                    .map(|context| {
+                        post_enter
+                            .iter()
+                            .map(|command| hook_command_line(&context.expand(command)))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let mut tabs: Option<RoomTabs> = None;
+                for tab_name in tab_names {
+                    match PtySession::new(cols, rows, &room_path, &env) {
+                        Ok(mut session) => {
+                            for line in &post_enter_lines {
+                                let _ = session.write(line.as_bytes());
+                            }
+                            match tabs.as_mut() {
+                                Some(tabs) => tabs.push(tab_name, session),
+                                None => tabs = Some(TabSet::new(tab_name, session)),
+                            }
+                        }
+                        Err(e) => {
+                            self.event_log.log_error(
+                                Some(&room_name),
+                                &format!("Failed to restore shell '{}': {}", tab_name, e),
+                            );
+                        }
+                    }
+                }
+                if let Some(mut tabs) = tabs {
+                    tabs.select(layout.active_tabs.get(&room_name).copied().unwrap_or(0));
+                    self.sessions.insert(room_name.clone(), tabs);
+                    self.start_services(&room_name, &room_path, ServiceStart::Autostart);
+                }
             }
+            self.sort_rooms_for_sidebar();
         }
+
+        if let Some(index) = layout
+            .selected_room
+            .as_ref()
+            .and_then(|name| self.rooms.iter().position(|room| &room.name == name))
+        {
+            self.selected_index = index;
+        }
+
+        if layout.terminal_focused && self.current_session().is_some() {
+            self.focus = Focus::MainScene;
+            self.sidebar_visible = layout.sidebar_visible;
+        }
+    }
+
+    /// Save the session layout so it can be restored on the next launch.
+    fn save_layout(&mut self) {
+        let layout = SessionLayout {
+            active_rooms: self
+                .rooms
+                .iter()
+                .filter(|room| self.sessions.contains_key(&room.name))
+                .map(|room| room.name.clone())
+                .collect(),
+            // Services are started from config on restore, so only shells are saved
+            tabs: self
+                .sessions
+                .iter()
+                .map(|(room_name, tabs)| {
+                    let names = tabs
+                        .iter()
+                        .filter(|tab| tab.kind == TabKind::Shell)
+                        .map(|tab| tab.name.clone())
+                        .collect();
+                    (room_name.clone(), names)
+                })
+                .collect(),
+            active_tabs: self
+                .sessions
+                .iter()
+                .map(|(room_name, tabs)| {
+                    let active = tabs
+                        .iter()
+                        .take(tabs.active_index() + 1)
+                        .filter(|tab| tab.kind == TabKind::Shell)
+                        .count()
+                        .saturating_sub(1);
+                    (room_name.clone(), active)
+                })
+                .collect(),
+            selected_room: self.selected_room_info().map(|room| room.name.clone()),
+            terminal_focused: self.focus == Focus::MainScene,
+            sidebar_visible: self.sidebar_visible,
+        };
+        self.update_state(|state| state.layout = layout);
     }
 
     fn handle_confirm_key(&mut self, key: KeyEvent) {
+        if self.confirm.name_input().is_some() {
+            self.handle_confirm_name_key(key);
+            return;
+        }
+
         match key.code {
             KeyCode::Esc => {
                 self.confirm.cancel();
             }
+            KeyCode::Enter | KeyCode::Char('y') if self.confirm.is_locked() => {
+                self.status_message =
+                    Some("Unlock the worktree with git worktree unlock first".to_string());
+            }
             KeyCode::Enter => {
-                if let Some(room_name) = self.confirm.confirm() {
-                    self.delete_room(&room_name);
+                if let Some(confirmed) = self.confirm.confirm() {
+                    self.apply_confirmed(confirmed);
                 }
             }
             KeyCode::Left
@@ -1320,10 +2546,8 @@ impl App {
             }
             KeyCode::Char('y') => {
                 // Quick confirm with 'y'
-                if let ConfirmState::DeleteRoom { room_name, .. } = &self.confirm {
-                    let name = room_name.clone();
-                    self.confirm.cancel();
-                    self.delete_room(&name);
+                if let Some(confirmed) = self.confirm.accept() {
+                    self.apply_confirmed(confirmed);
                 }
             }
             KeyCode::Char('n') => {
@@ -1334,8 +2558,56 @@ impl App {
         }
     }
 
+    /// Carry out what a confirmation dialog asked for.
+    fn apply_confirmed(&mut self, confirmed: Confirmed) {
+        match confirmed {
+            Confirmed::DeleteRoom(room_name) => self.delete_room(&room_name),
+            Confirmed::CloseTab {
+                room_name,
+                tab_index,
+            } => self.close_room_tab(&room_name, tab_index),
+        }
+    }
+
+    /// Keys for a delete confirmation that asks for the room name to be typed.
+    fn handle_confirm_name_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.confirm.cancel();
+            }
+            KeyCode::Enter => {
+                if self.confirm.awaits_name() {
+                    self.status_message =
+                        Some("Type the room name to confirm the deletion".to_string());
+                    return;
+                }
+                if let Some(confirmed) = self.confirm.confirm() {
+                    self.apply_confirmed(confirmed);
+                }
+            }
+            code => {
+                let Some(input) = self.confirm.name_input() else {
+                    return;
+                };
+                match code {
+                    KeyCode::Backspace => input.backspace(),
+                    KeyCode::Delete => input.delete(),
+                    KeyCode::Left => input.move_left(),
+                    KeyCode::Right => input.move_right(),
+                    KeyCode::Home => input.move_start(),
+                    KeyCode::End => input.move_end(),
+                    KeyCode::Char(c) => input.insert(c),
+                    _ => {}
+                }
+            }
+        }
+    }
+
     /// Start the room deletion flow.
-    fn start_room_deletion(&mut self) {
+    ///
+    /// With `immediate`, the room is deleted without a confirmation unless
+    /// the check finds unpushed commits or a locked worktree.
+    fn start_room_deletion(&mut self, immediate: bool) {
         let room = match self.selected_room_info() {
             Some(r) => r,
             None => {
@@ -1349,150 +2621,828 @@ impl App {
             return;
         }
 
+        // The confirmation opens once the check finishes
         let room_name = room.name.clone();
+        let id = self.jobs.push(Job::CheckDelete {
+            room_name: room_name.clone(),
+            path: room.path.clone(),
+            branch: room.branch.clone(),
+            base_branch: self.base_branch(&room_name),
+            immediate,
+        });
+        self.status_message = Some(format!("Checking {room_name} for changes..."));
+        self.room_jobs.insert(room_name, id);
+    }
+
+    /// Ask to confirm a deletion once the room's check finished.
+    fn confirm_room_deletion(
+        &mut self,
+        room_name: &str,
+        result: Result<DeleteCheck, RemoveRoomError>,
+        immediate: bool,
+    ) {
+        let Some(room) = self.rooms.iter().find(|room| room.name == room_name) else {
+            return;
+        };
+        // git refuses to remove a locked worktree, even with --force
+        let locked = self
+            .worktrees
+            .iter()
+            .find(|worktree| worktree.path == room.path)
+            .and_then(|worktree| worktree.locked.clone());
+        // Uncommitted changes are snapshotted, but unpushed commits need a typed confirmation
+        if immediate
+            && locked.is_none()
+            && result
+                .as_ref()
+                .is_ok_and(|check| check.unpushed_commits() == 0)
+        {
+            self.delete_room(room_name);
+            return;
+        }
+        // Don't replace a dialog opened while the check was running
+        if self.confirm.is_active() || self.prompt.is_active() {
+            return;
+        }
+
         let room_path = room.path.to_string_lossy().to_string();
         let branch = room
             .branch
             .clone()
             .unwrap_or_else(|| "detached".to_string());
-
-        // Check dirty status
-        let dirty_status = match DirtyStatus::check(&room.path) {
-            Ok(status) => Some(status),
+        let check = match result {
+            Ok(check) => {
+                self.status_message = None;
+                Some(check)
+            }
             Err(e) => {
                 self.status_message = Some(format!("Warning: couldn't check status: {}", e));
                 None
             }
-        };
-
-        self.confirm = ConfirmState::start_delete(room_name, room_path, branch, dirty_status);
+        };
+
+        self.confirm =
+            ConfirmState::start_delete(room_name.to_string(), room_path, branch, check, locked);
+    }
+
+    /// Queue the deletion of the room with the given name.
+    fn delete_room(&mut self, room_name: &str) {
+        let Some(path) = self
+            .rooms
+            .iter()
+            .find(|room| room.name == room_name)
+            .map(|room| room.path.clone())
+        else {
+            return;
+        };
+        let on_leave = self.leave_context(room_name);
+        // A dev server or watcher left running could write into the worktree while it's removed
+        if let Some(mut tabs) = self.sessions.remove(room_name) {
+            for tab in tabs.iter_mut() {
+                tab.session.kill();
+            }
+            if self
+                .selected_room_info()
+                .is_some_and(|room| room.name == room_name)
+            {
+                self.focus = Focus::Sidebar;
+            }
+        }
+        self.delete_failures.remove(room_name);
+        self.transient.set_status(room_name, RoomStatus::Deleting);
+        self.rebuild_rooms();
+        let id = self.jobs.push(Job::Remove {
+            room_name: room_name.to_string(),
+            path,
+            base_branch: self.base_branch(room_name),
+            on_leave,
+        });
+        self.room_jobs.insert(room_name.to_string(), id);
+        self.status_message = Some(format!("Deleting room: {room_name}"));
+    }
+
+    fn finish_room_deletion(
+        &mut self,
+        room_name: &str,
+        result: Result<RemovedRoom, RemoveRoomError>,
+        snapshot: Option<RoomSnapshot>,
+    ) {
+        self.transient.remove(room_name);
+        match result {
+            Ok(removed) => {
+                let name = removed.name;
+                // Remove PTY session if exists (keyed by room name)
+                self.sessions.remove(&name);
+                self.setup_failures.remove(&name);
+                self.delete_failures.remove(&name);
+                self.port_blocks.remove(&name);
+                let undo_hint = if snapshot.is_some() {
+                    " (u to undo)"
+                } else {
+                    ""
+                };
+                let dropped = self.update_state(|state| {
+                    let metadata = state.remove_by_name(&name);
+                    let Some(snapshot) = snapshot else {
+                        return Vec::new();
+                    };
+                    state.record_trashed(TrashedRoom {
+                        name: name.clone(),
+                        branch: snapshot.branch,
+                        head: snapshot.head,
+                        changes_ref: snapshot.changes_ref,
+                        base_branch: metadata.as_ref().and_then(|room| room.base_branch.clone()),
+                        notes: metadata.and_then(|room| room.notes),
+                        deleted_at: chrono::Utc::now(),
+                    })
+                });
+                self.discard_snapshots(dropped);
+
+                // Log the event
+                self.event_log.log_room_deleted(&name);
+
+                self.rebuild_rooms();
+                self.status_message = Some(match removed.post_hook_error {
+                    Some(err) => {
+                        let message = format!("post_delete hook {err}");
+                        self.event_log.log_error(Some(&name), &message);
+                        format!("Deleted room: {}, but {}{}", name, message, undo_hint)
+                    }
+                    None => format!("Deleted room: {}{}", name, undo_hint),
+                });
+            }
+            Err(e) => {
+                // Keep the room in the Failed section with what went wrong
+                let output = e.output().map_or_else(|| e.to_string(), str::to_string);
+                self.delete_failures
+                    .insert(room_name.to_string(), output.trim_end().to_string());
+                self.transient.set_error(room_name, e.to_string());
+                self.rebuild_rooms();
+                self.status_message = Some(format!("Failed to delete room: {}", e));
+                self.event_log.log_error(Some(room_name), &e.to_string());
+            }
+        }
+    }
+
+    /// Delete the saved changes of rooms dropped from the trash.
+    fn discard_snapshots(&mut self, trashed: Vec<TrashedRoom>) {
+        let changes_refs: Vec<String> = trashed
+            .into_iter()
+            .filter_map(|room| room.changes_ref)
+            .collect();
+        if !changes_refs.is_empty() {
+            self.jobs.push(Job::DiscardSnapshots { changes_refs });
+        }
+    }
+
+    /// Recreate the selected deleted room, or the most recently deleted one.
+    ///
+    /// The room comes back on its branch (recreated at the old commit if
+    /// the branch is gone) with its uncommitted changes restored.
+    fn restore_trashed_room(&mut self) {
+        let Some(trashed) = self
+            .selected_trashed_room()
+            .or_else(|| self.state.trash.first())
+            .cloned()
+        else {
+            self.status_message = Some("No deleted rooms to restore".to_string());
+            return;
+        };
+
+        let options = CreateRoomOptions {
+            name: Some(trashed.name.clone()),
+            branch: trashed.branch.clone(),
+            base_branch: trashed.branch.as_ref().map(|_| trashed.head.clone()),
+            commit: trashed.branch.is_none().then(|| trashed.head.clone()),
+            ports: self.config.ports.clone(),
+            files: self.config.files.clone(),
+            dependency_dirs: self.config.dependency_dirs.clone(),
+        };
+        match self.prepare_room_create(options) {
+            Ok((options, mut creating_room)) => {
+                creating_room.after_create = Some(AfterCreate::Restore {
+                    changes_ref: trashed.changes_ref,
+                    head: trashed.head,
+                });
+                self.start_room_creation(options, creating_room);
+            }
+            Err(message) => {
+                self.status_message = Some(format!("Can't restore {}: {message}", trashed.name));
+            }
+        }
+    }
+
+    /// Forget the selected deleted room and its saved changes.
+    fn discard_trashed_room(&mut self) {
+        let Some(name) = self.selected_trashed_room().map(|room| room.name.clone()) else {
+            return;
+        };
+        if let Some(trashed) = self.update_state(|state| state.take_trashed(&name)) {
+            self.discard_snapshots(vec![trashed]);
+            self.clamp_selection();
+            self.status_message = Some(format!("Discarded deleted room: {name}"));
+        }
+    }
+
+    /// Start the room rename flow.
+    fn start_room_rename(&mut self) {
+        let room = match self.selected_room_info() {
+            Some(r) => r,
+            None => {
+                self.status_message = Some("No room selected".to_string());
+                return;
+            }
+        };
+
+        if room.is_primary {
+            self.status_message = Some("Cannot rename the primary worktree".to_string());
+            return;
+        }
+
+        let current_name = room.name.clone();
+        self.prompt = PromptState::start_room_rename(current_name);
+    }
+
+    /// Queue a room rename.
+    fn apply_room_rename(&mut self, old_name: &str, new_name: &str) {
+        // Skip if new name is empty
+        if new_name.is_empty() {
+            self.status_message = Some("Rename cancelled: name cannot be empty".to_string());
+            return;
+        }
+
+        let id = self.jobs.push(Job::Rename {
+            old_name: old_name.to_string(),
+            new_name: new_name.to_string(),
+            base_branch: self.base_branch(old_name),
+            on_leave: self.leave_context(old_name),
+        });
+        self.room_jobs.insert(old_name.to_string(), id);
+        self.status_message = Some(format!("Renaming: {} -> {}", old_name, new_name));
+    }
+
+    fn finish_room_rename(&mut self, old_name: &str, result: Result<RenamedRoom, RenameRoomError>) {
+        match result {
+            Ok(renamed) => {
+                let new_name = renamed.name;
+                // Remove PTY session since the working directory changed (keyed by old name)
+                self.sessions.remove(old_name);
+                self.transient.remove(old_name);
+                self.setup_failures.remove(old_name);
+                self.delete_failures.remove(old_name);
+                if let Some(block) = self.port_blocks.remove(old_name) {
+                    self.port_blocks.insert(new_name.clone(), block);
+                }
+                self.update_state(|state| state.record_renamed(old_name, &new_name, &renamed.path));
+
+                // Log the event
+                self.event_log.log_room_renamed(old_name, &new_name);
+
+                self.rebuild_rooms();
+                self.status_message = Some(match renamed.post_hook_error {
+                    Some(err) => {
+                        let message = format!("post_rename hook {err}");
+                        self.event_log.log_error(Some(&new_name), &message);
+                        format!("Renamed: {} -> {}, but {}", old_name, new_name, message)
+                    }
+                    None => format!("Renamed: {} -> {}", old_name, new_name),
+                });
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to rename room: {}", e));
+                self.event_log.log_error(Some(old_name), &e.to_string());
+            }
+        }
+    }
+
+    /// Collect the background git status check and start a new one when due.
+    ///
+    /// Only one check runs at a time; rooms are checked in a single thread.
+    fn poll_git_statuses(&mut self) {
+        if let Some(receiver) = &self.git_status_handle {
+            match receiver.try_recv() {
+                Ok(statuses) => {
+                    self.git_statuses = statuses;
+                    self.git_status_handle = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => return,
+                Err(mpsc::TryRecvError::Disconnected) => self.git_status_handle = None,
+            }
+        }
+        if self
+            .git_status_checked_at
+            .is_some_and(|checked_at| checked_at.elapsed() < GIT_STATUS_INTERVAL)
+        {
+            return;
+        }
+
+        let rooms: Vec<(String, PathBuf, Option<String>, Option<String>)> = self
+            .rooms
+            .iter()
+            .filter(|room| self.pending_room_status(room).is_none())
+            .map(|room| {
+                let base_branch = self
+                    .room_metadata(room)
+                    .and_then(|metadata| metadata.base_branch.clone())
+                    .or_else(|| self.config.base_branch.clone());
+                (
+                    room.name.clone(),
+                    room.path.clone(),
+                    room.branch.clone(),
+                    base_branch,
+                )
+            })
+            .collect();
+        let repo_root = self.repo_root.clone();
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let stash_branches = list_stash_branches_from(&repo_root).unwrap_or_default();
+            let statuses = rooms
+                .into_iter()
+                .map(|(name, path, branch, base_branch)| {
+                    let status = RoomGitStatus::check(
+                        &path,
+                        branch.as_deref(),
+                        base_branch.as_deref(),
+                        &stash_branches,
+                    );
+                    (name, status)
+                })
+                .collect();
+            let _ = tx.send(statuses);
+        });
+        self.git_status_handle = Some(rx);
+        self.git_status_checked_at = Some(Instant::now());
+    }
+
+    /// Git status of a room from the last background check.
+    pub(crate) fn git_status(&self, room: &RoomInfo) -> Option<&RoomGitStatus> {
+        self.git_statuses.get(&room.name)
+    }
+
+    /// Re-read the bisect progress of detached rooms on the job queue.
+    ///
+    /// `git bisect` always detaches HEAD, so rooms on a branch are skipped.
+    fn refresh_bisects(&mut self) {
+        let rooms: Vec<(String, PathBuf)> = self
+            .rooms
+            .iter()
+            .filter(|room| {
+                room.branch.is_none() && !room.is_primary && room.status != RoomStatus::Creating
+            })
+            .map(|room| (room.name.clone(), room.path.clone()))
+            .collect();
+        if rooms.is_empty() {
+            self.bisects.clear();
+            return;
+        }
+        self.jobs.push(Job::CheckBisects { rooms });
+    }
+
+    /// Replace the bisect progress with the result of a check.
+    fn apply_bisects(
+        &mut self,
+        statuses: Vec<(String, Result<Option<BisectStatus>, CommandError>)>,
+    ) {
+        self.bisects.clear();
+        for (room_name, status) in statuses {
+            match status {
+                Ok(Some(status)) => {
+                    self.bisects.insert(room_name, status);
+                }
+                Ok(None) => {}
+                Err(e) => self.event_log.log_error(
+                    Some(&room_name),
+                    &format!("failed to read bisect status: {e}"),
+                ),
+            }
+        }
+    }
+
+    /// Bisect progress of a room, if it's bisecting.
+    pub(crate) fn bisect_status(&self, room: &RoomInfo) -> Option<&BisectStatus> {
+        self.bisects.get(&room.name)
+    }
+
+    /// Mark the commit under test in the selected bisect room.
+    ///
+    /// Git checks out the next commit to test, which can take a while on a
+    /// large tree, so the mark runs on the job queue.
+    fn mark_bisect(&mut self, mark: BisectMark) {
+        let Some(room) = self.selected_room_info() else {
+            return;
+        };
+        let room_name = room.name.clone();
+        let room_path = room.path.clone();
+        if self.check_room_busy_and_notify(&room_name) {
+            return;
+        }
+        match self.bisects.get(&room_name) {
+            None => {
+                self.status_message =
+                    Some("Room is not bisecting. Press B to start a bisect.".to_string());
+                return;
+            }
+            Some(BisectStatus {
+                first_bad: Some(sha),
+                ..
+            }) => {
+                self.status_message = Some(format!(
+                    "Bisect finished: first bad commit is {}",
+                    short_sha(sha)
+                ));
+                return;
+            }
+            Some(_) => {}
+        }
+
+        let id = self.jobs.push(Job::BisectMark {
+            room_name: room_name.clone(),
+            path: room_path,
+            mark,
+        });
+        self.status_message = Some(format!("Marking {}...", mark.as_str()));
+        self.room_jobs.insert(room_name, id);
+    }
+
+    /// Report a bisect mark and the progress after it.
+    fn finish_bisect_mark(
+        &mut self,
+        room_name: &str,
+        mark: BisectMark,
+        result: Result<Option<BisectStatus>, CommandError>,
+    ) {
+        let status = match result {
+            Ok(status) => status,
+            Err(e) => {
+                let message = format!("Failed to mark {}: {e}", mark.as_str());
+                self.event_log.log_error(Some(room_name), &message);
+                self.status_message = Some(message);
+                return;
+            }
+        };
+        self.status_message = Some(match &status {
+            Some(BisectStatus {
+                first_bad: Some(sha),
+                ..
+            }) => format!("First bad commit: {}", short_sha(sha)),
+            Some(status) => format!("Marked {}; now at {}", mark.as_str(), bisect_label(status)),
+            None => format!("Marked {}", mark.as_str()),
+        });
+        match status {
+            Some(status) => self.bisects.insert(room_name.to_string(), status),
+            None => self.bisects.remove(room_name),
+        };
+    }
+
+    /// End the bisect in the selected room, going back to the commit it started from.
+    fn reset_bisect(&mut self) {
+        let Some(room) = self.selected_room_info() else {
+            return;
+        };
+        let room_name = room.name.clone();
+        let room_path = room.path.clone();
+        if self.check_room_busy_and_notify(&room_name) {
+            return;
+        }
+        if !self.bisects.contains_key(&room_name) {
+            self.status_message = Some("Room is not bisecting".to_string());
+            return;
+        }
+
+        let id = self.jobs.push(Job::BisectReset {
+            room_name: room_name.clone(),
+            path: room_path,
+        });
+        self.status_message = Some(format!("Ending bisect in {room_name}..."));
+        self.room_jobs.insert(room_name, id);
+    }
+
+    fn finish_bisect_reset(&mut self, room_name: &str, result: Result<(), CommandError>) {
+        match result {
+            Ok(()) => {
+                self.bisects.remove(room_name);
+                self.status_message = Some(format!("Ended bisect in {room_name}"));
+            }
+            Err(e) => {
+                let message = format!("Failed to end bisect: {e}");
+                self.event_log.log_error(Some(room_name), &message);
+                self.status_message = Some(message);
+            }
+        }
+    }
+
+    /// Save user notes for a room.
+    fn apply_room_notes(&mut self, room_name: &str, notes: Option<String>) {
+        let saved = self.update_state(|state| {
+            let room = state.find_by_name_mut(room_name)?;
+            room.notes = notes;
+            Some(())
+        });
+        if saved.is_none() {
+            self.status_message = Some(format!("Room '{}' is not tracked", room_name));
+            return;
+        }
+        self.status_message = Some(format!("Saved notes for {}", room_name));
+    }
+
+    /// Get or create a PTY session for the selected room.
+    ///
+    /// If the active tab's shell has exited it is replaced with a fresh one.
+    pub fn get_or_create_session(&mut self, cols: u16, rows: u16) -> Option<bool> {
+        let room = self.selected_room_info()?;
+        let room_name = room.name.clone();
+        let room_path = room.path.clone();
+
+        let needs_shell = self
+            .sessions
+            .get(&room_name)
+            .is_none_or(|tabs| tabs.active().session.has_exited());
+        if !needs_shell {
+            return Some(false);
+        }
+
+        // An exited service tab restarts its service rather than opening a shell
+        if self
+            .sessions
+            .get(&room_name)
+            .is_some_and(|tabs| tabs.active().kind == TabKind::Service)
+        {
+            self.start_services(&room_name, &room_path, ServiceStart::All);
+            self.scrollback_offset = 0;
+            return Some(false);
+        }
+
+        let session = match PtySession::new(cols, rows, &room_path, &self.room_env(&room_name)) {
+            Ok(session) => session,
+            Err(e) => {
+                self.status_message = Some(format!("Failed to start shell: {}", e));
+                return None;
+            }
+        };
+        match self.sessions.get_mut(&room_name) {
+            Some(tabs) => tabs.active_mut().session = session,
+            None => {
+                self.sessions
+                    .insert(room_name.clone(), TabSet::new(DEFAULT_TAB_NAME, session));
+                self.sort_rooms_for_sidebar();
+                self.start_services(&room_name, &room_path, ServiceStart::Autostart);
+            }
+        }
+        self.scrollback_offset = 0;
+        Some(true)
+    }
+
+    /// Get the active tab's PTY session for the selected room, if it exists.
+    pub fn current_session(&self) -> Option<&PtySession> {
+        let room = self.selected_room_info()?;
+        Some(&self.sessions.get(&room.name)?.active().session)
+    }
+
+    /// Get the active tab's PTY session for the selected room mutably, if it exists.
+    pub fn current_session_mut(&mut self) -> Option<&mut PtySession> {
+        let room_name = self.selected_room_info()?.name.clone();
+        Some(&mut self.sessions.get_mut(&room_name)?.active_mut().session)
+    }
+
+    /// Open a new terminal tab in the selected room and focus it.
+    fn open_tab(&mut self) {
+        let Some(room) = self.selected_room_info() else {
+            return;
+        };
+        let room_name = room.name.clone();
+        let room_path = room.path.clone();
+
+        let Some(tabs) = self.sessions.get(&room_name) else {
+            // First tab: same as entering the room
+            self.enter_selected_room(false);
+            return;
+        };
+        let tab_name = tabs.unique_name(DEFAULT_TAB_NAME);
+
+        let (cols, rows) = self.calculate_pty_size();
+        match PtySession::new(cols, rows, &room_path, &self.room_env(&room_name)) {
+            Ok(session) => {
+                self.reset_scrollback();
+                if let Some(tabs) = self.sessions.get_mut(&room_name) {
+                    tabs.push(tab_name.clone(), session);
+                }
+                self.main_scene_visible = true;
+                self.focus = Focus::MainScene;
+                let post_enter = self.config.hooks.post_enter.clone();
+                self.run_hook_commands(&post_enter);
+                self.status_message = Some(format!("Opened tab '{}'", tab_name));
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to start shell: {}", e));
+            }
+        }
     }
 
-    /// Delete the currently selected room immediately without confirmation.
-    fn delete_room_immediate(&mut self) {
-        let room = match self.selected_room_info() {
-            Some(r) => r,
-            None => {
-                self.status_message = Some("No room selected".to_string());
-                return;
-            }
+    /// Close the active tab in the selected room.
+    ///
+    /// Asks first if the tab's process is still running.
+    fn close_tab(&mut self) {
+        let Some(room_name) = self.selected_room_info().map(|room| room.name.clone()) else {
+            return;
         };
-
-        if room.is_primary {
-            self.status_message = Some("Cannot delete the primary worktree".to_string());
+        let Some(tabs) = self.sessions.get(&room_name) else {
+            self.status_message = Some("No terminal to close".to_string());
             return;
+        };
+        let tab = tabs.active();
+        if tab.session.has_exited() {
+            self.close_room_tab(&room_name, tabs.active_index());
+        } else {
+            self.confirm =
+                ConfirmState::start_close_tab(room_name, tab.name.clone(), tabs.active_index());
         }
-
-        let room_name = room.name.clone();
-        self.delete_room(&room_name);
     }
 
-    /// Delete the room with the given name.
-    fn delete_room(&mut self, room_name: &str) {
-        // Use force=true since we already warned about dirty status
-        match remove_room(&self.repo_root, &self.rooms_dir, room_name, true) {
-            Ok(name) => {
-                // Remove PTY session if exists (keyed by room name)
-                self.sessions.remove(&name);
-                self.transient.remove(&name);
-
-                // Log the event
-                self.event_log.log_room_deleted(&name);
+    /// Close the tab at `index` in a room, killing its process.
+    ///
+    /// Closing the last tab ends the room's session.
+    fn close_room_tab(&mut self, room_name: &str, index: usize) {
+        let Some(mut tabs) = self.sessions.remove(room_name) else {
+            return;
+        };
+        let room_name = room_name.to_string();
+        if !tabs.select(index) {
+            self.sessions.insert(room_name, tabs);
+            return;
+        }
+        let tab_name = tabs.active().name.clone();
+        self.panes.tab_closed(&room_name, tabs.active_index());
+        // Dropping the session alone leaves processes that ignore SIGHUP running, and unreaped
+        tabs.active_mut().session.kill();
 
-                // Refresh rooms from git worktrees
-                self.refresh_rooms();
-                self.status_message = Some(format!("Deleted room: {}", name));
+        self.scrollback_offset = 0;
+        self.prev_scrollback_offset = 0;
+        match tabs.close_active() {
+            Some(tabs) => {
+                self.sessions.insert(room_name, tabs);
             }
-            Err(e) => {
-                self.status_message = Some(format!("Failed to delete room: {}", e));
-                self.event_log.log_error(Some(room_name), &e.to_string());
+            None => {
+                self.focus = Focus::Sidebar;
+                self.sort_rooms_for_sidebar();
+                if let Some((commands, context)) = self.on_leave_hooks(&room_name) {
+                    let event_log = EventLog::new(&self.rooms_dir);
+                    thread::spawn(move || run_on_leave(&commands, &context, &event_log));
+                }
             }
         }
+        self.status_message = Some(format!("Closed tab '{}'", tab_name));
     }
 
-    /// Start the room rename flow.
-    fn start_room_rename(&mut self) {
-        let room = match self.selected_room_info() {
-            Some(r) => r,
-            None => {
-                self.status_message = Some("No room selected".to_string());
-                return;
-            }
+    /// Switch the selected room's active tab.
+    fn switch_tab(&mut self, switch: impl FnOnce(&mut RoomTabs)) {
+        let Some(room_name) = self.selected_room_info().map(|room| room.name.clone()) else {
+            return;
         };
-
-        if room.is_primary {
-            self.status_message = Some("Cannot rename the primary worktree".to_string());
+        if !self.sessions.contains_key(&room_name) {
             return;
         }
+        self.reset_scrollback();
+        if let Some(tabs) = self.sessions.get_mut(&room_name) {
+            switch(tabs);
+        }
+    }
 
-        let current_name = room.name.clone();
-        self.prompt = PromptState::start_room_rename(current_name);
+    /// Start renaming the selected room's active tab.
+    fn start_tab_rename(&mut self) {
+        let Some(room) = self.selected_room_info() else {
+            return;
+        };
+        let Some(tabs) = self.sessions.get(&room.name) else {
+            self.status_message = Some("No terminal tab to rename".to_string());
+            return;
+        };
+        self.prompt = PromptState::start_tab_rename(room.name.clone(), tabs.active().name.clone());
     }
 
-    /// Apply a room rename.
-    fn apply_room_rename(&mut self, old_name: &str, new_name: &str) {
-        // Skip if new name is empty
+    /// Rename the active tab in a room.
+    fn apply_tab_rename(&mut self, room_name: &str, new_name: &str) {
+        let new_name = new_name.trim();
         if new_name.is_empty() {
             self.status_message = Some("Rename cancelled: name cannot be empty".to_string());
             return;
         }
+        let Some(tabs) = self.sessions.get_mut(room_name) else {
+            return;
+        };
+        if tabs.active().name != new_name && tabs.contains(new_name) {
+            self.status_message = Some(format!("Tab '{}' already exists", new_name));
+            return;
+        }
+        let old_name = std::mem::replace(&mut tabs.active_mut().name, new_name.to_string());
+        self.status_message = Some(format!("Renamed tab: {} -> {}", old_name, new_name));
+    }
 
-        match rename_room(&self.repo_root, &self.rooms_dir, old_name, new_name) {
-            Ok(_) => {
-                // Remove PTY session since the working directory changed (keyed by old name)
-                self.sessions.remove(old_name);
-                self.transient.remove(old_name);
-
-                // Log the event
-                self.event_log.log_room_renamed(old_name, new_name);
+    /// The room tab shown by the selected room, if it has a session.
+    fn current_pane(&self) -> Option<Pane> {
+        let room = self.selected_room_info()?;
+        let tabs = self.sessions.get(&room.name)?;
+        Some(Pane {
+            room_name: room.name.clone(),
+            tab: tabs.active_index(),
+        })
+    }
 
-                // Refresh rooms from git worktrees
-                self.refresh_rooms();
-                self.status_message = Some(format!("Renamed: {} -> {}", old_name, new_name));
-            }
-            Err(e) => {
-                self.status_message = Some(format!("Failed to rename room: {}", e));
-                self.event_log.log_error(Some(old_name), &e.to_string());
-            }
+    /// Keep the focused pane in sync with the selection and drop panes
+    /// whose room tab no longer exists.
+    fn sync_panes(&mut self) {
+        if !self.panes.is_split() {
+            return;
         }
+        if let Some(pane) = self.current_pane() {
+            self.panes.set_focused(pane);
+        }
+        let sessions = &self.sessions;
+        self.panes.retain_pinned(|pane| {
+            sessions
+                .get(&pane.room_name)
+                .is_some_and(|tabs| pane.tab < tabs.len())
+        });
     }
 
-    /// Get or create a PTY session for the selected room.
-    pub fn get_or_create_session(&mut self, cols: u16, rows: u16) -> Option<bool> {
-        use std::collections::hash_map::Entry;
+    /// Split the main scene, opening a new tab of the selected room in the new pane.
+    fn split_pane(&mut self, direction: SplitDirection) {
+        let Some(current) = self.current_pane() else {
+            self.status_message = Some("Start a shell in this room before splitting".to_string());
+            return;
+        };
+        self.open_tab();
+        let Some(new) = self.current_pane() else {
+            return;
+        };
+        if new != current {
+            self.panes.split(direction, current, new);
+        }
+    }
 
-        let room = self.selected_room_info()?;
-        let room_name = room.name.clone();
-        let room_path = room.path.clone();
-        let mut created = false;
+    /// Close the focused pane; its tab keeps running.
+    fn close_pane(&mut self) {
+        if !self.panes.is_split() {
+            self.status_message = Some("No split to close".to_string());
+            return;
+        }
+        self.reset_scrollback();
+        let focused = self.panes.close_focused().cloned();
+        if let Some(pane) = focused {
+            self.select_pane(&pane);
+        }
+    }
 
-        if let Entry::Vacant(entry) = self.sessions.entry(room_name.clone()) {
-            match PtySession::new(cols, rows, &room_path) {
-                Ok(session) => {
-                    entry.insert(session);
-                    self.sort_rooms_for_sidebar();
-                    created = true;
-                }
-                Err(e) => {
-                    self.status_message = Some(format!("Failed to start shell: {}", e));
-                    return None;
-                }
-            }
+    /// Move focus to the next or previous pane.
+    fn focus_pane(&mut self, forward: bool) {
+        if !self.panes.is_split() {
+            return;
+        }
+        self.sync_panes();
+        self.reset_scrollback();
+        self.clear_selection();
+        let focused = if forward {
+            self.panes.focus_next()
+        } else {
+            self.panes.focus_previous()
+        };
+        if let Some(pane) = focused.cloned() {
+            self.select_pane(&pane);
         }
+    }
 
-        Some(created)
+    /// Select the room and tab shown by a pane.
+    fn select_pane(&mut self, pane: &Pane) {
+        if let Some(index) = self
+            .rooms
+            .iter()
+            .position(|room| room.name == pane.room_name)
+        {
+            self.selected_index = index;
+        }
+        if let Some(tabs) = self.sessions.get_mut(&pane.room_name) {
+            tabs.select(pane.tab);
+        }
     }
 
-    /// Get the PTY session for the selected room, if it exists.
-    pub fn current_session(&self) -> Option<&PtySession> {
-        let room = self.selected_room_info()?;
-        self.sessions.get(&room.name)
+    /// Index of the pane at a screen position, if split.
+    fn pane_at(&self, column: u16, row: u16) -> Option<usize> {
+        if !self.panes.is_split() {
+            return None;
+        }
+        self.panes
+            .rects(self.main_scene_area())
+            .iter()
+            .position(|rect| rect.contains((column, row).into()))
     }
 
-    /// Get the PTY session for the selected room mutably, if it exists.
-    pub fn current_session_mut(&mut self) -> Option<&mut PtySession> {
-        let room_name = self.selected_room_info()?.name.clone();
-        self.sessions.get_mut(&room_name)
+    /// Return the current session to the live screen before switching away from it.
+    fn reset_scrollback(&mut self) {
+        if let Some(session) = self.current_session_mut() {
+            session.screen_mut().set_scrollback(0);
+        }
+        self.scrollback_offset = 0;
+        self.prev_scrollback_offset = 0;
     }
 
     /// Determine if the terminal cursor should be visible.
@@ -1523,15 +3473,7 @@ impl App {
     }
 
     fn main_scene_inner_rect(&self) -> Rect {
-        let area = Rect {
-            x: 0,
-            y: 0,
-            width: self.last_size.0,
-            height: self.last_size.1,
-        };
-        let chunks = self.calculate_layout(area);
-        let main_area =
-            Self::get_main_scene_area(area, &chunks, self.sidebar_visible, self.main_scene_visible);
+        let main_area = self.current_pane_area(self.main_scene_area());
         Rect {
             x: main_area.x.saturating_add(1),
             y: main_area.y.saturating_add(1),
@@ -1630,10 +3572,8 @@ impl App {
             KeyCode::Up => {
                 menu.selected = menu.selected.saturating_sub(1);
             }
-            KeyCode::Down => {
-                if menu.selected + 1 < menu.items.len() {
-                    menu.selected += 1;
-                }
+            KeyCode::Down if menu.selected + 1 < menu.items.len() => {
+                menu.selected += 1;
             }
             KeyCode::Enter => {
                 let action = menu.items.get(menu.selected).copied();
@@ -1963,6 +3903,69 @@ fn merge_pending_rooms(
     rooms
 }
 
+/// Reload `state` under its lock, apply `update`, and save it if that
+/// changed anything.
+///
+/// Reloading keeps changes headless `rooms` commands made meanwhile, such as
+/// the trash entry of a `rooms delete`. If the state can't be reloaded,
+/// `update` still applies to the copy in memory, which isn't saved.
+fn update_saved_state<T>(
+    state: &mut RoomsState,
+    rooms_dir: &Path,
+    event_log: &EventLog,
+    update: impl FnOnce(&mut RoomsState) -> T,
+) -> T {
+    let lock = state.reload_locked(rooms_dir);
+    if let Err(e) = &lock {
+        event_log.log_error(None, &format!("Failed to reload room state: {}", e));
+    }
+    let before = state.clone();
+    let result = update(state);
+    if lock.is_ok()
+        && *state != before
+        && let Err(e) = state.save_to_rooms_dir(rooms_dir)
+    {
+        event_log.log_error(None, &format!("Failed to save room state: {}", e));
+    }
+    result
+}
+
+/// Run `on_leave` hooks in a room's worktree, logging a failure.
+fn run_on_leave(commands: &[String], context: &HookContext, event_log: &EventLog) {
+    if let Err(err) = hooks::run_commands(commands, &context.path, context) {
+        event_log.log_error(Some(&context.room), &format!("on_leave hook {err}"));
+    }
+}
+
+/// Append a newline to a hook command unless it already ends with one.
+fn hook_command_line(command: &str) -> String {
+    if command.ends_with('\n') {
+        command.to_string()
+    } else {
+        format!("{command}\n")
+    }
+}
+
+/// Rooms from a saved layout that still exist and can host a shell.
+fn restorable_rooms<'a>(layout: &SessionLayout, rooms: &'a [RoomInfo]) -> Vec<&'a RoomInfo> {
+    layout
+        .active_rooms
+        .iter()
+        .filter_map(|name| rooms.iter().find(|room| &room.name == name))
+        .filter(|room| !room.is_prunable && room.last_error.is_none() && room.path.is_dir())
+        .collect()
+}
+
+/// Timestamp used to order rooms within a section for the current sort order.
+fn room_sort_time(state: &RoomsState, room: &RoomInfo) -> Option<chrono::DateTime<chrono::Utc>> {
+    let metadata = state.find_by_name(&room.name)?;
+    match state.sort_order {
+        SortOrder::Name => None,
+        SortOrder::RecentlyUsed => Some(metadata.last_used_at),
+        SortOrder::Created => Some(metadata.created_at),
+    }
+}
+
 fn room_section_rank_with_active(
     room: &RoomInfo,
     active_rooms: &std::collections::HashSet<String>,
@@ -1987,6 +3990,7 @@ mod creating_room_tests {
         RoomInfo {
             name: name.to_string(),
             branch: Some("main".to_string()),
+            head: None,
             path: PathBuf::from("/tmp"),
             status,
             is_prunable: false,
@@ -2003,8 +4007,11 @@ mod creating_room_tests {
             "creating".to_string(),
             PendingRoom {
                 name: "creating".to_string(),
-                branch: "creating".to_string(),
+                branch: Some("creating".to_string()),
                 path: PathBuf::from("/tmp/creating"),
+                base_branch: None,
+                commit: None,
+                after_create: None,
                 status: PendingRoomStatus::Creating,
             },
         );
@@ -2023,8 +4030,11 @@ mod creating_room_tests {
             "creating".to_string(),
             PendingRoom {
                 name: "creating".to_string(),
-                branch: "creating".to_string(),
+                branch: Some("creating".to_string()),
                 path: PathBuf::from("/tmp/creating"),
+                base_branch: None,
+                commit: None,
+                after_create: None,
                 status: PendingRoomStatus::Creating,
             },
         );
@@ -2053,8 +4063,11 @@ mod creating_room_tests {
             "failed-room".to_string(),
             PendingRoom {
                 name: "failed-room".to_string(),
-                branch: "main".to_string(),
+                branch: Some("main".to_string()),
                 path: PathBuf::from("/tmp/failed-room"),
+                base_branch: None,
+                commit: None,
+                after_create: None,
                 status: PendingRoomStatus::Failed("Some error".to_string()),
             },
         );
@@ -2069,6 +4082,181 @@ mod creating_room_tests {
         assert!(app.pending_rooms.len() == initial_count);
     }
 
+    #[test]
+    fn test_failed_deletion_moves_room_to_failed() {
+        use crate::git::Worktree;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let repo_root = temp_dir.path().to_path_buf();
+        let rooms_dir = repo_root.join(".rooms");
+        std::fs::create_dir_all(rooms_dir.join("quick-fox")).unwrap();
+
+        let config = Config::default();
+        let primary_worktree = repo_root.clone();
+        let mut app = App::new(repo_root, rooms_dir.clone(), config, primary_worktree, true);
+        app.worktrees = vec![Worktree {
+            path: rooms_dir.join("quick-fox"),
+            head: "abc123".to_string(),
+            branch: Some("quick-fox".to_string()),
+            is_main: false,
+            prunable: None,
+            locked: Some("in use".to_string()),
+        }];
+
+        app.transient.set_status("quick-fox", RoomStatus::Deleting);
+        app.rebuild_rooms();
+        assert_eq!(app.rooms[0].status, RoomStatus::Deleting);
+
+        app.finish_room_deletion(
+            "quick-fox",
+            Err(RemoveRoomError::WorktreeRemoval(
+                "fatal: cannot remove a locked working tree\n".to_string(),
+            )),
+            None,
+        );
+        let room = &app.rooms[0];
+        assert_eq!(app.room_section(room), RoomSection::Failed);
+        assert_eq!(
+            app.delete_failure_output(room),
+            Some("fatal: cannot remove a locked working tree")
+        );
+    }
+
+    #[test]
+    fn test_immediate_delete_confirms_unpushed_commits() {
+        use crate::git::{AheadBehind, Worktree};
+        use crate::room::DirtyStatus;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo_root = temp_dir.path().to_path_buf();
+        let rooms_dir = repo_root.join(".rooms");
+        std::fs::create_dir_all(rooms_dir.join("quick-fox")).unwrap();
+        let mut app = App::new(
+            repo_root.clone(),
+            rooms_dir.clone(),
+            Config::default(),
+            repo_root,
+            true,
+        );
+        app.worktrees = vec![Worktree {
+            path: rooms_dir.join("quick-fox"),
+            head: "abc123".to_string(),
+            branch: Some("quick-fox".to_string()),
+            is_main: false,
+            prunable: None,
+            locked: None,
+        }];
+        app.rebuild_rooms();
+        let check = |ahead| DeleteCheck {
+            dirty: DirtyStatus {
+                is_dirty: false,
+                modified_count: 0,
+                untracked_count: 0,
+                summary: String::new(),
+            },
+            upstream: None,
+            base_branch: Some("main".to_string()),
+            base: Some(AheadBehind { ahead, behind: 0 }),
+            stashes: 0,
+        };
+
+        app.confirm_room_deletion("quick-fox", Ok(check(2)), true);
+        assert!(app.confirm.awaits_name());
+        assert_eq!(app.rooms[0].status, RoomStatus::Ready);
+        app.confirm.cancel();
+
+        app.confirm_room_deletion("quick-fox", Ok(check(0)), true);
+        assert!(!app.confirm.is_active());
+        assert_eq!(app.rooms[0].status, RoomStatus::Deleting);
+    }
+
+    #[test]
+    fn test_deleted_room_is_listed_for_undo() {
+        use crate::room::{RemovedRoom, RoomSnapshot};
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let repo_root = temp_dir.path().to_path_buf();
+        let rooms_dir = repo_root.join(".rooms");
+        std::fs::create_dir_all(&rooms_dir).unwrap();
+
+        let config = Config::default();
+        let primary_worktree = repo_root.clone();
+        let mut app = App::new(repo_root, rooms_dir.clone(), config, primary_worktree, true);
+        app.update_state(|state| {
+            state.record_created(
+                "quick-fox",
+                Some("quick-fox"),
+                &rooms_dir.join("quick-fox"),
+                Some("main".to_string()),
+            )
+        });
+
+        app.finish_room_deletion(
+            "quick-fox",
+            Ok(RemovedRoom {
+                name: "quick-fox".to_string(),
+                post_hook_error: None,
+            }),
+            Some(RoomSnapshot {
+                branch: Some("quick-fox".to_string()),
+                head: "abc123".to_string(),
+                changes_ref: Some("refs/rooms/trash/quick-fox/1".to_string()),
+            }),
+        );
+        assert!(app.state.find_by_name("quick-fox").is_none());
+        let trashed = &app.trashed_rooms()[0];
+        assert_eq!(trashed.name, "quick-fox");
+        assert_eq!(trashed.base_branch.as_deref(), Some("main"));
+        assert!(
+            app.status_message
+                .as_deref()
+                .is_some_and(|message| message.contains("u to undo"))
+        );
+
+        // Deleted rooms are listed after the rooms
+        app.selected_index = app.rooms.len();
+        assert!(app.selected_room_info().is_none());
+        assert_eq!(app.selected_trashed_room().unwrap().name, "quick-fox");
+        assert_eq!(app.total_items(), app.rooms.len() + 1);
+
+        app.discard_trashed_room();
+        assert!(app.trashed_rooms().is_empty());
+        assert!(app.selected_trashed_room().is_none());
+    }
+
+    #[test]
+    fn test_bisect_reset_clears_progress() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo_root = temp_dir.path().to_path_buf();
+        let rooms_dir = repo_root.join(".rooms");
+        let mut app = App::new(
+            repo_root.clone(),
+            rooms_dir,
+            Config::default(),
+            repo_root,
+            true,
+        );
+        app.bisects
+            .insert("bisect-head".to_string(), BisectStatus::default());
+
+        app.finish_bisect_reset(
+            "bisect-head",
+            Err(CommandError::NotAGitRepo {
+                path: "bisect-head".to_string(),
+            }),
+        );
+        assert!(app.bisects.contains_key("bisect-head"));
+
+        app.finish_bisect_reset("bisect-head", Ok(()));
+        assert!(app.bisects.is_empty());
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Ended bisect in bisect-head")
+        );
+    }
+
     #[test]
     fn test_retry_pending_room_nonexistent() {
         use tempfile::TempDir;
@@ -2108,6 +4296,7 @@ mod creating_room_tests {
             name: Some("test-room".to_string()),
             branch: None,
             base_branch: None,
+            ..Default::default()
         };
 
         let result = app.prepare_room_create(options);
@@ -2117,11 +4306,37 @@ mod creating_room_tests {
         assert_eq!(options.name, Some("test-room".to_string()));
         assert_eq!(options.branch, Some("test-room".to_string())); // Defaults to name
         assert_eq!(pending.name, "test-room");
-        assert_eq!(pending.branch, "test-room");
+        assert_eq!(pending.branch.as_deref(), Some("test-room"));
         assert_eq!(pending.path, rooms_dir.join("test-room"));
         assert!(matches!(pending.status, PendingRoomStatus::Creating));
     }
 
+    #[test]
+    fn test_prepare_room_create_detached() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let repo_root = temp_dir.path().to_path_buf();
+        let rooms_dir = repo_root.join(".rooms");
+        std::fs::create_dir_all(&rooms_dir).unwrap();
+
+        let config = Config::default();
+        let primary_worktree = repo_root.clone();
+
+        let app = App::new(repo_root, rooms_dir, config, primary_worktree, true);
+
+        let options = CreateRoomOptions {
+            commit: Some("v1.2.0".to_string()),
+            ..Default::default()
+        };
+
+        let (options, pending) = app.prepare_room_create(options).unwrap();
+        assert_eq!(options.name, Some("v1-2-0".to_string()));
+        assert_eq!(options.branch, None);
+        assert_eq!(pending.branch, None);
+        assert_eq!(pending.commit, Some("v1.2.0".to_string()));
+    }
+
     #[test]
     fn test_prepare_room_create_sanitizes_name() {
         use tempfile::TempDir;
@@ -2140,6 +4355,7 @@ mod creating_room_tests {
             name: Some("Test Room!@#".to_string()),
             branch: None,
             base_branch: None,
+            ..Default::default()
         };
 
         let result = app.prepare_room_create(options);
@@ -2176,6 +4392,7 @@ mod creating_room_tests {
             name: Some("existing-room".to_string()),
             branch: None,
             base_branch: None,
+            ..Default::default()
         };
 
         let result = app.prepare_room_create(options);
@@ -2202,8 +4419,11 @@ mod creating_room_tests {
             "creating-room".to_string(),
             PendingRoom {
                 name: "creating-room".to_string(),
-                branch: "main".to_string(),
+                branch: Some("main".to_string()),
                 path: rooms_dir.join("creating-room"),
+                base_branch: None,
+                commit: None,
+                after_create: None,
                 status: PendingRoomStatus::Creating,
             },
         );
@@ -2212,6 +4432,7 @@ mod creating_room_tests {
             name: Some("creating-room".to_string()),
             branch: None,
             base_branch: None,
+            ..Default::default()
         };
 
         let result = app.prepare_room_create(options);
@@ -2237,6 +4458,7 @@ mod creating_room_tests {
             name: None,
             branch: None,
             base_branch: None,
+            ..Default::default()
         };
 
         let result = app.prepare_room_create(options);
@@ -2248,7 +4470,7 @@ mod creating_room_tests {
         // Auto-generated names should follow the pattern
         assert!(!name.is_empty());
         assert_eq!(pending.name, name);
-        assert_eq!(pending.branch, name);
+        assert_eq!(pending.branch, Some(name));
     }
 
     #[test]
@@ -2269,6 +4491,7 @@ mod creating_room_tests {
             name: Some("test-room".to_string()),
             branch: Some("feature-branch".to_string()),
             base_branch: None,
+            ..Default::default()
         };
 
         let result = app.prepare_room_create(options);
@@ -2278,7 +4501,46 @@ mod creating_room_tests {
         assert_eq!(options.name, Some("test-room".to_string()));
         assert_eq!(options.branch, Some("feature-branch".to_string()));
         assert_eq!(pending.name, "test-room");
-        assert_eq!(pending.branch, "feature-branch");
+        assert_eq!(pending.branch.as_deref(), Some("feature-branch"));
+    }
+
+    #[test]
+    fn test_prepare_room_create_keeps_branch_name() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let repo_root = temp_dir.path().to_path_buf();
+        let rooms_dir = repo_root.join(".rooms");
+
+        let mut app = App::new(
+            repo_root.clone(),
+            rooms_dir.clone(),
+            Config::default(),
+            repo_root,
+            true,
+        );
+        app.rooms
+            .push(make_room("feature-login", RoomStatus::Ready));
+
+        let options = CreateRoomOptions {
+            branch: Some("feature/Login".to_string()),
+            ..Default::default()
+        };
+        let (options, pending) = app.prepare_room_create(options).unwrap();
+        assert_eq!(options.branch, Some("feature/Login".to_string()));
+        assert_eq!(pending.name, "feature-login-2");
+        assert_eq!(pending.path, rooms_dir.join("feature-login-2"));
+
+        let options = CreateRoomOptions {
+            branch: Some("feature..login".to_string()),
+            ..Default::default()
+        };
+        app.finish_branch_name_check(options, Ok(None));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Invalid branch name: 'feature..login'")
+        );
+        assert!(app.pending_rooms.is_empty());
     }
 }
 
@@ -2395,4 +4657,45 @@ mod tests {
         let normalized = normalize_path_for_compare(&path2);
         assert!(is_primary_worktree(&path1, None, &normalized));
     }
+
+    #[test]
+    fn test_hook_command_line() {
+        assert_eq!(hook_command_line("make"), "make\n");
+        assert_eq!(hook_command_line("make\n"), "make\n");
+    }
+
+    #[test]
+    fn test_restorable_rooms_skips_missing_and_broken() {
+        let temp = tempfile::tempdir().unwrap();
+        let room = |name: &str, path: PathBuf, is_prunable: bool| RoomInfo {
+            name: name.to_string(),
+            branch: Some(name.to_string()),
+            head: None,
+            path,
+            status: RoomStatus::Ready,
+            is_prunable,
+            last_error: None,
+            is_primary: false,
+        };
+        let rooms = vec![
+            room("alive", temp.path().to_path_buf(), false),
+            room("gone", temp.path().join("gone"), false),
+            room("prunable", temp.path().to_path_buf(), true),
+        ];
+        let layout = SessionLayout {
+            active_rooms: vec![
+                "prunable".to_string(),
+                "deleted".to_string(),
+                "gone".to_string(),
+                "alive".to_string(),
+            ],
+            ..SessionLayout::default()
+        };
+
+        let restored: Vec<&str> = restorable_rooms(&layout, &rooms)
+            .into_iter()
+            .map(|room| room.name.as_str())
+            .collect();
+        assert_eq!(restored, vec!["alive"]);
+    }
 }
@@ -0,0 +1,189 @@
+//! Fuzzy-filterable branch list used by the create-room prompt.
+
+use crate::git::Branch;
+
+use super::prompt::TextInput;
+
+/// A text input over a list of branches.
+///
+/// Typing filters the list; the typed text is used as is unless a branch
+/// is highlighted with the arrow keys.
+#[derive(Debug, Clone)]
+pub struct BranchPicker {
+    /// Filter text, also the value used when nothing is highlighted.
+    pub input: TextInput,
+    branches: Vec<Branch>,
+    /// Index into `matches()` of the highlighted branch.
+    selected: Option<usize>,
+}
+
+impl BranchPicker {
+    pub fn new(placeholder: impl Into<String>, branches: Vec<Branch>) -> Self {
+        Self {
+            input: TextInput::new(placeholder),
+            branches,
+            selected: None,
+        }
+    }
+
+    /// Replace the branches, e.g. once they have been listed.
+    pub fn set_branches(&mut self, branches: Vec<Branch>) {
+        self.branches = branches;
+        self.selected = None;
+    }
+
+    /// All branches, in their original order.
+    pub fn branches(&self) -> &[Branch] {
+        &self.branches
+    }
+
+    /// Branches matching the filter, best matches first.
+    pub fn matches(&self) -> Vec<&Branch> {
+        let query = self.input.value.to_lowercase();
+        let mut scored: Vec<(usize, usize, &Branch)> = self
+            .branches
+            .iter()
+            .enumerate()
+            .filter_map(|(index, branch)| {
+                fuzzy_score(&query, &branch.name.to_lowercase()).map(|score| (score, index, branch))
+            })
+            .collect();
+        // Ties keep the original (most recent first) order.
+        scored.sort_by_key(|(score, index, _)| (*score, *index));
+        scored.into_iter().map(|(_, _, branch)| branch).collect()
+    }
+
+    /// Index into `matches()` of the highlighted branch.
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Highlight the next match, starting with the first.
+    pub fn select_next(&mut self) {
+        let count = self.matches().len();
+        if count == 0 {
+            return;
+        }
+        self.selected = Some(self.selected.map_or(0, |index| (index + 1).min(count - 1)));
+    }
+
+    /// Highlight the previous match; moving up from the first clears it.
+    pub fn select_previous(&mut self) {
+        self.selected = match self.selected {
+            Some(0) | None => None,
+            Some(index) => Some(index - 1),
+        };
+    }
+
+    /// Clear the highlight after the filter changed.
+    pub fn filter_changed(&mut self) {
+        self.selected = None;
+    }
+
+    /// The highlighted branch, if any.
+    pub fn selected_branch(&self) -> Option<&Branch> {
+        self.selected
+            .and_then(|index| self.matches().get(index).copied())
+    }
+}
+
+/// Score a case-folded subsequence match of `query` in `candidate`; lower is better.
+///
+/// Contiguous matches beat scattered ones, and earlier matches beat later ones.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<usize> {
+    if query.is_empty() {
+        return Some(0);
+    }
+    if let Some(position) = candidate.find(query) {
+        return Some(position);
+    }
+
+    let mut chars = candidate.char_indices();
+    let mut first = None;
+    let mut last = 0;
+    for wanted in query.chars() {
+        let (index, _) = chars.find(|(_, c)| *c == wanted)?;
+        first.get_or_insert(index);
+        last = index;
+    }
+    // Scattered matches rank after every contiguous one.
+    Some(candidate.len() + last - first.unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn branch(name: &str) -> Branch {
+        Branch {
+            name: name.to_string(),
+            remote: name.split_once('/').map(|(remote, _)| remote.to_string()),
+            committed_at: None,
+            worktree: None,
+        }
+    }
+
+    fn picker(query: &str) -> BranchPicker {
+        let mut picker = BranchPicker::new(
+            "",
+            vec![
+                branch("main"),
+                branch("origin/fix-login"),
+                branch("feature-flags"),
+            ],
+        );
+        for c in query.chars() {
+            picker.input.insert(c);
+        }
+        picker
+    }
+
+    fn names(picker: &BranchPicker) -> Vec<&str> {
+        picker
+            .matches()
+            .iter()
+            .map(|branch| branch.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert_eq!(fuzzy_score("", "main"), Some(0));
+        assert_eq!(fuzzy_score("ain", "main"), Some(1));
+        assert!(fuzzy_score("fl", "feature-flags") < fuzzy_score("fl", "fix-login"));
+        assert_eq!(fuzzy_score("xyz", "main"), None);
+    }
+
+    #[test]
+    fn test_matches_filters_and_ranks() {
+        assert_eq!(
+            names(&picker("")),
+            vec!["main", "origin/fix-login", "feature-flags"]
+        );
+        assert_eq!(
+            names(&picker("FL")),
+            vec!["feature-flags", "origin/fix-login"]
+        );
+        assert!(names(&picker("zzz")).is_empty());
+    }
+
+    #[test]
+    fn test_selection() {
+        let mut picker = picker("fl");
+        assert!(picker.selected_branch().is_none());
+
+        picker.select_next();
+        picker.select_next();
+        picker.select_next();
+        assert_eq!(picker.selected_branch().unwrap().name, "origin/fix-login");
+
+        picker.select_previous();
+        assert_eq!(picker.selected_branch().unwrap().name, "feature-flags");
+        picker.select_previous();
+        assert!(picker.selected_branch().is_none());
+
+        picker.select_next();
+        picker.filter_changed();
+        assert!(picker.selected_branch().is_none());
+    }
+}
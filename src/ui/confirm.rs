@@ -4,7 +4,9 @@ use ratatui::style::{Color, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 
-use crate::room::DirtyStatus;
+use crate::room::DeleteCheck;
+
+use super::prompt::{TextInput, render_input};
 
 /// State for a confirmation dialog.
 #[derive(Debug, Clone, Default)]
@@ -18,10 +20,34 @@ pub enum ConfirmState {
         room_name: String,
         room_path: String,
         branch: String,
-        dirty_status: Option<DirtyStatus>,
+        /// What the deletion would discard, if the check succeeded.
+        check: Option<Box<DeleteCheck>>,
+        /// Lock reason if the worktree is locked (possibly empty); the
+        /// deletion can't be confirmed while it is set.
+        locked: Option<String>,
+        /// Set when the room has unpushed commits; the room name must be typed to confirm.
+        name_input: Option<TextInput>,
         /// Current selection: true = confirm (delete), false = cancel
         selected_confirm: bool,
     },
+
+    /// Confirming closing a tab whose process is still running.
+    CloseTab {
+        room_name: String,
+        tab_name: String,
+        tab_index: usize,
+        /// Current selection: true = confirm (close), false = cancel
+        selected_confirm: bool,
+    },
+}
+
+/// What a confirmed dialog asked for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Confirmed {
+    /// Delete the room with this name.
+    DeleteRoom(String),
+    /// Close the tab at `tab_index` in the room, killing its process.
+    CloseTab { room_name: String, tab_index: usize },
 }
 
 impl ConfirmState {
@@ -30,48 +56,139 @@ impl ConfirmState {
         room_name: String,
         room_path: String,
         branch: String,
-        dirty_status: Option<DirtyStatus>,
+        check: Option<DeleteCheck>,
+        locked: Option<String>,
     ) -> Self {
+        let name_input = (locked.is_none()
+            && check
+                .as_ref()
+                .is_some_and(|check| check.unpushed_commits() > 0))
+        .then(|| TextInput::new("room name"));
         Self::DeleteRoom {
             room_name,
             room_path,
             branch,
-            dirty_status,
+            check: check.map(Box::new),
+            locked,
+            name_input,
             selected_confirm: false, // Default to cancel for safety
         }
     }
 
+    /// Start confirming closing a tab that is still running.
+    pub fn start_close_tab(room_name: String, tab_name: String, tab_index: usize) -> Self {
+        Self::CloseTab {
+            room_name,
+            tab_name,
+            tab_index,
+            selected_confirm: false,
+        }
+    }
+
     /// Check if a confirmation dialog is active.
     pub fn is_active(&self) -> bool {
         !matches!(self, Self::None)
     }
 
+    /// The room name input, if the deletion must be confirmed by typing it.
+    pub fn name_input(&mut self) -> Option<&mut TextInput> {
+        match self {
+            Self::DeleteRoom { name_input, .. } => name_input.as_mut(),
+            Self::CloseTab { .. } | Self::None => None,
+        }
+    }
+
+    /// Whether the worktree is locked, so the deletion can't be confirmed.
+    pub fn is_locked(&self) -> bool {
+        matches!(
+            self,
+            Self::DeleteRoom {
+                locked: Some(_),
+                ..
+            }
+        )
+    }
+
+    /// Whether the room name still has to be typed before confirming.
+    pub fn awaits_name(&self) -> bool {
+        matches!(
+            self,
+            Self::DeleteRoom {
+                room_name,
+                name_input: Some(input),
+                ..
+            } if input.value != *room_name
+        )
+    }
+
     /// Toggle the selection between confirm and cancel.
     pub fn toggle_selection(&mut self) {
         if let Self::DeleteRoom {
             selected_confirm, ..
+        }
+        | Self::CloseTab {
+            selected_confirm, ..
         } = self
         {
             *selected_confirm = !*selected_confirm;
         }
     }
 
-    /// Confirm the action. Returns the room name if confirmed, None if cancelled.
-    pub fn confirm(&mut self) -> Option<String> {
+    /// Confirm the action. Returns what to do if confirmed, None if cancelled.
+    ///
+    /// While the room name still has to be typed or the worktree is locked,
+    /// the dialog stays open and nothing is returned. Once the name is typed,
+    /// the buttons are ignored.
+    pub fn confirm(&mut self) -> Option<Confirmed> {
+        if self.awaits_name() || self.is_locked() {
+            return None;
+        }
         match std::mem::take(self) {
             Self::DeleteRoom {
+                room_name,
+                name_input: Some(_),
+                ..
+            }
+            | Self::DeleteRoom {
                 room_name,
                 selected_confirm: true,
                 ..
-            } => Some(room_name),
+            } => Some(Confirmed::DeleteRoom(room_name)),
+            Self::CloseTab {
+                room_name,
+                tab_index,
+                selected_confirm: true,
+                ..
+            } => Some(Confirmed::CloseTab {
+                room_name,
+                tab_index,
+            }),
             Self::DeleteRoom {
                 selected_confirm: false,
                 ..
+            }
+            | Self::CloseTab {
+                selected_confirm: false,
+                ..
             } => None,
             Self::None => None,
         }
     }
 
+    /// Select confirm and confirm, as the `y` shortcut does.
+    pub fn accept(&mut self) -> Option<Confirmed> {
+        if let Self::DeleteRoom {
+            selected_confirm, ..
+        }
+        | Self::CloseTab {
+            selected_confirm, ..
+        } = self
+        {
+            *selected_confirm = true;
+        }
+        self.confirm()
+    }
+
     /// Cancel the confirmation dialog.
     pub fn cancel(&mut self) {
         *self = Self::None;
@@ -80,26 +197,33 @@ impl ConfirmState {
 
 /// Render the confirmation dialog overlay.
 pub fn render_confirm(frame: &mut Frame, area: Rect, confirm: &ConfirmState) {
-    let (title, room_name, room_path, branch, dirty_status, selected_confirm) = match confirm {
-        ConfirmState::None => return,
-        ConfirmState::DeleteRoom {
-            room_name,
-            room_path,
-            branch,
-            dirty_status,
-            selected_confirm,
-        } => (
-            "Delete Room",
-            room_name,
-            room_path,
-            branch,
-            dirty_status,
-            *selected_confirm,
-        ),
+    if let ConfirmState::CloseTab {
+        room_name,
+        tab_name,
+        selected_confirm,
+        ..
+    } = confirm
+    {
+        render_close_tab(frame, area, room_name, tab_name, *selected_confirm);
+        return;
+    }
+    let ConfirmState::DeleteRoom {
+        room_name,
+        room_path,
+        branch,
+        check,
+        locked,
+        name_input,
+        selected_confirm,
+    } = confirm
+    else {
+        return;
     };
+    let title = "Delete Room";
+    let selected_confirm = *selected_confirm;
 
     // Center the dialog
-    let popup_area = centered_rect(60, 50, area);
+    let popup_area = centered_rect(60, 60, area);
 
     // Clear the area behind the popup
     frame.render_widget(Clear, popup_area);
@@ -130,8 +254,69 @@ pub fn render_confirm(frame: &mut Frame, area: Rect, confirm: &ConfirmState) {
         Line::from(""),
     ];
 
+    if let Some(reason) = locked {
+        let reason = if reason.is_empty() {
+            String::new()
+        } else {
+            format!(" ({reason})")
+        };
+        lines.push(Line::from(vec![Span::styled(
+            format!("WARNING: Worktree is locked{reason}!"),
+            Style::default().fg(Color::Red),
+        )]));
+        lines.push(Line::from(vec![Span::styled(
+            "  It can't be deleted until it's unlocked with git worktree unlock.",
+            Style::default().fg(Color::Gray),
+        )]));
+        lines.push(Line::from(""));
+    }
+
+    if let Some(check) = check {
+        let lines_before = lines.len();
+        let unpushed = check.unpushed_commits();
+        if unpushed > 0 {
+            let detail = match (&check.upstream, &check.base_branch) {
+                (Some(_), _) => "not pushed to the upstream".to_string(),
+                (None, Some(base)) => format!("not on {base} and never pushed"),
+                (None, None) => "never pushed".to_string(),
+            };
+            lines.push(Line::from(vec![Span::styled(
+                format!("WARNING: {}!", plural(unpushed, "unpushed commit")),
+                Style::default().fg(Color::Yellow),
+            )]));
+            lines.push(Line::from(vec![Span::styled(
+                format!("  {detail}; they stay on the branch only."),
+                Style::default().fg(Color::Gray),
+            )]));
+        }
+        if let (Some(_), Some(base), Some(base_branch)) =
+            (&check.upstream, &check.base, &check.base_branch)
+            && base.ahead > 0
+        {
+            lines.push(Line::from(vec![Span::styled(
+                format!(
+                    "{} not merged into {base_branch}",
+                    plural(base.ahead, "commit")
+                ),
+                Style::default().fg(Color::Gray),
+            )]));
+        }
+        if check.stashes > 0 {
+            lines.push(Line::from(vec![Span::styled(
+                format!(
+                    "{} made on this branch (kept)",
+                    plural(check.stashes, "stash entry")
+                ),
+                Style::default().fg(Color::Gray),
+            )]));
+        }
+        if lines.len() > lines_before {
+            lines.push(Line::from(""));
+        }
+    }
+
     // Add dirty warning if applicable
-    if let Some(status) = dirty_status
+    if let Some(status) = check.as_ref().map(|check| &check.dirty)
         && status.is_dirty
     {
         lines.push(Line::from(vec![Span::styled(
@@ -159,6 +344,10 @@ pub fn render_confirm(frame: &mut Frame, area: Rect, confirm: &ConfirmState) {
                 )]));
             }
         }
+        lines.push(Line::from(vec![Span::styled(
+            "  Changes are saved and can be restored with u.",
+            Style::default().fg(Color::Gray),
+        )]));
         lines.push(Line::from(""));
     }
 
@@ -167,6 +356,51 @@ pub fn render_confirm(frame: &mut Frame, area: Rect, confirm: &ConfirmState) {
         Style::default().fg(Color::Gray),
     )]));
     lines.push(Line::from(""));
+
+    if let Some(input) = name_input {
+        lines.push(Line::from(format!(
+            "Type the room name ({room_name}) to delete it:"
+        )));
+
+        // Layout: content, name input and help
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(3),
+                Constraint::Length(2),
+            ])
+            .split(inner);
+        frame.render_widget(Paragraph::new(lines).alignment(Alignment::Left), chunks[0]);
+        render_input(frame, chunks[1], input);
+        let help = Paragraph::new(Line::from(vec![
+            Span::styled("Enter", Style::default().fg(Color::Yellow)),
+            Span::raw(" delete  "),
+            Span::styled("Esc", Style::default().fg(Color::Yellow)),
+            Span::raw(" cancel"),
+        ]))
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(help, chunks[2]);
+        return;
+    }
+
+    if locked.is_some() {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(2)])
+            .split(inner);
+        frame.render_widget(Paragraph::new(lines).alignment(Alignment::Left), chunks[0]);
+        let help = Paragraph::new(Line::from(vec![
+            Span::styled("Esc", Style::default().fg(Color::Yellow)),
+            Span::raw(" close"),
+        ]))
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(help, chunks[1]);
+        return;
+    }
+
     lines.push(Line::from("Are you sure you want to delete this room?"));
     lines.push(Line::from(""));
 
@@ -180,7 +414,50 @@ pub fn render_confirm(frame: &mut Frame, area: Rect, confirm: &ConfirmState) {
     let content = Paragraph::new(lines).alignment(Alignment::Left);
     frame.render_widget(content, chunks[0]);
 
-    // Buttons
+    render_buttons(frame, chunks[1], "Delete", selected_confirm);
+}
+
+/// Render the dialog asking to close a tab that is still running.
+fn render_close_tab(
+    frame: &mut Frame,
+    area: Rect,
+    room_name: &str,
+    tab_name: &str,
+    selected_confirm: bool,
+) {
+    let popup_area = centered_rect(50, 30, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(" Close Tab ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Tab ", Style::default().fg(Color::Gray)),
+            Span::styled(tab_name, Style::default().fg(Color::White)),
+            Span::styled(" in ", Style::default().fg(Color::Gray)),
+            Span::styled(room_name, Style::default().fg(Color::White)),
+            Span::styled(" is still running.", Style::default().fg(Color::Gray)),
+        ]),
+        Line::from(""),
+        Line::from("Close it and kill its process?"),
+    ];
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(3)])
+        .split(inner);
+    frame.render_widget(Paragraph::new(lines).alignment(Alignment::Left), chunks[0]);
+    render_buttons(frame, chunks[1], "Close", selected_confirm);
+}
+
+/// Render the Cancel button and a confirm button labelled `action`.
+fn render_buttons(frame: &mut Frame, area: Rect, action: &str, selected_confirm: bool) {
     let button_cancel = if selected_confirm {
         Span::styled("  Cancel  ", Style::default().fg(Color::Gray))
     } else {
@@ -190,23 +467,32 @@ pub fn render_confirm(frame: &mut Frame, area: Rect, confirm: &ConfirmState) {
         )
     };
 
-    let button_delete = if selected_confirm {
+    let button_confirm = if selected_confirm {
         Span::styled(
-            "[ Delete ]",
+            format!("[ {action} ]"),
             Style::default().fg(Color::White).bg(Color::Red),
         )
     } else {
-        Span::styled("  Delete  ", Style::default().fg(Color::Red))
+        Span::styled(format!("  {action}  "), Style::default().fg(Color::Red))
     };
 
     let buttons = Paragraph::new(Line::from(vec![
         button_cancel,
         Span::raw("     "),
-        button_delete,
+        button_confirm,
     ]))
     .alignment(Alignment::Center);
 
-    frame.render_widget(buttons, chunks[1]);
+    frame.render_widget(buttons, area);
+}
+
+/// `count` followed by `noun`, pluralized, e.g. `2 unpushed commits`.
+fn plural(count: usize, noun: &str) -> String {
+    match (count, noun.strip_suffix('y')) {
+        (1, _) => format!("1 {noun}"),
+        (_, Some(stem)) => format!("{count} {stem}ies"),
+        (_, None) => format!("{count} {noun}s"),
+    }
 }
 
 /// Create a centered rectangle with the given percentage width and height.
@@ -241,6 +527,7 @@ mod tests {
             "/path/to/room".to_string(),
             "test-branch".to_string(),
             None,
+            None,
         );
 
         // Default to cancel (false)
@@ -268,12 +555,13 @@ mod tests {
             "/path/to/room".to_string(),
             "test-branch".to_string(),
             None,
+            None,
         );
 
         state.toggle_selection(); // Select confirm
         let result = state.confirm();
 
-        assert_eq!(result, Some("test-room".to_string()));
+        assert_eq!(result, Some(Confirmed::DeleteRoom("test-room".to_string())));
         assert!(!state.is_active());
     }
 
@@ -284,6 +572,7 @@ mod tests {
             "/path/to/room".to_string(),
             "test-branch".to_string(),
             None,
+            None,
         );
 
         // Don't toggle, keep cancel selected
@@ -293,6 +582,92 @@ mod tests {
         assert!(!state.is_active());
     }
 
+    #[test]
+    fn test_unpushed_commits_require_typing_the_name() {
+        use crate::git::AheadBehind;
+        use crate::room::DirtyStatus;
+
+        let check = DeleteCheck {
+            dirty: DirtyStatus {
+                is_dirty: false,
+                modified_count: 0,
+                untracked_count: 0,
+                summary: String::new(),
+            },
+            upstream: None,
+            base_branch: Some("main".to_string()),
+            base: Some(AheadBehind {
+                ahead: 2,
+                behind: 0,
+            }),
+            stashes: 0,
+        };
+        let mut state = ConfirmState::start_delete(
+            "test-room".to_string(),
+            "/path/to/room".to_string(),
+            "test-branch".to_string(),
+            Some(check),
+            None,
+        );
+
+        // The buttons don't matter until the name is typed
+        state.toggle_selection();
+        assert!(state.awaits_name());
+        assert_eq!(state.confirm(), None);
+        assert!(state.is_active());
+
+        for c in "test-room".chars() {
+            state.name_input().unwrap().insert(c);
+        }
+        assert!(!state.awaits_name());
+        assert_eq!(
+            state.confirm(),
+            Some(Confirmed::DeleteRoom("test-room".to_string()))
+        );
+        assert!(!state.is_active());
+    }
+
+    #[test]
+    fn test_locked_worktree_blocks_confirmation() {
+        let mut state = ConfirmState::start_delete(
+            "test-room".to_string(),
+            "/path/to/room".to_string(),
+            "test-branch".to_string(),
+            None,
+            Some("in use".to_string()),
+        );
+
+        assert!(state.is_locked());
+        assert_eq!(state.accept(), None);
+        assert!(state.is_active());
+    }
+
+    #[test]
+    fn test_close_tab_confirmation() {
+        let mut state =
+            ConfirmState::start_close_tab("test-room".to_string(), "server".to_string(), 1);
+        assert!(state.name_input().is_none());
+        assert_eq!(state.confirm(), None);
+        assert!(!state.is_active());
+
+        let mut state =
+            ConfirmState::start_close_tab("test-room".to_string(), "server".to_string(), 1);
+        assert_eq!(
+            state.accept(),
+            Some(Confirmed::CloseTab {
+                room_name: "test-room".to_string(),
+                tab_index: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn test_plural() {
+        assert_eq!(plural(1, "unpushed commit"), "1 unpushed commit");
+        assert_eq!(plural(2, "commit"), "2 commits");
+        assert_eq!(plural(3, "stash entry"), "3 stash entries");
+    }
+
     #[test]
     fn test_confirm_state_cancel_method() {
         let mut state = ConfirmState::start_delete(
@@ -300,6 +675,7 @@ mod tests {
             "/path/to/room".to_string(),
             "test-branch".to_string(),
             None,
+            None,
         );
 
         state.cancel();
@@ -7,7 +7,7 @@ use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 /// Render the help overlay.
 pub fn render_help(frame: &mut Frame, area: Rect) {
     // Center the help popup
-    let popup_area = centered_rect(60, 70, area);
+    let popup_area = centered_rect(60, 90, area);
 
     // Clear the area behind the popup
     frame.render_widget(Clear, popup_area);
@@ -57,6 +57,22 @@ pub fn render_help(frame: &mut Frame, area: Rect) {
             Span::styled("  A       ", Style::default().fg(Color::Yellow)),
             Span::raw("Add room (quick)"),
         ]),
+        Line::from(vec![
+            Span::styled("  C       ", Style::default().fg(Color::Yellow)),
+            Span::raw("Add room at a commit (detached)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  B       ", Style::default().fg(Color::Yellow)),
+            Span::raw("Add bisect room (bad / good commits)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  g/b/S   ", Style::default().fg(Color::Yellow)),
+            Span::raw("Bisect: mark good / bad / skip"),
+        ]),
+        Line::from(vec![
+            Span::styled("  G       ", Style::default().fg(Color::Yellow)),
+            Span::raw("Bisect: end (git bisect reset)"),
+        ]),
         Line::from(vec![
             Span::styled("  d       ", Style::default().fg(Color::Yellow)),
             Span::raw("Delete room"),
@@ -73,6 +89,46 @@ pub fn render_help(frame: &mut Frame, area: Rect) {
             Span::styled("  R       ", Style::default().fg(Color::Yellow)),
             Span::raw("Refresh room list"),
         ]),
+        Line::from(vec![
+            Span::styled("  c       ", Style::default().fg(Color::Yellow)),
+            Span::raw("Cancel queued room job"),
+        ]),
+        Line::from(vec![
+            Span::styled("  u       ", Style::default().fg(Color::Yellow)),
+            Span::raw("Restore deleted room"),
+        ]),
+        Line::from(vec![
+            Span::styled("  o       ", Style::default().fg(Color::Yellow)),
+            Span::raw("Cycle sort order"),
+        ]),
+        Line::from(vec![
+            Span::styled("  n       ", Style::default().fg(Color::Yellow)),
+            Span::raw("Edit room notes"),
+        ]),
+        Line::from(vec![
+            Span::styled("  t / T   ", Style::default().fg(Color::Yellow)),
+            Span::raw("New / rename terminal tab"),
+        ]),
+        Line::from(vec![
+            Span::styled("  x       ", Style::default().fg(Color::Yellow)),
+            Span::raw("Close terminal tab"),
+        ]),
+        Line::from(vec![
+            Span::styled("  [ / ]   ", Style::default().fg(Color::Yellow)),
+            Span::raw("Previous / next tab"),
+        ]),
+        Line::from(vec![
+            Span::styled("  | / -   ", Style::default().fg(Color::Yellow)),
+            Span::raw("Split side by side / stacked"),
+        ]),
+        Line::from(vec![
+            Span::styled("  X       ", Style::default().fg(Color::Yellow)),
+            Span::raw("Close pane"),
+        ]),
+        Line::from(vec![
+            Span::styled("  s       ", Style::default().fg(Color::Yellow)),
+            Span::raw("Restart services"),
+        ]),
         Line::from(vec![
             Span::styled("  Ctrl+b  ", Style::default().fg(Color::Yellow)),
             Span::raw("Toggle sidebar visibility"),
@@ -86,6 +142,14 @@ pub fn render_help(frame: &mut Frame, area: Rect) {
             Span::styled("  Ctrl+b  ", Style::default().fg(Color::Yellow)),
             Span::raw("Return to sidebar"),
         ]),
+        Line::from(vec![
+            Span::styled("  Alt+1-9 ", Style::default().fg(Color::Yellow)),
+            Span::raw("Switch terminal tab"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Alt+←/→ ", Style::default().fg(Color::Yellow)),
+            Span::raw("Move between panes"),
+        ]),
         Line::from(""),
         Line::from(Span::styled(
             "Press ? or Esc to close",
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use ratatui::Frame;
 use ratatui::layout::{Alignment, Rect};
 use ratatui::style::{Color, Modifier, Style};
@@ -5,6 +6,8 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 
 use super::app::{App, Focus, PendingRoomStatus, RoomSection};
+use super::panes::Pane;
+use crate::room::RoomStatus;
 use crate::terminal::debug_log;
 
 // UI message constants
@@ -12,6 +15,86 @@ const PRUNABLE_WORKTREE_MESSAGE: &str = "Worktree is prunable - Press Enter to p
 const FAILED_WORKTREE_DEFAULT_MESSAGE: &str =
     "Worktree is in a failed state. Check logs for details.";
 
+/// Format a timestamp relative to `now`, e.g. "3 days ago".
+pub(super) fn format_relative_time(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let elapsed = now.signed_duration_since(time);
+    let (count, unit) = if elapsed.num_days() > 0 {
+        (elapsed.num_days(), "day")
+    } else if elapsed.num_hours() > 0 {
+        (elapsed.num_hours(), "hour")
+    } else if elapsed.num_minutes() > 0 {
+        (elapsed.num_minutes(), "minute")
+    } else {
+        return "just now".to_string();
+    };
+    let plural = if count == 1 { "" } else { "s" };
+    format!("{} {}{} ago", count, unit, plural)
+}
+
+/// Render a vt100 screen into `inner`, cell by cell.
+fn render_screen(
+    frame: &mut Frame,
+    inner: Rect,
+    screen: &vt100::Screen,
+    is_selected: impl Fn(u16, u16) -> bool,
+) {
+    let (screen_rows, screen_cols) = screen.size();
+
+    // Log if there's a mismatch between screen size and rendered area
+    if debug_log::is_enabled() && (screen_cols != inner.width || screen_rows != inner.height) {
+        debug_log::log_debug(&format!(
+            "SIZE MISMATCH: screen={}x{} inner={}x{}",
+            screen_cols, screen_rows, inner.width, inner.height
+        ));
+    }
+
+    // Render cells directly to frame buffer
+    let buf = frame.buffer_mut();
+    for y in 0..inner.height as usize {
+        for x in 0..inner.width as usize {
+            let buf_x = inner.x + x as u16;
+            let buf_y = inner.y + y as u16;
+
+            // Get cell from vt100 screen if within bounds
+            if (y as u16) < screen_rows && (x as u16) < screen_cols {
+                let cell = screen.cell(y as u16, x as u16);
+                if let Some(cell) = cell {
+                    let c = cell.contents().chars().next().unwrap_or(' ');
+                    let mut fg = vt100_color_to_ratatui(cell.fgcolor(), true);
+                    let mut bg = vt100_color_to_ratatui(cell.bgcolor(), false);
+
+                    let style = if is_selected(y as u16, x as u16) {
+                        Style::default().fg(Color::White).bg(Color::DarkGray)
+                    } else if cell.inverse() {
+                        // When fg/bg are defaults, swapping does nothing; use REVERSED to show cursor.
+                        if fg == Color::Reset && bg == Color::Reset {
+                            Style::default().add_modifier(Modifier::REVERSED)
+                        } else {
+                            std::mem::swap(&mut fg, &mut bg);
+                            Style::default().fg(fg).bg(bg)
+                        }
+                    } else {
+                        Style::default().fg(fg).bg(bg)
+                    };
+
+                    buf[(buf_x, buf_y)].set_char(c).set_style(style);
+                } else {
+                    buf[(buf_x, buf_y)]
+                        .set_char(' ')
+                        .set_fg(Color::Reset)
+                        .set_bg(Color::Reset);
+                }
+            } else {
+                // Outside screen buffer - clear
+                buf[(buf_x, buf_y)]
+                    .set_char(' ')
+                    .set_fg(Color::Reset)
+                    .set_bg(Color::Reset);
+            }
+        }
+    }
+}
+
 /// Convert vt100 color to ratatui Color.
 fn vt100_color_to_ratatui(color: vt100::Color, is_foreground: bool) -> Color {
     match color {
@@ -67,6 +150,58 @@ fn indexed_to_color(idx: u8) -> Color {
 
 /// Render the main scene panel (terminal area).
 pub fn render_main_scene(frame: &mut Frame, area: Rect, app: &App) {
+    if !app.panes.is_split() {
+        render_current_pane(frame, area, app);
+        return;
+    }
+
+    let rects = app.panes.rects(area);
+    for (index, (pane, rect)) in app.panes.iter().zip(rects).enumerate() {
+        if index == app.panes.focused_index() {
+            render_current_pane(frame, rect, app);
+        } else {
+            render_pinned_pane(frame, rect, app, pane);
+        }
+    }
+}
+
+/// Render a pane that isn't focused: the tab it was pinned to when focus left it.
+fn render_pinned_pane(frame: &mut Frame, area: Rect, app: &App, pane: &Pane) {
+    let tab = app
+        .sessions
+        .get(&pane.room_name)
+        .and_then(|tabs| tabs.get(pane.tab));
+
+    let mut title = format!(" {} ", pane.room_name);
+    if let Some(tab) = tab {
+        title.push_str(&format!("· {} ", tab.name));
+        if let Some(code) = tab.session.exit_code() {
+            title.push_str(&format!("[exited {}] ", code));
+        }
+    }
+
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    match tab {
+        Some(tab) => render_screen(frame, inner, tab.session.screen(), |_, _| false),
+        None => {
+            let paragraph = Paragraph::new(Line::from(Span::styled(
+                "Terminal closed",
+                Style::default().fg(Color::DarkGray),
+            )))
+            .alignment(Alignment::Center);
+            frame.render_widget(paragraph, inner);
+        }
+    }
+}
+
+/// Render the pane showing the selected room's active tab.
+fn render_current_pane(frame: &mut Frame, area: Rect, app: &App) {
     let is_focused = app.focus == Focus::MainScene;
 
     let border_style = if is_focused {
@@ -76,13 +211,31 @@ pub fn render_main_scene(frame: &mut Frame, area: Rect, app: &App) {
     };
 
     let title = if let Some(room) = app.selected_room_info() {
-        if app.scrollback_offset > 0 {
-            format!(" {} [↑{}] ", room.name, app.scrollback_offset)
-        } else {
-            format!(" {} ", room.name)
+        let mut spans = vec![Span::raw(format!(" {} ", room.name))];
+        if let Some(tabs) = app.room_tabs(room)
+            && tabs.len() > 1
+        {
+            for (index, tab) in tabs.iter().enumerate() {
+                let style = if index == tabs.active_index() {
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                };
+                spans.push(Span::styled(format!(" {}:{} ", index + 1, tab.name), style));
+            }
+            spans.push(Span::raw(" "));
         }
+        if let Some(code) = app.session_exit_code(room) {
+            spans.push(Span::raw(format!("[exited {} - Enter to restart] ", code)));
+        } else if app.scrollback_offset > 0 {
+            spans.push(Span::raw(format!("[↑{}] ", app.scrollback_offset)));
+        }
+        Line::from(spans)
     } else {
-        " Terminal ".to_string()
+        Line::from(" Terminal ")
     };
 
     let block = Block::default()
@@ -95,67 +248,13 @@ pub fn render_main_scene(frame: &mut Frame, area: Rect, app: &App) {
 
     // Check if we have a PTY session for the selected room
     if let Some(session) = app.current_session() {
-        let screen = session.screen();
-        let (screen_rows, screen_cols) = screen.size();
-
-        // Log if there's a mismatch between screen size and rendered area
-        if debug_log::is_enabled() && (screen_cols != inner.width || screen_rows != inner.height) {
-            debug_log::log_debug(&format!(
-                "SIZE MISMATCH: screen={}x{} inner={}x{}",
-                screen_cols, screen_rows, inner.width, inner.height
-            ));
-        }
-
-        // Render cells directly to frame buffer
-        let buf = frame.buffer_mut();
-        for y in 0..inner.height as usize {
-            for x in 0..inner.width as usize {
-                let buf_x = inner.x + x as u16;
-                let buf_y = inner.y + y as u16;
-
-                // Get cell from vt100 screen if within bounds
-                if (y as u16) < screen_rows && (x as u16) < screen_cols {
-                    let cell = screen.cell(y as u16, x as u16);
-                    if let Some(cell) = cell {
-                        let c = cell.contents().chars().next().unwrap_or(' ');
-                        let mut fg = vt100_color_to_ratatui(cell.fgcolor(), true);
-                        let mut bg = vt100_color_to_ratatui(cell.bgcolor(), false);
-                        let is_selected = app.selection_contains(y as u16, x as u16);
-
-                        let style = if is_selected {
-                            Style::default().fg(Color::White).bg(Color::DarkGray)
-                        } else if cell.inverse() {
-                            // When fg/bg are defaults, swapping does nothing; use REVERSED to show cursor.
-                            if fg == Color::Reset && bg == Color::Reset {
-                                Style::default().add_modifier(Modifier::REVERSED)
-                            } else {
-                                std::mem::swap(&mut fg, &mut bg);
-                                Style::default().fg(fg).bg(bg)
-                            }
-                        } else {
-                            Style::default().fg(fg).bg(bg)
-                        };
-
-                        buf[(buf_x, buf_y)].set_char(c).set_style(style);
-                    } else {
-                        buf[(buf_x, buf_y)]
-                            .set_char(' ')
-                            .set_fg(Color::Reset)
-                            .set_bg(Color::Reset);
-                    }
-                } else {
-                    // Outside screen buffer - clear
-                    buf[(buf_x, buf_y)]
-                        .set_char(' ')
-                        .set_fg(Color::Reset)
-                        .set_bg(Color::Reset);
-                }
-            }
-        }
+        render_screen(frame, inner, session.screen(), |row, col| {
+            app.selection_contains(row, col)
+        });
 
         // Note: Cursor positioning is handled in app.rs after all rendering is complete
     } else if let Some(room) = app.selected_room_info() {
-        let branch = room.branch.as_deref().unwrap_or("detached");
+        let branch = room.branch_label();
         let mut content = vec![
             Line::from(""),
             Line::from(Span::styled(
@@ -166,9 +265,36 @@ pub fn render_main_scene(frame: &mut Frame, area: Rect, app: &App) {
                 format!("Branch: {}", branch),
                 Style::default().fg(Color::DarkGray),
             )),
-            Line::from(""),
         ];
 
+        if let Some(metadata) = app.room_metadata(room) {
+            let now = Utc::now();
+            let mut details = vec![format!(
+                "Created {}",
+                format_relative_time(metadata.created_at, now)
+            )];
+            if metadata.last_used_at > metadata.created_at {
+                details.push(format!(
+                    "last used {}",
+                    format_relative_time(metadata.last_used_at, now)
+                ));
+            }
+            if let Some(base_branch) = metadata.base_branch.as_deref() {
+                details.push(format!("from {}", base_branch));
+            }
+            content.push(Line::from(Span::styled(
+                details.join(" · "),
+                Style::default().fg(Color::DarkGray),
+            )));
+            if let Some(notes) = metadata.notes.as_deref() {
+                content.push(Line::from(Span::styled(
+                    notes.to_string(),
+                    Style::default().fg(Color::Gray),
+                )));
+            }
+        }
+        content.push(Line::from(""));
+
         if let Some(PendingRoomStatus::Creating) = app.pending_room_status(room) {
             let content = vec![
                 Line::from(""),
@@ -241,6 +367,60 @@ pub fn render_main_scene(frame: &mut Frame, area: Rect, app: &App) {
             return;
         }
 
+        if room.status == RoomStatus::SettingUp {
+            content.push(Line::from(Span::styled(
+                "Running post_create hooks...",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            content.push(Line::from(""));
+            content.push(Line::from(Span::styled(
+                "You'll be connected automatically when ready.",
+                Style::default().fg(Color::DarkGray),
+            )));
+            let paragraph = Paragraph::new(content).alignment(Alignment::Center);
+            frame.render_widget(paragraph, inner);
+            return;
+        }
+
+        if room.status == RoomStatus::Deleting {
+            content.push(Line::from(Span::styled(
+                "Deleting room...",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            content.push(Line::from(""));
+            content.push(Line::from(Span::styled(
+                "Removing the worktree. Press c in the sidebar to cancel if it hasn't started.",
+                Style::default().fg(Color::DarkGray),
+            )));
+            let paragraph = Paragraph::new(content).alignment(Alignment::Center);
+            frame.render_widget(paragraph, inner);
+            return;
+        }
+
+        if let Some(output) = app.setup_failure_output(room) {
+            let summary = room
+                .last_error
+                .as_deref()
+                .unwrap_or("post_create hook failed");
+            let hint = "Press Enter to open a shell anyway";
+            render_failure_output(frame, inner, content, summary, hint, output);
+            return;
+        }
+
+        if let Some(output) = app.delete_failure_output(room) {
+            let hint = if room.path.exists() {
+                "Press d to retry or Enter to open a shell anyway"
+            } else {
+                "Press d to retry"
+            };
+            render_failure_output(frame, inner, content, "Deleting failed", hint, output);
+            return;
+        }
+
         if app.room_section(room) == RoomSection::Failed {
             let detail = if room.is_prunable {
                 PRUNABLE_WORKTREE_MESSAGE.to_string()
@@ -262,6 +442,48 @@ pub fn render_main_scene(frame: &mut Frame, area: Rect, app: &App) {
             )));
         }
 
+        let paragraph = Paragraph::new(content).alignment(Alignment::Center);
+        frame.render_widget(paragraph, inner);
+    } else if let Some(trashed) = app.selected_trashed_room() {
+        let mut content = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("Deleted room: {}", trashed.name),
+                Style::default().fg(Color::White),
+            )),
+            Line::from(Span::styled(
+                format!("Branch: {}", trashed.branch_label()),
+                Style::default().fg(Color::DarkGray),
+            )),
+            Line::from(Span::styled(
+                format!(
+                    "Deleted {}",
+                    format_relative_time(trashed.deleted_at, Utc::now())
+                ),
+                Style::default().fg(Color::DarkGray),
+            )),
+        ];
+        if let Some(notes) = trashed.notes.as_deref() {
+            content.push(Line::from(Span::styled(
+                notes.to_string(),
+                Style::default().fg(Color::Gray),
+            )));
+        }
+        content.push(Line::from(""));
+        content.push(Line::from(Span::styled(
+            if trashed.changes_ref.is_some() {
+                "Uncommitted changes were saved and will be put back"
+            } else {
+                "The room had no uncommitted changes"
+            },
+            Style::default().fg(Color::Gray),
+        )));
+        content.push(Line::from(""));
+        content.push(Line::from(Span::styled(
+            "Press u to restore or D to discard",
+            Style::default().fg(Color::Yellow),
+        )));
+
         let paragraph = Paragraph::new(content).alignment(Alignment::Center);
         frame.render_widget(paragraph, inner);
     } else {
@@ -283,3 +505,71 @@ pub fn render_main_scene(frame: &mut Frame, area: Rect, app: &App) {
         frame.render_widget(paragraph, inner);
     }
 }
+
+/// Render `content` with a failure summary, then the failing command's output.
+fn render_failure_output(
+    frame: &mut Frame,
+    inner: Rect,
+    mut content: Vec<Line>,
+    summary: &str,
+    hint: &str,
+    output: &str,
+) {
+    content.push(Line::from(Span::styled(
+        summary.to_string(),
+        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+    )));
+    content.push(Line::from(Span::styled(
+        hint.to_string(),
+        Style::default().fg(Color::Yellow),
+    )));
+    content.push(Line::from(""));
+    // Command output is left-aligned below the summary, like a terminal.
+    let header_height = (content.len() as u16).min(inner.height);
+    let paragraph = Paragraph::new(content).alignment(Alignment::Center);
+    frame.render_widget(paragraph, inner);
+
+    let output_area = Rect {
+        y: inner.y + header_height,
+        height: inner.height - header_height,
+        ..inner
+    };
+    let lines: Vec<Line> = output
+        .lines()
+        .map(|line| Line::from(Span::styled(line, Style::default().fg(Color::Gray))))
+        .collect();
+    frame.render_widget(Paragraph::new(lines), output_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_format_relative_time() {
+        let now = Utc::now();
+        assert_eq!(format_relative_time(now, now), "just now");
+        assert_eq!(
+            format_relative_time(now - Duration::seconds(30), now),
+            "just now"
+        );
+        assert_eq!(
+            format_relative_time(now - Duration::minutes(1), now),
+            "1 minute ago"
+        );
+        assert_eq!(
+            format_relative_time(now - Duration::hours(5), now),
+            "5 hours ago"
+        );
+        assert_eq!(
+            format_relative_time(now - Duration::days(3), now),
+            "3 days ago"
+        );
+        // Clock skew should not produce negative durations
+        assert_eq!(
+            format_relative_time(now + Duration::minutes(5), now),
+            "just now"
+        );
+    }
+}
@@ -1,10 +1,12 @@
 mod app;
+mod branch_picker;
 mod clipboard;
 mod confirm;
 mod context_menu;
 mod help;
 mod input;
 mod main_scene;
+mod panes;
 mod prompt;
 mod selection;
 mod sidebar;
@@ -0,0 +1,255 @@
+//! Split panes in the main scene.
+//!
+//! Without splits the main scene shows the selected room's active tab. Once
+//! split, each pane shows one room tab. The focused pane always follows the
+//! sidebar selection; the other panes stay pinned to what they showed when
+//! focus left them.
+
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+
+/// How panes are arranged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SplitDirection {
+    /// Panes side by side.
+    #[default]
+    Vertical,
+    /// Panes stacked top to bottom.
+    Horizontal,
+}
+
+/// A room tab shown in a pane.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pane {
+    pub room_name: String,
+    /// Index into the room's tabs.
+    pub tab: usize,
+}
+
+/// Arrangement of panes in the main scene.
+#[derive(Debug, Clone, Default)]
+pub struct PaneLayout {
+    direction: SplitDirection,
+    panes: Vec<Pane>,
+    focused: usize,
+}
+
+impl PaneLayout {
+    /// Whether the main scene is split into more than one pane.
+    pub fn is_split(&self) -> bool {
+        self.panes.len() > 1
+    }
+
+    /// Split the focused pane, showing `new` next to it and focusing it.
+    ///
+    /// `current` is what the main scene shows right now; it is only used
+    /// for the first split. All panes share the direction of the last split.
+    pub fn split(&mut self, direction: SplitDirection, current: Pane, new: Pane) {
+        self.direction = direction;
+        if self.panes.is_empty() {
+            self.panes = vec![current, new];
+            self.focused = 1;
+            return;
+        }
+        self.focused += 1;
+        self.panes.insert(self.focused, new);
+    }
+
+    /// Close the focused pane. Returns the pane that now has focus, if still split.
+    pub fn close_focused(&mut self) -> Option<&Pane> {
+        if !self.is_split() {
+            return None;
+        }
+        self.panes.remove(self.focused);
+        self.focused = self.focused.min(self.panes.len() - 1);
+        self.collapse_if_single();
+        self.focused()
+    }
+
+    /// Move focus to the next pane, wrapping around.
+    pub fn focus_next(&mut self) -> Option<&Pane> {
+        if !self.is_split() {
+            return None;
+        }
+        self.focused = (self.focused + 1) % self.panes.len();
+        self.focused()
+    }
+
+    /// Move focus to the previous pane, wrapping around.
+    pub fn focus_previous(&mut self) -> Option<&Pane> {
+        if !self.is_split() {
+            return None;
+        }
+        self.focused = (self.focused + self.panes.len() - 1) % self.panes.len();
+        self.focused()
+    }
+
+    /// Focus the pane at `index`.
+    pub fn focus(&mut self, index: usize) -> Option<&Pane> {
+        if index >= self.panes.len() {
+            return None;
+        }
+        self.focused = index;
+        self.focused()
+    }
+
+    /// The focused pane, if split.
+    pub fn focused(&self) -> Option<&Pane> {
+        if !self.is_split() {
+            return None;
+        }
+        self.panes.get(self.focused)
+    }
+
+    /// Index of the focused pane.
+    pub fn focused_index(&self) -> usize {
+        self.focused
+    }
+
+    /// Point the focused pane at `pane` (keeps it in sync with the selection).
+    pub fn set_focused(&mut self, pane: Pane) {
+        if let Some(focused) = self.panes.get_mut(self.focused) {
+            *focused = pane;
+        }
+    }
+
+    /// Iterate over panes in display order.
+    pub fn iter(&self) -> impl Iterator<Item = &Pane> {
+        self.panes.iter()
+    }
+
+    /// Drop unfocused panes for which `keep` returns false.
+    pub fn retain_pinned(&mut self, mut keep: impl FnMut(&Pane) -> bool) {
+        let mut index = 0;
+        let focused = self.focused;
+        let mut new_focused = focused;
+        self.panes.retain(|pane| {
+            let retain = index == focused || keep(pane);
+            if !retain && index < focused {
+                new_focused -= 1;
+            }
+            index += 1;
+            retain
+        });
+        self.focused = new_focused;
+        self.collapse_if_single();
+    }
+
+    /// Adjust pane tab indices after a room's tab at `index` was closed.
+    pub fn tab_closed(&mut self, room_name: &str, index: usize) {
+        self.retain_pinned(|pane| !(pane.room_name == room_name && pane.tab == index));
+        let focused = self.focused;
+        for (position, pane) in self.panes.iter_mut().enumerate() {
+            if pane.room_name == room_name && pane.tab > index && position != focused {
+                pane.tab -= 1;
+            }
+        }
+    }
+
+    /// Split `area` into one rect per pane.
+    pub fn rects(&self, area: Rect) -> Vec<Rect> {
+        if !self.is_split() {
+            return vec![area];
+        }
+        let direction = match self.direction {
+            SplitDirection::Vertical => Direction::Horizontal,
+            SplitDirection::Horizontal => Direction::Vertical,
+        };
+        Layout::default()
+            .direction(direction)
+            .constraints(vec![Constraint::Fill(1); self.panes.len()])
+            .split(area)
+            .to_vec()
+    }
+
+    fn collapse_if_single(&mut self) {
+        if self.panes.len() < 2 {
+            self.panes.clear();
+            self.focused = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pane(room_name: &str, tab: usize) -> Pane {
+        Pane {
+            room_name: room_name.to_string(),
+            tab,
+        }
+    }
+
+    #[test]
+    fn test_split_and_close() {
+        let mut layout = PaneLayout::default();
+        assert!(!layout.is_split());
+
+        layout.split(SplitDirection::Vertical, pane("a", 0), pane("a", 1));
+        assert!(layout.is_split());
+        assert_eq!(layout.focused(), Some(&pane("a", 1)));
+
+        layout.split(SplitDirection::Horizontal, pane("ignored", 0), pane("b", 0));
+        assert_eq!(layout.iter().count(), 3);
+        assert_eq!(layout.focused_index(), 2);
+
+        assert_eq!(layout.close_focused(), Some(&pane("a", 1)));
+        assert_eq!(layout.close_focused(), None);
+        assert!(!layout.is_split());
+    }
+
+    #[test]
+    fn test_focus_wraps() {
+        let mut layout = PaneLayout::default();
+        layout.split(SplitDirection::Vertical, pane("a", 0), pane("b", 0));
+
+        assert_eq!(layout.focus_next(), Some(&pane("a", 0)));
+        assert_eq!(layout.focus_previous(), Some(&pane("b", 0)));
+        assert_eq!(layout.focus(0), Some(&pane("a", 0)));
+        assert_eq!(layout.focus(5), None);
+    }
+
+    #[test]
+    fn test_retain_pinned_keeps_focused_pane() {
+        let mut layout = PaneLayout::default();
+        layout.split(SplitDirection::Vertical, pane("gone", 0), pane("a", 0));
+        layout.retain_pinned(|pane| pane.room_name != "gone");
+        assert!(!layout.is_split());
+
+        let mut layout = PaneLayout::default();
+        layout.split(SplitDirection::Vertical, pane("gone", 0), pane("a", 0));
+        layout.split(SplitDirection::Vertical, pane("ignored", 0), pane("b", 0));
+        layout.retain_pinned(|pane| pane.room_name != "gone");
+        assert_eq!(layout.iter().count(), 2);
+        assert_eq!(layout.focused(), Some(&pane("b", 0)));
+    }
+
+    #[test]
+    fn test_tab_closed_shifts_indices() {
+        let mut layout = PaneLayout::default();
+        layout.split(SplitDirection::Vertical, pane("a", 2), pane("b", 0));
+        layout.split(SplitDirection::Vertical, pane("ignored", 0), pane("a", 1));
+        layout.focus(1);
+
+        layout.tab_closed("a", 1);
+        let panes: Vec<&Pane> = layout.iter().collect();
+        assert_eq!(panes, vec![&pane("a", 1), &pane("b", 0)]);
+    }
+
+    #[test]
+    fn test_rects_follow_direction() {
+        let area = Rect::new(0, 0, 100, 40);
+        let mut layout = PaneLayout::default();
+        assert_eq!(layout.rects(area), vec![area]);
+
+        layout.split(SplitDirection::Vertical, pane("a", 0), pane("b", 0));
+        let rects = layout.rects(area);
+        assert_eq!(rects[0], Rect::new(0, 0, 50, 40));
+        assert_eq!(rects[1], Rect::new(50, 0, 50, 40));
+
+        layout.split(SplitDirection::Horizontal, pane("a", 0), pane("c", 0));
+        let rects = layout.rects(area);
+        assert_eq!(rects.len(), 3);
+        assert!(rects.iter().all(|rect| rect.width == 100));
+    }
+}
@@ -3,6 +3,12 @@ use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use unicode_width::UnicodeWidthStr;
+
+use crate::git::Branch;
+
+use super::branch_picker::BranchPicker;
+use super::main_scene::format_relative_time;
 
 /// State for a text input prompt.
 #[derive(Debug, Clone)]
@@ -10,7 +16,7 @@ pub struct TextInput {
     /// Current input value.
     pub value: String,
 
-    /// Cursor position in the input.
+    /// Cursor position in the input, as a byte index on a char boundary.
     pub cursor: usize,
 
     /// Placeholder text shown when empty.
@@ -29,14 +35,14 @@ impl TextInput {
     /// Insert a character at the cursor position.
     pub fn insert(&mut self, c: char) {
         self.value.insert(self.cursor, c);
-        self.cursor += 1;
+        self.cursor += c.len_utf8();
     }
 
     /// Delete the character before the cursor (backspace).
     pub fn backspace(&mut self) {
-        if self.cursor > 0 {
-            self.cursor -= 1;
-            self.value.remove(self.cursor);
+        if let Some(prev) = self.prev_boundary() {
+            self.value.remove(prev);
+            self.cursor = prev;
         }
     }
 
@@ -49,16 +55,26 @@ impl TextInput {
 
     /// Move cursor left.
     pub fn move_left(&mut self) {
-        self.cursor = self.cursor.saturating_sub(1);
+        if let Some(prev) = self.prev_boundary() {
+            self.cursor = prev;
+        }
     }
 
     /// Move cursor right.
     pub fn move_right(&mut self) {
-        if self.cursor < self.value.len() {
-            self.cursor += 1;
+        if let Some(c) = self.value[self.cursor..].chars().next() {
+            self.cursor += c.len_utf8();
         }
     }
 
+    /// Byte index of the character before the cursor, if any.
+    fn prev_boundary(&self) -> Option<usize> {
+        self.value[..self.cursor]
+            .char_indices()
+            .next_back()
+            .map(|(index, _)| index)
+    }
+
     /// Move cursor to start.
     pub fn move_start(&mut self) {
         self.cursor = 0;
@@ -86,6 +102,17 @@ impl TextInput {
     }
 }
 
+/// Values collected by the create-room prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewRoom {
+    /// Room name, or `None` to generate one.
+    pub name: Option<String>,
+    /// Branch name, or `None` to use the room name.
+    pub branch: Option<String>,
+    /// Branch to create a new branch from, or `None` for HEAD.
+    pub base_branch: Option<String>,
+}
+
 /// The current prompt being shown.
 #[derive(Debug, Clone, Default)]
 pub enum PromptState {
@@ -94,14 +121,38 @@ pub enum PromptState {
     None,
 
     /// Prompting for room name.
-    RoomName(TextInput),
+    RoomName {
+        input: TextInput,
+        /// Branches offered in the next steps.
+        branches: Vec<Branch>,
+        /// Base branch used when none is picked.
+        default_base: Option<String>,
+    },
 
-    /// Prompting for branch name (after room name).
+    /// Picking a branch (after room name).
     BranchName {
         room_name: Option<String>,
-        input: TextInput,
+        picker: BranchPicker,
+        default_base: Option<String>,
+    },
+
+    /// Picking the base for a new branch (after branch name).
+    BaseBranch {
+        room_name: Option<String>,
+        branch: Option<String>,
+        picker: BranchPicker,
+        default_base: Option<String>,
     },
 
+    /// Prompting for the commit-ish of a detached room.
+    DetachedCommit { input: TextInput },
+
+    /// Prompting for the bad commit of a bisect room.
+    BisectBad { input: TextInput },
+
+    /// Prompting for the good commit of a bisect room (after the bad one).
+    BisectGood { bad: String, input: TextInput },
+
     /// Prompting for new room name (rename).
     RenameRoom {
         /// Original name (for lookup during save).
@@ -109,12 +160,57 @@ pub enum PromptState {
         /// Text input pre-filled with current name.
         input: TextInput,
     },
+
+    /// Renaming the active terminal tab of a room.
+    RenameTab {
+        room_name: String,
+        /// Text input pre-filled with the current tab name.
+        input: TextInput,
+    },
+
+    /// Editing the notes attached to a room.
+    EditNotes {
+        room_name: String,
+        /// Text input pre-filled with the current notes.
+        input: TextInput,
+    },
 }
 
 impl PromptState {
-    /// Start prompting for a new room.
-    pub fn start_room_creation() -> Self {
-        Self::RoomName(TextInput::new("Leave empty for generated name"))
+    /// Start prompting for a new room, offering `branches` to pick from.
+    pub fn start_room_creation(branches: Vec<Branch>, default_base: Option<String>) -> Self {
+        Self::RoomName {
+            input: TextInput::new("Leave empty for generated name"),
+            branches,
+            default_base,
+        }
+    }
+
+    /// Offer `branches` in a create-room prompt that opened before they were listed.
+    pub fn set_branches(&mut self, branches: Vec<Branch>) {
+        match self {
+            Self::RoomName {
+                branches: offered, ..
+            } => *offered = branches,
+            Self::BranchName { picker, .. } | Self::BaseBranch { picker, .. } => {
+                picker.set_branches(branches)
+            }
+            _ => {}
+        }
+    }
+
+    /// Start prompting for the commit, tag or ref of a detached room.
+    pub fn start_detached_creation() -> Self {
+        Self::DetachedCommit {
+            input: TextInput::new("Commit, tag or ref, e.g. v1.2.0 or HEAD~3"),
+        }
+    }
+
+    /// Start prompting for the bad and good commits of a bisect room.
+    pub fn start_bisect() -> Self {
+        Self::BisectBad {
+            input: TextInput::new("Leave empty for HEAD"),
+        }
     }
 
     /// Start prompting for a room rename.
@@ -128,6 +224,22 @@ impl PromptState {
         }
     }
 
+    /// Start renaming a room's active terminal tab.
+    pub fn start_tab_rename(room_name: String, tab_name: String) -> Self {
+        let mut input = TextInput::new("");
+        input.value = tab_name;
+        input.cursor = input.value.len();
+        Self::RenameTab { room_name, input }
+    }
+
+    /// Start editing a room's notes.
+    pub fn start_notes_edit(room_name: String, notes: Option<String>) -> Self {
+        let mut input = TextInput::new("Leave empty to clear notes");
+        input.value = notes.unwrap_or_default();
+        input.cursor = input.value.len();
+        Self::EditNotes { room_name, input }
+    }
+
     /// Check if a prompt is active.
     pub fn is_active(&self) -> bool {
         !matches!(self, Self::None)
@@ -137,33 +249,106 @@ impl PromptState {
     pub fn current_input(&mut self) -> Option<&mut TextInput> {
         match self {
             Self::None => None,
-            Self::RoomName(input) => Some(input),
-            Self::BranchName { input, .. } => Some(input),
+            Self::RoomName { input, .. }
+            | Self::DetachedCommit { input }
+            | Self::BisectBad { input }
+            | Self::BisectGood { input, .. } => Some(input),
+            Self::BranchName { picker, .. } | Self::BaseBranch { picker, .. } => {
+                Some(&mut picker.input)
+            }
             Self::RenameRoom { input, .. } => Some(input),
+            Self::RenameTab { input, .. } => Some(input),
+            Self::EditNotes { input, .. } => Some(input),
+        }
+    }
+
+    /// Get the current branch picker, if any.
+    pub fn picker(&mut self) -> Option<&mut BranchPicker> {
+        match self {
+            Self::BranchName { picker, .. } | Self::BaseBranch { picker, .. } => Some(picker),
+            _ => None,
         }
     }
 
     /// Advance to the next prompt step, returning the final result if done.
-    /// Returns Some((room_name, branch_name)) when complete.
+    ///
+    /// The base branch step is skipped when the branch already exists
+    /// locally or on a remote.
     /// Note: RenameRoom is handled separately and should not use this method.
-    pub fn advance(&mut self) -> Option<(Option<String>, Option<String>)> {
+    pub fn advance(&mut self) -> Option<NewRoom> {
         match std::mem::take(self) {
             Self::None => None,
-            Self::RoomName(input) => {
-                let room_name = input.get_value();
+            Self::RoomName {
+                input,
+                branches,
+                default_base,
+            } => {
                 *self = Self::BranchName {
+                    room_name: input.get_value(),
+                    picker: BranchPicker::new("Leave empty to use room name", branches),
+                    default_base,
+                };
+                None
+            }
+            Self::BranchName {
+                room_name,
+                picker,
+                default_base,
+            } => {
+                let branch = match picker.selected_branch() {
+                    Some(branch) => Some(branch.local_name().to_string()),
+                    None => picker.input.get_value(),
+                };
+                let checkout = branch.as_ref().or(room_name.as_ref());
+                let exists = checkout.is_some_and(|checkout| {
+                    picker
+                        .branches()
+                        .iter()
+                        .any(|branch| branch.local_name() == checkout)
+                });
+                if exists {
+                    return Some(NewRoom {
+                        name: room_name,
+                        branch,
+                        base_branch: None,
+                    });
+                }
+
+                let placeholder = format!(
+                    "Leave empty to use {}",
+                    default_base.as_deref().unwrap_or("HEAD")
+                );
+                *self = Self::BaseBranch {
                     room_name,
-                    input: TextInput::new("Leave empty to use room name"),
+                    branch,
+                    picker: BranchPicker::new(placeholder, picker.branches().to_vec()),
+                    default_base,
                 };
                 None
             }
-            Self::BranchName { room_name, input } => {
-                let branch_name = input.get_value();
-                *self = Self::None;
-                Some((room_name, branch_name))
+            Self::BaseBranch {
+                room_name,
+                branch,
+                picker,
+                default_base,
+            } => {
+                let base_branch = match picker.selected_branch() {
+                    Some(base) => Some(base.name.clone()),
+                    None => picker.input.get_value().or(default_base),
+                };
+                Some(NewRoom {
+                    name: room_name,
+                    branch,
+                    base_branch,
+                })
             }
-            Self::RenameRoom { .. } => {
-                // RenameRoom is handled directly in handle_prompt_key, not via advance()
+            Self::DetachedCommit { .. }
+            | Self::BisectBad { .. }
+            | Self::BisectGood { .. }
+            | Self::RenameRoom { .. }
+            | Self::RenameTab { .. }
+            | Self::EditNotes { .. } => {
+                // Single-step prompts are handled directly in handle_prompt_key, not via advance()
                 *self = Self::None;
                 None
             }
@@ -180,11 +365,43 @@ impl PromptState {
 pub fn render_prompt(frame: &mut Frame, area: Rect, prompt: &PromptState) {
     let (title, hint, input) = match prompt {
         PromptState::None => return,
-        PromptState::RoomName(input) => ("Create Room - Name", "Enter room name:", input),
-        PromptState::BranchName { input, .. } => {
-            ("Create Room - Branch", "Enter branch name:", input)
+        PromptState::RoomName { input, .. } => ("Create Room - Name", "Enter room name:", input),
+        PromptState::BranchName { picker, .. } => {
+            return render_branch_picker(
+                frame,
+                area,
+                "Create Room - Branch",
+                "Pick or enter a branch:",
+                picker,
+            );
+        }
+        PromptState::BaseBranch { picker, .. } => {
+            return render_branch_picker(
+                frame,
+                area,
+                "Create Room - Base Branch",
+                "Pick or enter the branch to start from:",
+                picker,
+            );
         }
+        PromptState::DetachedCommit { input } => (
+            "Create Detached Room",
+            "Enter a commit, tag or ref to check out:",
+            input,
+        ),
+        PromptState::BisectBad { input } => (
+            "Bisect - Bad Commit",
+            "Enter a commit, tag or ref that has the bug:",
+            input,
+        ),
+        PromptState::BisectGood { input, .. } => (
+            "Bisect - Good Commit",
+            "Enter a commit, tag or ref without the bug:",
+            input,
+        ),
         PromptState::RenameRoom { input, .. } => ("Rename Room", "Enter new name:", input),
+        PromptState::RenameTab { input, .. } => ("Rename Tab", "Enter new tab name:", input),
+        PromptState::EditNotes { input, .. } => ("Room Notes", "Enter notes for this room:", input),
     };
 
     // Center the prompt
@@ -215,7 +432,123 @@ pub fn render_prompt(frame: &mut Frame, area: Rect, prompt: &PromptState) {
     let hint_text = Paragraph::new(hint).style(Style::default().fg(Color::White));
     frame.render_widget(hint_text, chunks[0]);
 
-    // Input field
+    render_input(frame, chunks[1], input);
+
+    // Help text
+    let help = Paragraph::new(vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Enter", Style::default().fg(Color::Yellow)),
+            Span::raw(" confirm  "),
+            Span::styled("Esc", Style::default().fg(Color::Yellow)),
+            Span::raw(" cancel"),
+        ]),
+    ])
+    .alignment(Alignment::Center)
+    .style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(help, chunks[2]);
+}
+
+/// Render a prompt with a filterable branch list below the input.
+fn render_branch_picker(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    hint: &str,
+    picker: &BranchPicker,
+) {
+    let popup_area = centered_rect(60, 60, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(" {} ", title))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    // Layout: hint, input, branch list, help
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Length(3),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    let hint_text = Paragraph::new(hint).style(Style::default().fg(Color::White));
+    frame.render_widget(hint_text, chunks[0]);
+
+    render_input(frame, chunks[1], &picker.input);
+
+    let matches = picker.matches();
+    let visible = usize::from(chunks[2].height);
+    // Keep the highlighted branch in view
+    let offset = picker
+        .selected()
+        .map_or(0, |index| (index + 1).saturating_sub(visible));
+    let now = chrono::Utc::now();
+    let lines: Vec<Line> = if matches.is_empty() {
+        vec![Line::from(Span::styled(
+            "  No matching branches; Enter uses the typed name",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        matches
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(visible)
+            .map(|(index, branch)| {
+                let selected = picker.selected() == Some(index);
+                let name_style = match (selected, branch.remote.is_some()) {
+                    (true, _) => Style::default().fg(Color::Black).bg(Color::Cyan),
+                    (false, true) => Style::default().fg(Color::Blue),
+                    (false, false) => Style::default().fg(Color::White),
+                };
+                let mut spans = vec![
+                    Span::raw(if selected { "> " } else { "  " }),
+                    Span::styled(branch.name.clone(), name_style),
+                ];
+                if let Some(time) = branch.committed_at {
+                    spans.push(Span::styled(
+                        format!("  {}", format_relative_time(time, now)),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                if let Some(worktree) = &branch.worktree {
+                    let name = worktree
+                        .file_name()
+                        .map_or_else(|| worktree.to_string_lossy(), |name| name.to_string_lossy());
+                    spans.push(Span::styled(
+                        format!("  checked out in {name}"),
+                        Style::default().fg(Color::Yellow),
+                    ));
+                }
+                Line::from(spans)
+            })
+            .collect()
+    };
+    frame.render_widget(Paragraph::new(lines), chunks[2]);
+
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("↑/↓", Style::default().fg(Color::Yellow)),
+        Span::raw(" pick  "),
+        Span::styled("Enter", Style::default().fg(Color::Yellow)),
+        Span::raw(" confirm  "),
+        Span::styled("Esc", Style::default().fg(Color::Yellow)),
+        Span::raw(" cancel"),
+    ]))
+    .alignment(Alignment::Center)
+    .style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(help, chunks[3]);
+}
+
+/// Render a bordered text input and place the cursor in it.
+pub(super) fn render_input(frame: &mut Frame, area: Rect, input: &TextInput) {
     let display_value = if input.value.is_empty() {
         Span::styled(&input.placeholder, Style::default().fg(Color::DarkGray))
     } else {
@@ -227,28 +560,14 @@ pub fn render_prompt(frame: &mut Frame, area: Rect, prompt: &PromptState) {
         .border_style(Style::default().fg(Color::Yellow));
 
     let input_paragraph = Paragraph::new(Line::from(display_value)).block(input_block);
-    frame.render_widget(input_paragraph, chunks[1]);
+    frame.render_widget(input_paragraph, area);
 
     // Set cursor position
     if !input.value.is_empty() || input.placeholder.is_empty() {
-        let cursor_x = chunks[1].x + 1 + input.cursor as u16;
-        let cursor_y = chunks[1].y + 1;
+        let cursor_x = area.x + 1 + input.value[..input.cursor].width() as u16;
+        let cursor_y = area.y + 1;
         frame.set_cursor_position((cursor_x, cursor_y));
     }
-
-    // Help text
-    let help = Paragraph::new(vec![
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Enter", Style::default().fg(Color::Yellow)),
-            Span::raw(" confirm  "),
-            Span::styled("Esc", Style::default().fg(Color::Yellow)),
-            Span::raw(" cancel"),
-        ]),
-    ])
-    .alignment(Alignment::Center)
-    .style(Style::default().fg(Color::DarkGray));
-    frame.render_widget(help, chunks[2]);
 }
 
 /// Create a centered rectangle with the given percentage width and height.
@@ -316,9 +635,46 @@ mod tests {
         assert_eq!(input.cursor, 3);
     }
 
+    #[test]
+    fn test_text_input_non_ascii() {
+        let mut input = TextInput::new("");
+        input.insert('é');
+        input.insert('é');
+        assert_eq!(input.value, "éé");
+        assert_eq!(input.cursor, 4);
+
+        input.move_left();
+        assert_eq!(input.cursor, 2);
+        input.insert('x');
+        assert_eq!(input.value, "éxé");
+
+        input.move_right();
+        assert_eq!(input.cursor, input.value.len());
+        input.move_right();
+        assert_eq!(input.cursor, input.value.len());
+
+        input.backspace();
+        assert_eq!(input.value, "éx");
+        input.move_start();
+        input.delete();
+        assert_eq!(input.value, "x");
+        assert_eq!(input.cursor, 0);
+        input.backspace();
+        assert_eq!(input.value, "x");
+    }
+
+    fn branch(name: &str, remote: Option<&str>) -> Branch {
+        Branch {
+            name: name.to_string(),
+            remote: remote.map(str::to_string),
+            committed_at: None,
+            worktree: None,
+        }
+    }
+
     #[test]
     fn test_prompt_state_flow() {
-        let mut prompt = PromptState::start_room_creation();
+        let mut prompt = PromptState::start_room_creation(Vec::new(), Some("main".to_string()));
         assert!(prompt.is_active());
 
         // Enter room name
@@ -334,11 +690,70 @@ mod tests {
         assert!(result.is_none());
         assert!(matches!(prompt, PromptState::BranchName { .. }));
 
-        // Leave branch empty and advance
+        // Leave branch empty and advance to the base branch
+        assert!(prompt.advance().is_none());
+        assert!(matches!(prompt, PromptState::BaseBranch { .. }));
+
+        // Leave base empty to use the default
+        let result = prompt.advance();
+        assert_eq!(
+            result,
+            Some(NewRoom {
+                name: Some("test".to_string()),
+                branch: None,
+                base_branch: Some("main".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_prompt_picks_existing_branch() {
+        let branches = vec![
+            branch("main", None),
+            branch("origin/feature/login", Some("origin")),
+        ];
+        let mut prompt = PromptState::start_room_creation(branches, None);
+        prompt.advance();
+
+        // Highlight the remote branch; it's checked out under its local name
+        for c in "login".chars() {
+            prompt.current_input().unwrap().insert(c);
+        }
+        prompt.picker().unwrap().select_next();
         let result = prompt.advance();
-        assert!(result.is_some());
-        let (room_name, branch_name) = result.unwrap();
-        assert_eq!(room_name, Some("test".to_string()));
-        assert_eq!(branch_name, None);
+        assert_eq!(
+            result,
+            Some(NewRoom {
+                name: None,
+                branch: Some("feature/login".to_string()),
+                base_branch: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_prompt_picks_base_branch() {
+        let branches = vec![branch("main", None), branch("origin/main", Some("origin"))];
+        let mut prompt = PromptState::start_room_creation(branches, None);
+        prompt.advance();
+        for c in "new-work".chars() {
+            prompt.current_input().unwrap().insert(c);
+        }
+        assert!(prompt.advance().is_none());
+
+        let picker = prompt.picker().unwrap();
+        picker.select_next();
+        picker.select_next();
+        let result = prompt.advance().unwrap();
+        assert_eq!(result.branch, Some("new-work".to_string()));
+        assert_eq!(result.base_branch, Some("origin/main".to_string()));
+    }
+
+    #[test]
+    fn test_notes_prompt_prefilled() {
+        let mut prompt = PromptState::start_notes_edit("room".to_string(), Some("wip".to_string()));
+        let input = prompt.current_input().unwrap();
+        assert_eq!(input.value, "wip");
+        assert_eq!(input.cursor, 3);
     }
 }
@@ -5,12 +5,15 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, List, ListItem, ListState};
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
-use crate::room::{RoomInfo, RoomStatus};
+use crate::git::BisectStatus;
+use crate::room::{RoomGitStatus, RoomInfo, RoomStatus, short_sha};
 
-use super::app::{App, Focus, RoomSection};
+use super::app::{App, Focus, RoomSection, ServiceStatus};
+use super::main_scene::format_relative_time;
 
 const PRUNABLE_LABEL: &str = " [prunable]";
 const ERROR_LABEL: &str = " [error]";
+const TRASH_SECTION_TITLE: &str = "RECENTLY DELETED";
 
 /// Truncate a string to fit within max_width, adding ellipsis if needed.
 /// Uses unicode width to handle multi-byte characters correctly.
@@ -62,7 +65,7 @@ pub fn render_sidebar(frame: &mut Frame, area: Rect, app: &App) {
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    if app.rooms.is_empty() {
+    if app.rooms.is_empty() && app.trashed_rooms().is_empty() {
         // Show empty state
         let empty_msg = vec![
             Line::from(""),
@@ -92,6 +95,8 @@ pub fn render_sidebar(frame: &mut Frame, area: Rect, app: &App) {
     const BRANCH_PREFIX_WIDTH: usize = 5;
     const PRIMARY_LABEL: &str = " [primary]";
     const CREATING_LABEL: &str = " Creating...";
+    const SETTING_UP_LABEL: &str = " Setting up...";
+    const DELETING_LABEL: &str = " Deleting...";
 
     let left_pad = " ".repeat(ITEM_PADDING as usize);
     let right_pad = " ".repeat(ITEM_PADDING as usize);
@@ -100,7 +105,7 @@ pub fn render_sidebar(frame: &mut Frame, area: Rect, app: &App) {
     let mut list_state = ListState::default();
     let mut list_index = 0;
     let mut selected_list_index = None;
-    let mut selected_is_creating = false;
+    let mut selected_is_busy = false;
     let mut current_section: Option<RoomSection> = None;
     let mut has_rendered_section = false;
 
@@ -127,12 +132,18 @@ pub fn render_sidebar(frame: &mut Frame, area: Rect, app: &App) {
             has_rendered_section = true;
         }
 
-        let status_icon = if room.status == RoomStatus::Creating {
+        let in_progress = matches!(
+            room.status,
+            RoomStatus::Creating | RoomStatus::SettingUp | RoomStatus::Deleting
+        );
+        // Rooms being created or deleted can't be entered
+        let is_busy = matches!(room.status, RoomStatus::Creating | RoomStatus::Deleting);
+        let status_icon = if in_progress {
             app.creation_pulse_glyph()
         } else {
             status_icon_for_room(room, section)
         };
-        let status_color = if room.status == RoomStatus::Creating {
+        let status_color = if in_progress {
             Color::Yellow
         } else {
             status_color(&room.status)
@@ -140,7 +151,7 @@ pub fn render_sidebar(frame: &mut Frame, area: Rect, app: &App) {
         let status_style = Style::default().fg(status_color);
 
         let is_selected = i == app.selected_index;
-        let style = if is_selected && room.status == RoomStatus::Creating {
+        let style = if is_selected && is_busy {
             Style::default().fg(Color::Gray).bg(Color::DarkGray)
         } else if is_selected && is_focused {
             Style::default()
@@ -155,21 +166,32 @@ pub fn render_sidebar(frame: &mut Frame, area: Rect, app: &App) {
 
         let failed_label = failed_reason_label(room);
         let primary_label = if room.is_primary { PRIMARY_LABEL } else { "" };
-        let creating_label = if room.status == RoomStatus::Creating {
-            CREATING_LABEL
-        } else {
-            ""
+        let progress_label = match room.status {
+            RoomStatus::Creating => CREATING_LABEL,
+            RoomStatus::SettingUp => SETTING_UP_LABEL,
+            RoomStatus::Deleting => DELETING_LABEL,
+            _ => "",
         };
-        let label_width = primary_label.width() + failed_label.width() + creating_label.width();
+        let exit_label = app
+            .session_exit_code(room)
+            .map(|code| format!(" exited {}", code))
+            .unwrap_or_default();
+        let label_width = primary_label.width()
+            + failed_label.width()
+            + progress_label.width()
+            + exit_label.width();
         let room_name_min_width = 4;
         let room_name_max_width = content_width
             .saturating_sub(STATUS_PREFIX_WIDTH + label_width)
             .max(room_name_min_width);
-        let branch_name_max_width = content_width.saturating_sub(BRANCH_PREFIX_WIDTH);
+        let git_status = app.git_status(room);
+        let upstream_label = git_status.map(upstream_label).unwrap_or_default();
+        let branch_name_max_width =
+            content_width.saturating_sub(BRANCH_PREFIX_WIDTH + upstream_label.width());
 
         let room_name = truncate_with_ellipsis(&room.name, room_name_max_width);
-        let branch = room.branch.as_deref().unwrap_or("detached");
-        let branch_name = truncate_with_ellipsis(branch, branch_name_max_width);
+        let branch = room.branch_label();
+        let branch_name = truncate_with_ellipsis(&branch, branch_name_max_width);
 
         let mut title_spans = vec![
             Span::raw(left_pad.clone()),
@@ -182,12 +204,20 @@ pub fn render_sidebar(frame: &mut Frame, area: Rect, app: &App) {
                 Style::default().fg(Color::LightRed),
             ));
         }
-        if room.status == RoomStatus::Creating {
+        if in_progress {
             title_spans.push(Span::styled(
-                creating_label,
+                progress_label,
                 Style::default().fg(Color::Yellow),
             ));
         }
+        if let Some(code) = app.session_exit_code(room) {
+            let color = if code == 0 {
+                Color::DarkGray
+            } else {
+                Color::LightRed
+            };
+            title_spans.push(Span::styled(exit_label, Style::default().fg(color)));
+        }
         if room.is_primary {
             title_spans.push(Span::styled(
                 primary_label,
@@ -196,29 +226,139 @@ pub fn render_sidebar(frame: &mut Frame, area: Rect, app: &App) {
         }
         title_spans.push(Span::raw(right_pad.clone()));
 
-        let content = vec![
+        let mut content = vec![
             // Line 1: Status icon + Room name + primary label
             Line::from(title_spans),
-            // Line 2: Branch indicator + Branch name
+            // Line 2: Branch indicator + Branch name + upstream divergence
             Line::from(vec![
                 Span::raw(left_pad.clone()),
                 Span::styled("  └─ ", Style::default().fg(Color::DarkGray)),
                 Span::styled(branch_name, Style::default().fg(Color::DarkGray)),
+                Span::styled(upstream_label, Style::default().fg(Color::Cyan)),
                 Span::raw(right_pad.clone()),
             ]),
         ];
 
+        // Line 3: Uncommitted changes, stashes and base divergence, if any
+        let status_parts = git_status.map(git_status_parts).unwrap_or_default();
+        if !status_parts.is_empty() {
+            let mut status_spans = vec![Span::raw(left_pad.clone()), Span::raw("     ")];
+            for (index, (text, color)) in status_parts.into_iter().enumerate() {
+                if index > 0 {
+                    status_spans.push(Span::raw("  "));
+                }
+                status_spans.push(Span::styled(text, Style::default().fg(color)));
+            }
+            content.push(Line::from(status_spans));
+        }
+
+        // Bisect progress, if the room is bisecting
+        if let Some(bisect) = app.bisect_status(room) {
+            content.push(Line::from(vec![
+                Span::raw(left_pad.clone()),
+                Span::raw("     "),
+                Span::styled(
+                    truncate_with_ellipsis(&bisect_label(bisect), branch_name_max_width),
+                    Style::default().fg(Color::Magenta),
+                ),
+            ]));
+        }
+
+        // Service badges, if any services have been started
+        let services = app.service_statuses(room);
+        if !services.is_empty() {
+            let mut badge_spans = vec![Span::raw(left_pad.clone()), Span::raw("     ")];
+            for (name, status) in services {
+                let (glyph, color) = service_badge(status);
+                badge_spans.push(Span::styled(
+                    format!("{} {} ", glyph, name),
+                    Style::default().fg(color),
+                ));
+            }
+            content.push(Line::from(badge_spans));
+        }
+
         items.push(ListItem::new(content).style(style));
         if is_selected {
             selected_list_index = Some(list_index);
-            if room.status == RoomStatus::Creating {
-                selected_is_creating = true;
+            if is_busy {
+                selected_is_busy = true;
+            }
+        }
+        list_index += 1;
+    }
+
+    // Deleted rooms that can be brought back with u
+    let now = chrono::Utc::now();
+    for (i, trashed) in app.trashed_rooms().iter().enumerate() {
+        if i == 0 {
+            if has_rendered_section {
+                items.push(ListItem::new(Line::from("")));
+                list_index += 1;
             }
+            items.push(ListItem::new(Line::from(vec![
+                Span::raw(left_pad.clone()),
+                Span::styled(
+                    TRASH_SECTION_TITLE,
+                    Style::default()
+                        .fg(Color::DarkGray)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ])));
+            items.push(ListItem::new(Line::from("")));
+            list_index += 2;
+        }
+
+        let is_selected = app.rooms.len() + i == app.selected_index;
+        let style = if is_selected && is_focused {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else if is_selected {
+            Style::default().fg(Color::Black).bg(Color::DarkGray)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+
+        let room_name = truncate_with_ellipsis(
+            &trashed.name,
+            content_width.saturating_sub(STATUS_PREFIX_WIDTH),
+        );
+        let mut details = vec![
+            trashed.branch_label(),
+            format_relative_time(trashed.deleted_at, now),
+        ];
+        if trashed.changes_ref.is_some() {
+            details.push("changes saved".to_string());
+        }
+        let details = truncate_with_ellipsis(
+            &details.join(" · "),
+            content_width.saturating_sub(BRANCH_PREFIX_WIDTH),
+        );
+
+        let content = vec![
+            Line::from(vec![
+                Span::raw(left_pad.clone()),
+                Span::styled("↺ ", Style::default().fg(Color::DarkGray)),
+                Span::styled(room_name, style),
+                Span::raw(right_pad.clone()),
+            ]),
+            Line::from(vec![
+                Span::raw(left_pad.clone()),
+                Span::styled("  └─ ", Style::default().fg(Color::DarkGray)),
+                Span::styled(details, Style::default().fg(Color::DarkGray)),
+                Span::raw(right_pad.clone()),
+            ]),
+        ];
+        items.push(ListItem::new(content).style(style));
+        if is_selected {
+            selected_list_index = Some(list_index);
         }
         list_index += 1;
     }
 
-    let highlight_style = if selected_is_creating {
+    let highlight_style = if selected_is_busy {
         Style::default()
     } else {
         Style::default()
@@ -233,6 +373,71 @@ pub fn render_sidebar(frame: &mut Frame, area: Rect, app: &App) {
     frame.render_stateful_widget(list, inner, &mut list_state);
 }
 
+/// Commits ahead/behind the upstream, e.g. ` ↑2 ↓1`; empty when in sync.
+fn upstream_label(status: &RoomGitStatus) -> String {
+    let Some(upstream) = status.upstream else {
+        return String::new();
+    };
+    let mut label = String::new();
+    if upstream.ahead > 0 {
+        label.push_str(&format!(" ↑{}", upstream.ahead));
+    }
+    if upstream.behind > 0 {
+        label.push_str(&format!(" ↓{}", upstream.behind));
+    }
+    label
+}
+
+/// Non-empty parts of a room's git status with their colors, e.g.
+/// `3 changed`, `1 untracked`, `2 stashed` and `base ↑4 ↓2`.
+fn git_status_parts(status: &RoomGitStatus) -> Vec<(String, Color)> {
+    let mut parts = Vec::new();
+    if let Some(dirty) = &status.dirty {
+        if dirty.modified_count > 0 {
+            parts.push((format!("{} changed", dirty.modified_count), Color::Yellow));
+        }
+        if dirty.untracked_count > 0 {
+            parts.push((
+                format!("{} untracked", dirty.untracked_count),
+                Color::Yellow,
+            ));
+        }
+    }
+    if status.stashes > 0 {
+        parts.push((format!("{} stashed", status.stashes), Color::Cyan));
+    }
+    if let Some(base) = status.base.filter(|base| !base.is_even()) {
+        parts.push((
+            format!("base ↑{} ↓{}", base.ahead, base.behind),
+            Color::DarkGray,
+        ));
+    }
+    parts
+}
+
+/// Describe bisect progress, e.g. `bisect step 3: 4 left (~2 steps)`.
+pub(super) fn bisect_label(status: &BisectStatus) -> String {
+    if let Some(sha) = &status.first_bad {
+        return format!("bisect done: first bad {}", short_sha(sha));
+    }
+    match (status.revisions_left, status.steps_left) {
+        (Some(revisions), Some(steps)) => format!(
+            "bisect step {}: {revisions} left (~{steps} {})",
+            status.step,
+            if steps == 1 { "step" } else { "steps" }
+        ),
+        _ => format!("bisect step {}", status.step),
+    }
+}
+
+fn service_badge(status: ServiceStatus) -> (&'static str, Color) {
+    match status {
+        ServiceStatus::Running => ("●", Color::Green),
+        ServiceStatus::Exited => ("○", Color::DarkGray),
+        ServiceStatus::Failed(_) => ("✗", Color::LightRed),
+    }
+}
+
 fn section_title(section: RoomSection) -> &'static str {
     match section {
         RoomSection::Active => "ACTIVE",
@@ -249,6 +454,7 @@ fn status_icon_for_room(room: &RoomInfo, section: RoomSection) -> &'static str {
     match room.status {
         RoomStatus::Idle => "○",
         RoomStatus::Creating => "◐",
+        RoomStatus::SettingUp => "◐",
         RoomStatus::Ready => "●",
         RoomStatus::Error => "!",
         RoomStatus::Deleting => "◐",
@@ -273,6 +479,7 @@ fn status_color(status: &RoomStatus) -> Color {
     match status {
         RoomStatus::Idle => Color::White,
         RoomStatus::Creating => Color::Yellow,
+        RoomStatus::SettingUp => Color::Yellow,
         RoomStatus::Ready => Color::Green,
         RoomStatus::Error => Color::Red,
         RoomStatus::Deleting => Color::Yellow,
@@ -283,11 +490,14 @@ fn status_color(status: &RoomStatus) -> Color {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::git::AheadBehind;
+    use crate::room::DirtyStatus;
 
     fn make_room(name: &str, status: RoomStatus) -> RoomInfo {
         RoomInfo {
             name: name.to_string(),
             branch: Some("main".to_string()),
+            head: None,
             path: std::path::PathBuf::from("/tmp"),
             status,
             is_prunable: false,
@@ -351,4 +561,65 @@ mod tests {
         let label = failed_reason_label(&room);
         assert_eq!(label, ERROR_LABEL);
     }
+
+    #[test]
+    fn test_git_status_labels() {
+        let mut status = RoomGitStatus {
+            dirty: Some(DirtyStatus {
+                is_dirty: true,
+                modified_count: 3,
+                untracked_count: 0,
+                summary: String::new(),
+            }),
+            upstream: Some(AheadBehind {
+                ahead: 2,
+                behind: 0,
+            }),
+            base: Some(AheadBehind {
+                ahead: 4,
+                behind: 1,
+            }),
+            stashes: 1,
+        };
+        assert_eq!(upstream_label(&status), " ↑2");
+        let parts: Vec<String> = git_status_parts(&status)
+            .into_iter()
+            .map(|(text, _)| text)
+            .collect();
+        assert_eq!(parts, vec!["3 changed", "1 stashed", "base ↑4 ↓1"]);
+
+        status = RoomGitStatus {
+            base: Some(AheadBehind::default()),
+            ..Default::default()
+        };
+        assert_eq!(upstream_label(&status), "");
+        assert!(git_status_parts(&status).is_empty());
+    }
+
+    #[test]
+    fn test_bisect_label() {
+        let mut status = BisectStatus {
+            step: 3,
+            revisions_left: Some(4),
+            steps_left: Some(2),
+            first_bad: None,
+        };
+        assert_eq!(bisect_label(&status), "bisect step 3: 4 left (~2 steps)");
+
+        status.revisions_left = None;
+        assert_eq!(bisect_label(&status), "bisect step 3");
+
+        status.first_bad = Some("1190a704ecc83981".to_string());
+        assert_eq!(bisect_label(&status), "bisect done: first bad 1190a70");
+    }
+
+    #[test]
+    fn test_service_badge() {
+        assert_eq!(service_badge(ServiceStatus::Running), ("●", Color::Green));
+        assert_eq!(service_badge(ServiceStatus::Exited), ("○", Color::DarkGray));
+        assert_eq!(
+            service_badge(ServiceStatus::Failed(1)),
+            ("✗", Color::LightRed)
+        );
+    }
 }